@@ -0,0 +1,121 @@
+// suggestions for the word at the cursor, shown by CodeMirror in the autocompletion popup
+use wasm_bindgen::JsValue;
+
+use crate::{js::set, offsets};
+
+// decides the icon next to the label
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompletionKind {
+    Class,
+    Constant,
+    Function,
+    Method,
+    Module,
+    Property,
+    Type,
+    Variable,
+}
+
+impl CompletionKind {
+    // the type of the completion in CodeMirror
+    fn as_str(&self) -> &'static str {
+        match self {
+            CompletionKind::Class => "class",
+            CompletionKind::Constant => "constant",
+            CompletionKind::Function => "function",
+            CompletionKind::Method => "method",
+            CompletionKind::Module => "namespace",
+            CompletionKind::Property => "property",
+            CompletionKind::Type => "type",
+            CompletionKind::Variable => "variable",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Completion {
+    pub label: String,
+    pub kind: CompletionKind,
+    // shown after the label
+    pub detail: Option<String>,
+    // shown next to the list for the selected completion
+    pub info: Option<String>,
+    // replaces the typed text instead of the label, `${}` marks where the cursor goes
+    pub snippet: Option<String>,
+    // moves the completion up or down among those that match equally well
+    pub boost: i32,
+}
+
+impl Completion {
+    pub fn new(label: impl Into<String>, kind: CompletionKind) -> Completion {
+        Completion {
+            label: label.into(),
+            kind,
+            detail: None,
+            info: None,
+            snippet: None,
+            boost: 0,
+        }
+    }
+
+    pub fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn info(mut self, info: impl Into<String>) -> Self {
+        self.info = Some(info.into());
+        self
+    }
+
+    pub fn snippet(mut self, snippet: impl Into<String>) -> Self {
+        self.snippet = Some(snippet.into());
+        self
+    }
+
+    pub fn boost(mut self, boost: i32) -> Self {
+        self.boost = boost;
+        self
+    }
+
+    fn to_js(&self, snippet_completion: &impl Fn(&str, &js_sys::Object) -> JsValue) -> JsValue {
+        let object = js_sys::Object::new();
+        set(&object, "label", self.label.as_str());
+        set(&object, "type", self.kind.as_str());
+        if let Some(detail) = &self.detail {
+            set(&object, "detail", detail.as_str());
+        }
+        if let Some(info) = &self.info {
+            set(&object, "info", info.as_str());
+        }
+        set(&object, "boost", self.boost);
+        match &self.snippet {
+            Some(snippet) => snippet_completion(snippet, &object),
+            None => object.into(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Completions {
+    // where the typed word starts, in bytes of the text
+    pub from: usize,
+    pub options: Vec<Completion>,
+    // the pattern of the text from `from` to the cursor for which the options stay valid while
+    // typing, so that they are filtered instead of asked for again
+    pub valid_for: &'static str,
+}
+
+impl Completions {
+    // the result that the completion sources of CodeMirror return, snippets are made into
+    // completions by `snippetCompletion` of CodeMirror, which only the frontend can import
+    pub fn to_js(&self, text: &str, snippet_completion: impl Fn(&str, &js_sys::Object) -> JsValue) -> JsValue {
+        let object = js_sys::Object::new();
+        set(&object, "from", offsets::utf16_offset(text, self.from) as u32);
+        set(&object, "options", self.options.iter()
+            .map(|completion| completion.to_js(&snippet_completion))
+            .collect::<js_sys::Array>());
+        set(&object, "validFor", js_sys::RegExp::new(self.valid_for, ""));
+        object.into()
+    }
+}
@@ -0,0 +1,50 @@
+// problems found in a file, shown by CodeMirror as squiggles and markers in the gutter
+use std::ops::Range;
+
+use crate::{js::set, offsets};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+// the range is in bytes of the text until the diagnostics are handed to CodeMirror
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub range: Range<usize>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn error(range: Range<usize>, message: impl Into<String>) -> Diagnostic {
+        Diagnostic { range, severity: Severity::Error, message: message.into() }
+    }
+
+    pub fn warning(range: Range<usize>, message: impl Into<String>) -> Diagnostic {
+        Diagnostic { range, severity: Severity::Warning, message: message.into() }
+    }
+}
+
+// the diagnostics in the form that the lint sources of CodeMirror return
+pub fn to_js(text: &str, diagnostics: &[Diagnostic]) -> js_sys::Array {
+    let ranges = diagnostics.iter().map(|diagnostic| diagnostic.range.clone()).collect::<Vec<_>>();
+    diagnostics.iter().zip(offsets::utf16_ranges(text, &ranges)).map(|(diagnostic, range)| {
+        let object = js_sys::Object::new();
+        set(&object, "from", range.start as u32);
+        set(&object, "to", range.end as u32);
+        set(&object, "severity", diagnostic.severity.as_str());
+        set(&object, "message", diagnostic.message.as_str());
+        object
+    }).collect()
+}
@@ -0,0 +1,6 @@
+// the objects that are handed to CodeMirror are built field by field
+use wasm_bindgen::JsValue;
+
+pub fn set(object: &JsValue, key: &str, value: impl Into<JsValue>) {
+    js_sys::Reflect::set(object, &key.into(), &value.into()).unwrap();
+}
@@ -0,0 +1,6 @@
+// what the frontend and the python crate both hand to CodeMirror, the crates work with byte
+// offsets into the text and CodeMirror with UTF-16 code units, so the conversions live here too
+pub mod completions;
+pub mod diagnostics;
+pub mod js;
+pub mod offsets;
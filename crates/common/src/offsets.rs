@@ -0,0 +1,73 @@
+// positions in CodeMirror are in UTF-16 code units and offsets in the crates are in bytes
+use std::ops::Range;
+
+// converts a position of CodeMirror into bytes of the text
+pub fn byte_offset(text: &str, position: u32) -> usize {
+    let mut units = 0;
+    for (offset, character) in text.char_indices() {
+        if units >= position as usize {
+            return offset;
+        }
+        units += character.len_utf16();
+    }
+    text.len()
+}
+
+pub fn utf16_offset(text: &str, offset: usize) -> usize {
+    text[..offset].encode_utf16().count()
+}
+
+// converts many byte ranges at once, the text is only read once
+pub fn utf16_ranges(text: &str, ranges: &[Range<usize>]) -> Vec<Range<usize>> {
+    let mut offsets = ranges.iter()
+        .flat_map(|range| [range.start, range.end])
+        .collect::<Vec<_>>();
+    offsets.sort_unstable();
+    offsets.dedup();
+    let mut converted = Vec::with_capacity(offsets.len());
+    let (mut byte, mut unit) = (0, 0);
+    for &offset in &offsets {
+        unit += text[byte..offset].encode_utf16().count();
+        byte = offset;
+        converted.push(unit);
+    }
+    let convert = |offset: usize| converted[offsets.binary_search(&offset).unwrap()];
+    ranges.iter()
+        .map(|range| convert(range.start)..convert(range.end))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets() {
+        let text = "ä😀b";
+        assert_eq!(byte_offset(text, 0), 0);
+        assert_eq!(byte_offset(text, 1), 2);
+        assert_eq!(byte_offset(text, 3), 6);
+        assert_eq!(byte_offset(text, 9), text.len());
+        assert_eq!(utf16_offset(text, 6), 3);
+    }
+
+    #[test]
+    fn ascii_ranges_are_unchanged() {
+        assert_eq!(utf16_ranges("abc def", &[4..7, 0..3]), [4..7, 0..3]);
+    }
+
+    #[test]
+    fn non_ascii_ranges() {
+        // `ä` is two bytes and one unit, `😀` is four bytes and two units
+        let text = "ä = '😀'; x";
+        let x = text.find('x').unwrap();
+        let emoji = text.find('😀').unwrap();
+        assert_eq!(utf16_ranges(text, &[x..x + 1, emoji..emoji + 4, 0..2]), [10..11, 5..7, 0..1]);
+    }
+
+    #[test]
+    fn shared_and_empty_ranges() {
+        let text = "é é";
+        assert_eq!(utf16_ranges(text, &[0..2, 2..2, 0..text.len()]), [0..1, 1..1, 0..3]);
+    }
+}
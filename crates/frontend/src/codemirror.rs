@@ -0,0 +1,273 @@
+// parts of the CodeMirror API that codemirror-sys does not cover, the types are structural, i.e.,
+// the values from codemirror-sys are cast to them with `unchecked_ref`
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    pub type View;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn state(this: &View) -> State;
+
+    #[wasm_bindgen(method)]
+    pub fn dispatch(this: &View, transaction: &JsValue);
+
+    #[wasm_bindgen(method)]
+    pub fn focus(this: &View);
+
+    #[wasm_bindgen(method, getter, js_name = scrollDOM)]
+    pub fn scroll_dom(this: &View) -> web_sys::HtmlElement;
+
+    #[wasm_bindgen(method, js_name = requestMeasure)]
+    pub fn request_measure(this: &View);
+
+    #[wasm_bindgen(method, getter, js_name = hasFocus)]
+    pub fn has_focus(this: &View) -> bool;
+
+    // removes the view from the document and its listeners, e.g., from the window
+    #[wasm_bindgen(method)]
+    pub fn destroy(this: &View);
+
+    pub type Update;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn state(this: &Update) -> State;
+
+    #[wasm_bindgen(method, getter, js_name = selectionSet)]
+    pub fn selection_set(this: &Update) -> bool;
+
+    #[wasm_bindgen(method, getter, js_name = focusChanged)]
+    pub fn focus_changed(this: &Update) -> bool;
+
+    #[wasm_bindgen(method, getter, js_name = docChanged)]
+    pub fn doc_changed(this: &Update) -> bool;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn view(this: &Update) -> View;
+
+    // a ChangeSet, which can be dispatched to another view of the same document
+    #[wasm_bindgen(method, getter)]
+    pub fn changes(this: &Update) -> JsValue;
+
+    pub type State;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn selection(this: &State) -> Selection;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn doc(this: &State) -> Text;
+
+    pub type Text;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn length(this: &Text) -> u32;
+
+    #[wasm_bindgen(method, js_name = toString)]
+    pub fn text(this: &Text) -> String;
+
+    pub type Selection;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn main(this: &Selection) -> SelectionRange;
+
+    pub type SelectionRange;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn head(this: &SelectionRange) -> u32;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn anchor(this: &SelectionRange) -> u32;
+
+    #[wasm_bindgen(method, js_name = lineAt)]
+    pub fn line_at(this: &Text, position: u32) -> Line;
+
+    pub type Line;
+
+    // starting at 1
+    #[wasm_bindgen(method, getter)]
+    pub fn number(this: &Line) -> u32;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn from(this: &Line) -> u32;
+
+    pub type Compartment;
+
+    // the effect that replaces the contents of the compartment
+    #[wasm_bindgen(method)]
+    pub fn reconfigure(this: &Compartment, extension: &JsValue) -> JsValue;
+
+    pub type RangeSet;
+
+    // calls the function with the start and end of every range that touches from..to
+    #[wasm_bindgen(method)]
+    pub fn between(this: &RangeSet, from: u32, to: u32, f: &mut dyn FnMut(u32, u32));
+
+    // what the completion sources of the autocompletion are called with
+    pub type CompletionContext;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn state(this: &CompletionContext) -> State;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn pos(this: &CompletionContext) -> u32;
+
+    // whether the completions were asked for, e.g., with Ctrl+Space, instead of while typing
+    #[wasm_bindgen(method, getter)]
+    pub fn explicit(this: &CompletionContext) -> bool;
+
+    pub type StateEffectType;
+
+    #[wasm_bindgen(method)]
+    pub fn of(this: &StateEffectType, value: &JsValue) -> JsValue;
+
+    pub type StateEffect;
+
+    #[wasm_bindgen(method)]
+    pub fn is(this: &StateEffect, effect_type: &StateEffectType) -> bool;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn value(this: &StateEffect) -> JsValue;
+
+    // what the update functions of state fields are called with
+    pub type Transaction;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn effects(this: &Transaction) -> js_sys::Array;
+
+    #[wasm_bindgen(method, getter, js_name = docChanged)]
+    pub fn doc_changed(this: &Transaction) -> bool;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn changes(this: &Transaction) -> ChangeSet;
+
+    pub type ChangeSet;
+
+    // where a position of the old document is in the new one
+    #[wasm_bindgen(method, js_name = mapPos)]
+    pub fn map_pos(this: &ChangeSet, position: u32) -> u32;
+}
+
+#[wasm_bindgen(module = "@codemirror/language")]
+extern "C" {
+    #[wasm_bindgen(js_name = foldedRanges)]
+    pub fn folded_ranges(state: &State) -> RangeSet;
+
+    #[wasm_bindgen(js_name = foldEffect)]
+    pub static FOLD_EFFECT: StateEffectType;
+
+    // the text that is inserted for one level of indentation
+    #[wasm_bindgen(js_name = indentUnit)]
+    pub static INDENT_UNIT: Facet;
+}
+
+#[wasm_bindgen(module = "@codemirror/state")]
+extern "C" {
+    pub type Facet;
+
+    #[wasm_bindgen(method)]
+    pub fn of(this: &Facet, value: &JsValue) -> JsValue;
+
+    // provides the value of a state field to the facet
+    #[wasm_bindgen(method, js_name = from)]
+    pub fn from_field(this: &Facet, field: &JsValue) -> JsValue;
+
+    // takes an object with the `create` and `update` functions and what the field `provide`s
+    #[wasm_bindgen(js_namespace = StateField, js_name = define)]
+    pub fn define_state_field(config: &JsValue) -> JsValue;
+
+    #[wasm_bindgen(js_namespace = StateEffect, js_name = define)]
+    pub fn define_state_effect() -> StateEffectType;
+
+    #[wasm_bindgen(js_namespace = EditorState, js_name = tabSize)]
+    pub static TAB_SIZE: Facet;
+
+    // takes a function that returns objects, e.g., with an `autocomplete` source
+    #[wasm_bindgen(js_namespace = EditorState, js_name = languageData)]
+    pub static LANGUAGE_DATA: Facet;
+
+    #[wasm_bindgen(js_namespace = EditorState, js_name = readOnly)]
+    pub static READ_ONLY: Facet;
+}
+
+// the options of the view that codemirror-sys does not cover
+#[wasm_bindgen(module = "@codemirror/view")]
+extern "C" {
+    // takes an object with a `formatNumber` function
+    #[wasm_bindgen(js_name = lineNumbers)]
+    pub fn line_numbers_with(config: &JsValue) -> JsValue;
+
+    #[wasm_bindgen(js_name = highlightWhitespace)]
+    pub fn highlight_whitespace() -> JsValue;
+
+    #[wasm_bindgen(js_name = highlightTrailingWhitespace)]
+    pub fn highlight_trailing_whitespace() -> JsValue;
+
+    #[wasm_bindgen(js_namespace = EditorView, js_name = lineWrapping)]
+    pub static LINE_WRAPPING: JsValue;
+
+    #[wasm_bindgen(js_namespace = EditorView)]
+    pub fn theme(spec: &JsValue) -> JsValue;
+
+    // the source is called with the view, the position under the mouse and the side of the
+    // position and returns a tooltip, a promise of one, or null
+    #[wasm_bindgen(js_name = hoverTooltip)]
+    pub fn hover_tooltip(source: &JsValue) -> JsValue;
+
+    // the tooltips that are shown, e.g., provided by a state field
+    #[wasm_bindgen(js_name = showTooltip)]
+    pub static SHOW_TOOLTIP: Facet;
+
+    #[wasm_bindgen(js_namespace = EditorView, js_name = editable)]
+    pub static EDITABLE: Facet;
+}
+
+// the position of the primary cursor
+pub fn cursor(state: &State) -> usize {
+    state.selection().main().head() as usize
+}
+
+#[wasm_bindgen(module = "@codemirror/lint")]
+extern "C" {
+    // the source is called with the view and returns the diagnostics or a promise of them
+    pub fn linter(source: &JsValue) -> JsValue;
+
+    #[wasm_bindgen(js_name = lintGutter)]
+    pub fn lint_gutter() -> JsValue;
+
+    // runs the linter of the view again, e.g., after something that it checks against changed
+    #[wasm_bindgen(js_name = forceLinting)]
+    pub fn force_linting(view: &View);
+}
+
+#[wasm_bindgen(module = "@codemirror/autocomplete")]
+extern "C" {
+    // a completion that inserts the template, where `${}` marks the fields
+    #[wasm_bindgen(js_name = snippetCompletion)]
+    pub fn snippet_completion(template: &str, completion: &js_sys::Object) -> JsValue;
+}
+
+// the language support that codemirror-sys only has for Python
+#[wasm_bindgen(module = "@codemirror/lang-xml")]
+extern "C" {
+    pub fn xml() -> JsValue;
+}
+
+#[wasm_bindgen(module = "@codemirror/lang-json")]
+extern "C" {
+    pub fn json() -> JsValue;
+}
+
+#[wasm_bindgen(module = "@codemirror/lang-yaml")]
+extern "C" {
+    pub fn yaml() -> JsValue;
+}
+
+#[wasm_bindgen(module = "@codemirror/lang-markdown")]
+extern "C" {
+    pub fn markdown() -> JsValue;
+}
+
+#[wasm_bindgen(module = "@codemirror/lang-cpp")]
+extern "C" {
+    pub fn cpp() -> JsValue;
+}
@@ -0,0 +1,269 @@
+// a menu that is navigated with the pointer or the keyboard and that is closed by Escape, by
+// activating an item or by pressing the pointer outside of it
+use std::rc::Rc;
+
+use dominator::{clone, events, html, with_node, Dom, EventOptions};
+use futures_signals::signal::{Mutable, SignalExt};
+use wasm_bindgen::JsCast;
+use web_sys::HtmlElement;
+
+use crate::styles;
+
+#[derive(Clone)]
+pub enum Entry {
+    Item(Item),
+    Separator,
+}
+
+#[derive(Clone)]
+pub struct Item {
+    pub label: String,
+    pub keybinding: Option<&'static str>,
+    pub enabled: bool,
+    // shows a check mark in front of the label, e.g., for the current choice of a setting
+    pub checked: bool,
+    action: Option<Rc<dyn Fn()>>,
+    submenu: Vec<Entry>,
+}
+
+impl Item {
+    pub fn new(label: impl Into<String>, action: impl Fn() + 'static) -> Item {
+        Item {
+            label: label.into(),
+            keybinding: None,
+            enabled: true,
+            checked: false,
+            action: Some(Rc::new(action)),
+            submenu: Vec::new(),
+        }
+    }
+
+    pub fn submenu(label: impl Into<String>, submenu: Vec<Entry>) -> Item {
+        Item {
+            label: label.into(),
+            keybinding: None,
+            enabled: true,
+            checked: false,
+            action: None,
+            submenu,
+        }
+    }
+
+    pub fn keybinding(mut self, keybinding: Option<&'static str>) -> Item {
+        self.keybinding = keybinding;
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Item {
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn checked(mut self, checked: bool) -> Item {
+        self.checked = checked;
+        self
+    }
+}
+
+impl Entry {
+    fn is_selectable(&self) -> bool {
+        matches!(self, Entry::Item(item) if item.enabled)
+    }
+}
+
+// the next entry in the direction that can be selected, wrapping around at the ends
+fn step(entries: &[Entry], from: Option<usize>, forward: bool) -> Option<usize> {
+    let count = entries.len();
+    let mut index = from;
+    for _ in 0..count {
+        let next = match (index, forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(index), true) => (index + 1) % count,
+            (Some(index), false) => (index + count - 1) % count,
+        };
+        if entries[next].is_selectable() {
+            return Some(next);
+        }
+        index = Some(next);
+    }
+    None
+}
+
+fn window_size() -> (f64, f64) {
+    let window = web_sys::window().unwrap();
+    let size = |size: Result<wasm_bindgen::JsValue, _>| size.ok()
+        .and_then(|size| size.as_f64())
+        .unwrap_or(0.0);
+    (size(window.inner_width()), size(window.inner_height()))
+}
+
+// moves the menu back inside the window, submenus open to the left instead if there is no room on
+// the right
+fn clamp_to_window(element: &HtmlElement, position: Option<(i32, i32)>) {
+    let (width, height) = window_size();
+    let rect = element.get_bounding_client_rect();
+    let overflow_x = (rect.right() - width).max(0.0).min(rect.left());
+    let overflow_y = (rect.bottom() - height).max(0.0).min(rect.top());
+    let style = element.style();
+    match position {
+        Some((x, y)) => {
+            let _ = style.set_property("left", &format!("{}px", x as f64 - overflow_x));
+            let _ = style.set_property("top", &format!("{}px", y as f64 - overflow_y));
+        }
+        None => {
+            if overflow_x > 0.0 {
+                let _ = style.set_property("left", "auto");
+                let _ = style.set_property("right", "100%");
+            }
+            let _ = style.set_property("top", &format!("{}px", -overflow_y));
+        }
+    }
+}
+
+pub fn render(entries: Vec<Entry>, position: (i32, i32), close: Rc<dyn Fn()>) -> Dom {
+    render_menu(Rc::new(entries), Some(position), close.clone(), close)
+}
+
+// `close` closes the whole menu while `close_self` only closes a submenu and returns to its parent
+fn render_menu(
+    entries: Rc<Vec<Entry>>,
+    position: Option<(i32, i32)>,
+    close: Rc<dyn Fn()>,
+    close_self: Rc<dyn Fn()>
+) -> Dom {
+    let active = Mutable::new(None);
+    let open_submenu = Mutable::new(None);
+    html!("div", {
+        .apply(|dom| match position {
+            Some((x, y)) => styles::contextmenu::body(dom, &x, &y),
+            None => styles::contextmenu::submenu(dom),
+        })
+        .attr("role", "menu")
+        .attr("tabindex", "-1")
+        .class("outline-none")
+        .focused(true)
+        .after_inserted(move |element| clamp_to_window(&element, position))
+        .with_node!(element => {
+            .event_with_options(&EventOptions::preventable(), clone!(entries, active, open_submenu, close => move |event: events::KeyDown| {
+                // a submenu is inside of its parent, which would handle the key again
+                event.stop_propagation();
+                match event.key().as_str() {
+                    "ArrowDown" => active.set(step(&entries, active.get(), true)),
+                    "ArrowUp" => active.set(step(&entries, active.get(), false)),
+                    "Home" => active.set(step(&entries, None, true)),
+                    "End" => active.set(step(&entries, None, false)),
+                    "ArrowRight" | "Enter" | " " => match active.get().map(|index| (index, &entries[index])) {
+                        Some((index, Entry::Item(item))) if !item.submenu.is_empty() => open_submenu.set(Some(index)),
+                        Some((_, Entry::Item(item))) if event.key() != "ArrowRight" => {
+                            close();
+                            if let Some(action) = &item.action {
+                                action();
+                            }
+                        }
+                        _ => {}
+                    },
+                    "ArrowLeft" if position.is_none() => close_self(),
+                    "Escape" => close_self(),
+                    "Tab" => close(),
+                    _ => return,
+                }
+                event.prevent_default();
+            }))
+            // only the outermost menu needs to listen, submenus are inside of it
+            .apply_if(position.is_some(), clone!(close, element => move |dom| {
+                dom.global_event(move |event: events::PointerDown| {
+                    let inside = event.target()
+                        .and_then(|target| target.dyn_into::<web_sys::Node>().ok())
+                        .is_some_and(|target| element.contains(Some(&target)));
+                    if !inside {
+                        close();
+                    }
+                })
+            }))
+            .children(entries.iter().enumerate().map(|(index, entry)| match entry {
+                Entry::Separator => html!("div", {
+                    .apply(styles::contextmenu::separator)
+                }),
+                Entry::Item(item) => {
+                    // returns the keyboard focus to this menu once the submenu is closed
+                    let close_submenu: Rc<dyn Fn()> = Rc::new(clone!(open_submenu, element => move || {
+                        open_submenu.set(None);
+                        let _ = element.focus();
+                    }));
+                    render_item(item, index, &active, &open_submenu, &close, close_submenu)
+                }
+            }))
+        })
+    })
+}
+
+fn render_item(
+    item: &Item,
+    index: usize,
+    active: &Mutable<Option<usize>>,
+    open_submenu: &Mutable<Option<usize>>,
+    close: &Rc<dyn Fn()>,
+    close_submenu: Rc<dyn Fn()>
+) -> Dom {
+    let item = item.clone();
+    let has_submenu = !item.submenu.is_empty();
+    html!("div", {
+        .apply(|dom| styles::contextmenu::item(dom, active.signal_ref(move |active| *active == Some(index)), item.enabled))
+        .attr("role", "menuitem")
+        .attr("aria-disabled", if item.enabled { "false" } else { "true" })
+        .apply_if(item.checked, |dom| dom.child(html!("span", {
+            .class("absolute")
+            .class("left-1")
+            .text("✓")
+        })))
+        .child(html!("div", {
+            .class("flex")
+            .class("justify-between")
+            .child(html!("span", {
+                .text(&item.label)
+            }))
+            .apply(|dom| match (item.keybinding, has_submenu) {
+                (_, true) => dom.child(html!("span", {
+                    .apply(styles::contextmenu::keybinding)
+                    .text("›")
+                })),
+                (Some(keybinding), false) => dom.child(html!("span", {
+                    .apply(styles::contextmenu::keybinding)
+                    .text(keybinding)
+                })),
+                (None, false) => dom,
+            })
+        }))
+        .event(clone!(active, open_submenu, item => move |_: events::PointerEnter| {
+            if item.enabled {
+                active.set(Some(index));
+                open_submenu.set(has_submenu.then_some(index));
+            } else {
+                active.set(None);
+                open_submenu.set(None);
+            }
+        }))
+        .event(clone!(open_submenu, close, item => move |event: events::Click| {
+            event.stop_propagation();
+            if !item.enabled {
+                return;
+            }
+            match &item.action {
+                Some(action) => {
+                    close();
+                    action();
+                }
+                None => open_submenu.set(Some(index)),
+            }
+        }))
+        .child_signal(open_submenu.signal().map(clone!(close, item => move |open| {
+            (open == Some(index)).then(|| render_menu(
+                Rc::new(item.submenu.clone()),
+                None,
+                close.clone(),
+                close_submenu.clone()
+            ))
+        })))
+    })
+}
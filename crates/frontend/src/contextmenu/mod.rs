@@ -1,13 +1,68 @@
 use std::rc::Rc;
-use dominator::{Dom, html, clone, events};
+use dominator::{clone, Dom};
+use futures_signals::{map_ref, signal::SignalExt};
 use crate::sidebar::explorer::RENAME;
-use crate::styles;
-use crate::{vfs::{Directory, File}, DEFAULT_DIRECTORY_MODE, DEFAULT_FILE_MODE};
+use crate::{vfs::{self, Directory, File}, DEFAULT_DIRECTORY_MODE, DEFAULT_FILE_MODE};
+use registry::MenuItem;
+use templates::{Template, TEMPLATES};
+
+pub mod menu;
+pub mod registry;
+mod templates;
+mod when;
+
 #[derive(Clone)]
 pub enum Target {
     File(Rc<File>), 
     Directory(Rc<Directory>)
 }
+
+impl Target {
+    pub fn ptr_eq(&self, other: &Target) -> bool {
+        match (self, other) {
+            (Target::File(left), Target::File(right)) => Rc::ptr_eq(left, right),
+            (Target::Directory(left), Target::Directory(right)) => Rc::ptr_eq(left, right),
+            _ => false
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Target::File(file) => file.name.get_cloned(),
+            Target::Directory(directory) => directory.name.get_cloned(),
+        }
+    }
+
+    // the directories from the root down to and including the parent of the target
+    pub fn ancestors(&self, root: &Rc<Directory>) -> Option<Vec<Rc<Directory>>> {
+        match self {
+            Target::File(file) => vfs::ancestors_of_file(root, file),
+            Target::Directory(directory) => vfs::ancestors_of_directory(root, directory),
+        }
+    }
+
+    // the path of the target relative to the root, which is empty for the root itself
+    pub fn relative_path(&self, root: &Rc<Directory>) -> Option<String> {
+        let ancestors = self.ancestors(root)?;
+        let is_root = matches!(self, Target::Directory(directory) if Rc::ptr_eq(directory, root));
+        Some(ancestors.iter()
+            .skip(1)
+            .map(|directory| directory.name.get_cloned())
+            .chain((!is_root).then(|| self.name()))
+            .collect::<Vec<_>>()
+            .join("/"))
+    }
+
+    // the path of the target including the root, e.g., /project/velocity_control/run.py
+    pub fn path(&self, root: &Rc<Directory>) -> Option<String> {
+        let relative_path = self.relative_path(root)?;
+        let root_name = root.name.get_cloned();
+        Some(match relative_path.is_empty() {
+            true => format!("/{root_name}"),
+            false => format!("/{root_name}/{relative_path}"),
+        })
+    }
+}
 #[derive(Clone)]
 pub struct ContextMenu {
     // visibility and position of contextmenu
@@ -24,71 +79,15 @@ impl ContextMenu {
         }
     }
 
-    // rendering context menu for folder
-    pub fn folder_menu_render(
-        context_menu: &ContextMenu
-    ) -> Dom {
-        html!("div", {
-                .apply(|dom| styles::contextmenu::body(dom, &context_menu.position.0, &context_menu.position.1))
-                .children(&mut [
-                    html!("div", {
-                        .text("New Folder")
-                        .apply(styles::contextmenu::option)
-                        .event(clone!(context_menu => move |_event: events::MouseDown| {
-                            web_sys::console::log_1(&"New Folder Created".into());
-                            context_menu.add_folder();
-                        }))
-                    }), 
-                    html!("div", {
-                        .text("New File")
-                        .apply(styles::contextmenu::option)
-                        .event(clone!(context_menu => move |_event: events::MouseDown| {
-                            web_sys::console::log_1(&"New File Created".into());
-                            context_menu.add_file();
-                        }))
-                    }),
-                    html!("div", {
-                        .text("Rename Folder")
-                        .apply(styles::contextmenu::option)
-                        .event(clone!(context_menu => move |_event: events::MouseDown| {
-                            web_sys::console::log_1(&"Renaming Folder".into());
-                            if let Target::Directory(dir) = &context_menu.target  {
-                                RENAME.with(|rename| {
-                                    rename.set(Some(Target::Directory(dir.clone())));
-                                });
-                            }
-                        }))
-                    })
-                ])
-            })
-    }
-    
-    pub fn file_menu_render(
-        context_menu: &ContextMenu
-    ) -> Dom {
-        html!("div", {
-            .apply(|dom| styles::contextmenu::body(dom, &context_menu.position.0, &context_menu.position.1))
-            .children(&mut [
-                html!("div", {
-                    .text("Rename File")
-                    .apply(styles::contextmenu::option)
-                    .event(clone!(context_menu => move |_event: events::MouseDown| {
-                        web_sys::console::log_1(&"Renaming File".into());
-                        if let Target::File(file) = &context_menu.target  {
-                            RENAME.with(|rename| {
-                                rename.set(Some(Target::File(file.clone())));
-                            });
-                        }
-                    }))
-                })
-            ])
-        })
+    pub fn render(context_menu: &ContextMenu, close: Rc<dyn Fn()>) -> Dom {
+        let entries = crate::PROJECT.with(|root| registry::menu(&context_menu.target, root));
+        menu::render(entries, context_menu.position, close)
     }
 
     // to add folder under a folder
     pub fn add_folder(
-        &self
-    ) -> () {
+        target: &Target
+    ) {
         // initialise a new directory
         let new_directory = Rc::new(
             Directory {
@@ -100,7 +99,7 @@ impl ContextMenu {
         );
 
         // to access the target directory for modification
-        if let Target::Directory(dir) = &self.target {
+        if let Target::Directory(dir) = target {
             dir.directories.lock_mut().push_cloned(new_directory.clone());
             // this signals renaming after creating and pushing it into the directory structure
             RENAME.with(|rename| {
@@ -109,21 +108,58 @@ impl ContextMenu {
         } 
     }
 
+    // adds a file with the template filled in under a folder and lets the user rename it, the
+    // contents follow the name until the renaming ends
+    pub fn add_file_from_template(target: &Target, template: &'static Template) {
+        let Target::Directory(dir) = target else {
+            return;
+        };
+        // the top-level directory that contains the folder is the package
+        let package = crate::PROJECT.with(|root| vfs::ancestors_of_directory(root, dir))
+            .and_then(|ancestors| match ancestors.len() {
+                1 => Some(dir.clone()),
+                _ => ancestors.get(1).cloned(),
+            })
+            .map(|package| package.name.get_cloned());
+        let (mut file_name, mut contents) = template.instantiate("new node", package.as_deref()).unwrap();
+        let mut index = 1;
+        while dir.contains_name(&file_name) {
+            index += 1;
+            (file_name, contents) = template.instantiate(&format!("new node {index}"), package.as_deref()).unwrap();
+        }
+        let file = Rc::new(File::new(&file_name, DEFAULT_FILE_MODE, contents.into_bytes()));
+        dir.files.lock_mut().push_cloned(file.clone());
+        RENAME.with(|rename| rename.set(Some(Target::File(file.clone()))));
+        let renaming = RENAME.with(|rename| rename.signal_ref(clone!(file => move |rename| {
+            matches!(rename, Some(Target::File(renamed)) if Rc::ptr_eq(renamed, &file))
+        })));
+        let follow = map_ref! {
+            let name = file.name.signal_cloned(),
+            let renaming = renaming => (name.clone(), *renaming)
+        };
+        wasm_bindgen_futures::spawn_local(follow
+            .stop_if(|(_, renaming)| !renaming)
+            .for_each(clone!(file => move |(name, renaming)| {
+                let instance = template.name_of(&name)
+                    .and_then(|name| template.instantiate(name, package.as_deref()));
+                if let (true, Some((_, contents))) = (renaming, instance) {
+                    file.write(contents.into_bytes());
+                }
+                async {}
+            })));
+    }
+
     // to add file under a folder
     pub fn add_file(
-        &self
-    ) -> () {
+        target: &Target
+    ) {
         // initialise a new file
         let new_file = Rc::new(
-            File {
-                name: "Placeholder".to_owned().into(),
-                mode: DEFAULT_FILE_MODE.into(),
-                data: "Placeholder".as_bytes().to_vec().into()
-            }
+            File::new("Placeholder", DEFAULT_FILE_MODE, "Placeholder".as_bytes().to_vec())
         );
 
         // to access the target directory for modification
-        if let Target::Directory(dir) = &self.target {
+        if let Target::Directory(dir) = target {
             dir.files.lock_mut().push_cloned(new_file.clone());
             // this signals renaming after creating and pushing it into the directory structure
             RENAME.with(|rename| {
@@ -131,4 +167,40 @@ impl ContextMenu {
             });
         }
     }
-}
\ No newline at end of file
+}
+
+// the entries for creating and renaming files and folders
+pub fn register_menu_items() {
+    registry::register(MenuItem::new("New Folder")
+        .group("1_new", 1)
+        .when("isFolder")
+        .enabled_when("!isReadonly")
+        .action(ContextMenu::add_folder));
+    registry::register(MenuItem::new("New File")
+        .group("1_new", 2)
+        .when("isFolder")
+        .enabled_when("!isReadonly")
+        .action(ContextMenu::add_file));
+    registry::register(MenuItem::new("New File from Template…")
+        .group("1_new", 3)
+        .when("isFolder")
+        .enabled_when("!isReadonly")
+        .submenu(TEMPLATES.iter()
+            .enumerate()
+            .map(|(index, template)| MenuItem::new(template.label)
+                .group("templates", index as i32)
+                .action(|target| ContextMenu::add_file_from_template(target, template)))
+            .collect()));
+    registry::register(MenuItem::new("Rename Folder")
+        .group("3_modification", 1)
+        .keybinding("F2")
+        .when("isFolder && !isRoot")
+        .enabled_when("!isReadonly")
+        .action(|target| RENAME.with(|rename| rename.set(Some(target.clone())))));
+    registry::register(MenuItem::new("Rename File")
+        .group("3_modification", 1)
+        .keybinding("F2")
+        .when("isFile")
+        .enabled_when("!isReadonly")
+        .action(|target| RENAME.with(|rename| rename.set(Some(target.clone())))));
+}
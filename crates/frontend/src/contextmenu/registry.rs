@@ -0,0 +1,157 @@
+// menu items are contributed by the subsystems that implement them, the menus are built from the
+// items whose condition holds for the target
+use std::{cell::RefCell, rc::Rc};
+
+use crate::vfs::Directory;
+
+use super::{menu::{Entry, Item}, when::{Context, When}, Target};
+
+#[derive(Clone)]
+pub struct MenuItem {
+    pub label: &'static str,
+    // items are shown in groups that are sorted by name, e.g., 1_new before 2_edit
+    pub group: &'static str,
+    // the position within the group
+    pub order: i32,
+    // only shown next to the label, the shortcut itself is handled by the subsystem
+    pub keybinding: Option<&'static str>,
+    when: Option<When>,
+    // shown but greyed out while the condition does not hold
+    enablement: Option<When>,
+    action: Option<Rc<dyn Fn(&Target)>>,
+    pub submenu: Vec<MenuItem>,
+}
+
+impl MenuItem {
+    pub fn new(label: &'static str) -> MenuItem {
+        MenuItem {
+            label,
+            group: "",
+            order: 0,
+            keybinding: None,
+            when: None,
+            enablement: None,
+            action: None,
+            submenu: Vec::new(),
+        }
+    }
+
+    pub fn group(mut self, group: &'static str, order: i32) -> MenuItem {
+        self.group = group;
+        self.order = order;
+        self
+    }
+
+    pub fn keybinding(mut self, keybinding: &'static str) -> MenuItem {
+        self.keybinding = Some(keybinding);
+        self
+    }
+
+    // panics on a malformed condition since conditions are written in the source
+    pub fn when(mut self, condition: &str) -> MenuItem {
+        self.when = Some(When::parse(condition).unwrap());
+        self
+    }
+
+    pub fn enabled_when(mut self, condition: &str) -> MenuItem {
+        self.enablement = Some(When::parse(condition).unwrap());
+        self
+    }
+
+    pub fn action(mut self, action: impl Fn(&Target) + 'static) -> MenuItem {
+        self.action = Some(Rc::new(action));
+        self
+    }
+
+    pub fn submenu(mut self, submenu: Vec<MenuItem>) -> MenuItem {
+        self.submenu = submenu;
+        self
+    }
+
+    fn applies_to(&self, context: &Context) -> bool {
+        self.when.as_ref().is_none_or(|when| when.evaluate(context))
+    }
+
+    fn is_enabled(&self, context: &Context) -> bool {
+        self.enablement.as_ref().is_none_or(|enablement| enablement.evaluate(context))
+    }
+
+    // the entry of the menu for the target, running the action on it
+    fn entry(&self, target: &Target, root: &Rc<Directory>, context: &Context) -> Entry {
+        let item = match self.submenu.is_empty() {
+            true => {
+                let action = self.action.clone();
+                let target = target.clone();
+                Item::new(self.label, move || {
+                    if let Some(action) = &action {
+                        action(&target);
+                    }
+                })
+            }
+            false => Item::submenu(self.label, entries(&self.submenu, target, root)),
+        };
+        Entry::Item(item
+            .keybinding(self.keybinding)
+            .enabled(self.is_enabled(context)))
+    }
+}
+
+thread_local! {
+    static MENU_ITEMS: RefCell<Vec<MenuItem>> = RefCell::new(Vec::new());
+}
+
+pub fn register(item: MenuItem) {
+    MENU_ITEMS.with(|items| items.borrow_mut().push(item));
+}
+
+// the groups of items that apply to the target in the order they are shown
+fn groups(items: &[MenuItem], target: &Target, root: &Rc<Directory>) -> Vec<Vec<MenuItem>> {
+    let context = Context::new(target, root);
+    let mut items = items.iter()
+        .filter(|item| item.applies_to(&context))
+        .cloned()
+        .collect::<Vec<_>>();
+    items.sort_by(|left, right| (left.group, left.order).cmp(&(right.group, right.order)));
+    let mut groups: Vec<Vec<MenuItem>> = Vec::new();
+    for item in items {
+        match groups.last_mut() {
+            Some(group) if group[0].group == item.group => group.push(item),
+            _ => groups.push(vec![item]),
+        }
+    }
+    groups
+}
+
+// the entries for the items that apply to the target with separators between the groups
+pub fn entries(items: &[MenuItem], target: &Target, root: &Rc<Directory>) -> Vec<Entry> {
+    let context = Context::new(target, root);
+    let mut entries = Vec::new();
+    for (index, group) in groups(items, target, root).iter().enumerate() {
+        if index > 0 {
+            entries.push(Entry::Separator);
+        }
+        entries.extend(group.iter().map(|item| item.entry(target, root, &context)));
+    }
+    entries
+}
+
+// runs the item with the keybinding, e.g., `Ctrl+X`, if it applies to the target and is enabled,
+// so that shortcuts are bound by the same conditions as the menu, returns whether an item ran
+pub fn run_keybinding(keybinding: &str, target: &Target, root: &Rc<Directory>) -> bool {
+    let context = Context::new(target, root);
+    let action = MENU_ITEMS.with(|items| items.borrow()
+        .iter()
+        .find(|item| item.keybinding == Some(keybinding) && item.applies_to(&context) && item.is_enabled(&context))
+        .and_then(|item| item.action.clone()));
+    match action {
+        Some(action) => {
+            action(target);
+            true
+        }
+        None => false,
+    }
+}
+
+pub fn menu(target: &Target, root: &Rc<Directory>) -> Vec<Entry> {
+    MENU_ITEMS.with(|items| entries(&items.borrow(), target, root))
+}
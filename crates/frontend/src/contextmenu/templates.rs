@@ -0,0 +1,197 @@
+// templates for new files, `{{ClassName}}` and `{{node_name}}` are replaced with the name that the
+// user enters in the class and node name conventions respectively and `{{package}}` with the
+// package that the file is created in, or the node name in the root of the project
+pub struct Template {
+    pub label: &'static str,
+    // the file name with the same placeholders as the contents
+    file_name: &'static str,
+    contents: &'static str,
+}
+
+pub static TEMPLATES: [Template; 4] = [
+    Template {
+        label: "rclpy Publisher Node",
+        file_name: "{{node_name}}.py",
+        contents: PUBLISHER,
+    },
+    Template {
+        label: "rclpy Subscriber Node",
+        file_name: "{{node_name}}.py",
+        contents: SUBSCRIBER,
+    },
+    Template {
+        label: "Launch File",
+        file_name: "{{node_name}}.launch.xml",
+        contents: LAUNCH,
+    },
+    Template {
+        label: "Message Definition",
+        file_name: "{{ClassName}}.msg",
+        contents: MESSAGE,
+    },
+];
+
+const PUBLISHER: &str = "\
+import rclpy
+
+from rclpy.node import Node
+from std_msgs.msg import String
+
+class {{ClassName}}(Node):
+    def __init__(self):
+        super().__init__('{{node_name}}')
+        self.publisher_ = \\
+            self.create_publisher(String, 'topic', 10)
+        timer_period = 0.5  # seconds
+        self.timer = \\
+            self.create_timer(timer_period, self.timer_callback)
+        self.count = 0
+    def timer_callback(self):
+        message = String()
+        message.data = f'Hello World: {self.count}'
+        self.publisher_.publish(message)
+        self.get_logger().info(f'Publishing: {message.data}')
+        self.count += 1
+
+rclpy.init()
+{{node_name}} = {{ClassName}}()
+rclpy.spin({{node_name}})
+{{node_name}}.destroy_node()
+rclpy.shutdown()
+";
+
+const SUBSCRIBER: &str = "\
+import rclpy
+
+from rclpy.node import Node
+from std_msgs.msg import String
+
+class {{ClassName}}(Node):
+    def __init__(self):
+        super().__init__('{{node_name}}')
+        self.subscription = \\
+            self.create_subscription(String, 'topic', self.listener_callback, 10)
+    def listener_callback(self, message):
+        self.get_logger().info(f'Received: {message.data}')
+
+rclpy.init()
+{{node_name}} = {{ClassName}}()
+rclpy.spin({{node_name}})
+{{node_name}}.destroy_node()
+rclpy.shutdown()
+";
+
+const LAUNCH: &str = "\
+<launch>
+  <node pkg=\"{{package}}\" exec=\"{{node_name}}\" />
+</launch>
+";
+
+const MESSAGE: &str = "\
+# {{ClassName}}
+float64 data
+";
+
+impl Template {
+    // the file name and the contents for the name, None if the name has no letters or digits or
+    // starts with a digit, which neither Python names nor ROS node names may
+    pub fn instantiate(&self, name: &str, package: Option<&str>) -> Option<(String, String)> {
+        let words = words(name);
+        if words.first().is_none_or(|word| word.starts_with(char::is_numeric)) {
+            return None;
+        }
+        let node_name = words.join("_");
+        let class_name = words.iter()
+            .map(|word| {
+                let mut characters = word.chars();
+                characters.next()
+                    .map(|first| first.to_uppercase().chain(characters).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect::<String>();
+        let substitute = |text: &str| text
+            .replace("{{ClassName}}", &class_name)
+            .replace("{{node_name}}", &node_name)
+            .replace("{{package}}", package.unwrap_or(&node_name));
+        Some((substitute(self.file_name), substitute(self.contents)))
+    }
+
+    // the name in a file name of the template, e.g., `velocity_publisher` in
+    // `velocity_publisher.launch.xml`
+    pub fn name_of<'a>(&self, file_name: &'a str) -> Option<&'a str> {
+        let (_, extension) = self.file_name.split_once("}}")?;
+        file_name.strip_suffix(extension).filter(|name| !name.is_empty())
+    }
+}
+
+// splits the name into lowercase words at separators and at the start of capitalized words, e.g.,
+// "VelocityPublisher", "velocity publisher" and "velocity_publisher" give the same words
+fn words(name: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut previous: Option<char> = None;
+    for character in name.chars() {
+        if !character.is_alphanumeric() {
+            previous = None;
+            continue;
+        }
+        let starts_word = match previous {
+            None => true,
+            Some(previous) => character.is_uppercase() && !previous.is_uppercase(),
+        };
+        match (starts_word, words.last_mut()) {
+            (false, Some(word)) => word.extend(character.to_lowercase()),
+            _ => words.push(character.to_lowercase().collect()),
+        }
+        previous = Some(character);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_of_names() {
+        for name in ["VelocityPublisher", "velocity publisher", "velocity_publisher", " Velocity-Publisher "] {
+            assert_eq!(words(name), ["velocity", "publisher"]);
+        }
+        assert_eq!(words("IMUReader"), ["imureader"]);
+        assert_eq!(words("lidar2d node"), ["lidar2d", "node"]);
+        assert!(words("_-_").is_empty());
+    }
+
+    #[test]
+    fn instantiate_publisher() {
+        let (file_name, contents) = TEMPLATES[0].instantiate("velocity publisher", Some("control")).unwrap();
+        assert_eq!(file_name, "velocity_publisher.py");
+        assert!(contents.contains("class VelocityPublisher(Node):"));
+        assert!(contents.contains("super().__init__('velocity_publisher')"));
+        assert!(!contents.contains("{{"));
+    }
+
+    #[test]
+    fn instantiate_launch_file() {
+        let (file_name, contents) = TEMPLATES[2].instantiate("talker", Some("control")).unwrap();
+        assert_eq!(file_name, "talker.launch.xml");
+        assert!(contents.contains(r#"<node pkg="control" exec="talker" />"#));
+        // in the root of the project
+        let (_, contents) = TEMPLATES[2].instantiate("talker", None).unwrap();
+        assert!(contents.contains(r#"<node pkg="talker" exec="talker" />"#));
+    }
+
+    #[test]
+    fn instantiate_rejects_invalid_names() {
+        assert!(TEMPLATES[0].instantiate("", None).is_none());
+        assert!(TEMPLATES[0].instantiate("2d publisher", None).is_none());
+        assert!(TEMPLATES[3].instantiate("Pose2D", None).is_some());
+    }
+
+    #[test]
+    fn name_of_file_name() {
+        assert_eq!(TEMPLATES[2].name_of("talker.launch.xml"), Some("talker"));
+        assert_eq!(TEMPLATES[2].name_of("talker.xml"), None);
+        assert_eq!(TEMPLATES[3].name_of("Pose.msg"), Some("Pose"));
+        assert_eq!(TEMPLATES[0].name_of(".py"), None);
+    }
+}
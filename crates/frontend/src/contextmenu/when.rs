@@ -0,0 +1,161 @@
+// conditions in the style of the when clauses of VS Code, e.g., `isFolder && !isReadonly` or
+// `resourceExtname == .py`, that decide whether a menu item applies to a target
+use std::{iter::Peekable, rc::Rc, str::Chars};
+
+use crate::{sidebar::explorer::clipboard::CLIPBOARD, vfs::Directory};
+
+use super::Target;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum When {
+    Key(String),
+    Equals(String, String),
+    Not(Box<When>),
+    And(Box<When>, Box<When>),
+    Or(Box<When>, Box<When>),
+}
+
+// the values that conditions can refer to
+pub struct Context {
+    is_file: bool,
+    is_root: bool,
+    is_readonly: bool,
+    filename: String,
+    file_copied: bool,
+}
+
+impl Context {
+    pub fn new(target: &Target, root: &Rc<Directory>) -> Context {
+        let mode = match target {
+            Target::File(file) => file.mode.get(),
+            Target::Directory(directory) => directory.mode.get(),
+        };
+        Context {
+            is_file: matches!(target, Target::File(_)),
+            is_root: matches!(target, Target::Directory(directory) if Rc::ptr_eq(directory, root)),
+            // nobody may write to the target
+            is_readonly: mode & 0o222 == 0,
+            filename: target.name(),
+            // a file or folder was cut or copied and can be pasted
+            file_copied: CLIPBOARD.with(|clipboard| clipboard.lock_ref().is_some()),
+        }
+    }
+
+    fn value(&self, key: &str) -> String {
+        let flag = |value: bool| value.to_string();
+        match key {
+            "isFile" => flag(self.is_file),
+            "isFolder" => flag(!self.is_file),
+            "isRoot" => flag(self.is_root),
+            "isReadonly" => flag(self.is_readonly),
+            "fileCopied" => flag(self.file_copied),
+            "resourceFilename" => self.filename.clone(),
+            // including the dot, e.g., .py, and empty for folders and files without extension
+            "resourceExtname" => match self.filename.rsplit_once('.') {
+                Some((stem, extension)) if self.is_file && !stem.is_empty() => format!(".{extension}"),
+                _ => String::new(),
+            },
+            _ => String::new(),
+        }
+    }
+}
+
+impl When {
+    pub fn evaluate(&self, context: &Context) -> bool {
+        match self {
+            When::Key(key) => !matches!(context.value(key).as_str(), "" | "false"),
+            When::Equals(key, value) => context.value(key) == *value,
+            When::Not(condition) => !condition.evaluate(context),
+            When::And(left, right) => left.evaluate(context) && right.evaluate(context),
+            When::Or(left, right) => left.evaluate(context) || right.evaluate(context),
+        }
+    }
+
+    // `||` binds weaker than `&&`, which binds weaker than `!`, `==` and `!=` compare a key with
+    // the literal on their right
+    pub fn parse(text: &str) -> Result<When, String> {
+        let mut parser = Parser { characters: text.chars().peekable() };
+        let condition = parser.or()?;
+        parser.skip_whitespace();
+        match parser.characters.next() {
+            None => Ok(condition),
+            Some(character) => Err(format!("unexpected '{character}' in `{text}`")),
+        }
+    }
+}
+
+struct Parser<'a> {
+    characters: Peekable<Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.characters.next_if(|character| character.is_whitespace()).is_some() {}
+    }
+
+    // consumes the operator if it comes next
+    fn operator(&mut self, operator: &str) -> bool {
+        self.skip_whitespace();
+        let mut lookahead = self.characters.clone();
+        if operator.chars().all(|expected| lookahead.next() == Some(expected)) {
+            self.characters = lookahead;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn or(&mut self) -> Result<When, String> {
+        let mut condition = self.and()?;
+        while self.operator("||") {
+            condition = When::Or(Box::new(condition), Box::new(self.and()?));
+        }
+        Ok(condition)
+    }
+
+    fn and(&mut self) -> Result<When, String> {
+        let mut condition = self.unary()?;
+        while self.operator("&&") {
+            condition = When::And(Box::new(condition), Box::new(self.unary()?));
+        }
+        Ok(condition)
+    }
+
+    fn unary(&mut self) -> Result<When, String> {
+        if self.operator("(") {
+            let condition = self.or()?;
+            return match self.operator(")") {
+                true => Ok(condition),
+                false => Err("missing ')'".to_owned()),
+            };
+        }
+        // `!=` is handled as part of a comparison
+        let mut lookahead = self.characters.clone();
+        if lookahead.next() == Some('!') && lookahead.next() != Some('=') {
+            self.characters.next();
+            return Ok(When::Not(Box::new(self.unary()?)));
+        }
+        let key = self.word();
+        if key.is_empty() {
+            return Err("expected a key".to_owned());
+        }
+        if self.operator("==") {
+            Ok(When::Equals(key, self.word()))
+        } else if self.operator("!=") {
+            Ok(When::Not(Box::new(When::Equals(key, self.word()))))
+        } else {
+            Ok(When::Key(key))
+        }
+    }
+
+    fn word(&mut self) -> String {
+        self.skip_whitespace();
+        let mut word = String::new();
+        while let Some(character) = self.characters
+            .next_if(|character| character.is_alphanumeric() || matches!(character, '.' | '_' | '-'))
+        {
+            word.push(character);
+        }
+        word
+    }
+}
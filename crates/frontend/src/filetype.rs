@@ -0,0 +1,203 @@
+use codemirror_sys::lang_python;
+use dominator::{svg, Dom};
+use strum::{AsRefStr, EnumIter};
+use wasm_bindgen::JsValue;
+
+use crate::codemirror;
+
+// the language identifiers follow the ids used by VS Code
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AsRefStr, EnumIter)]
+#[strum(serialize_all = "lowercase")]
+pub enum Language {
+    Python,
+    Xml,
+    Json,
+    Yaml,
+    Markdown,
+    Cpp,
+    PlainText,
+}
+
+impl Language {
+    // the extension that is loaded into the language compartment of the editor
+    pub fn extension(&self) -> JsValue {
+        match self {
+            Language::Python => lang_python::python().into(),
+            Language::Xml => codemirror::xml(),
+            Language::Json => codemirror::json(),
+            Language::Yaml => codemirror::yaml(),
+            Language::Markdown => codemirror::markdown(),
+            Language::Cpp => codemirror::cpp(),
+            Language::PlainText => js_sys::Array::new().into(),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::Python => "Python",
+            Language::Xml => "XML",
+            Language::Json => "JSON",
+            Language::Yaml => "YAML",
+            Language::Markdown => "Markdown",
+            Language::Cpp => "C++",
+            Language::PlainText => "Plain Text",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Icon {
+    Python,
+    Launch,
+    Xml,
+    Message,
+    Yaml,
+    Json,
+    Markdown,
+    Cpp,
+    Image,
+    Binary,
+    Generic,
+}
+
+impl Icon {
+    fn path(&self) -> &'static str {
+        match self {
+            Icon::Python => "M19.14,7.5A2.86,2.86 0 0,1 22,10.36V14.14A2.86,2.86 0 0,1 19.14,17H12C12,\
+                17.39 12.32,17.96 12.71,17.96H17V19.64A2.86,2.86 0 0,1 14.14,22.5H9.86A2.86,2.86 0 0,\
+                1 7,19.64V15.89C7,14.31 8.28,13.04 9.86,13.04H15.11C16.69,13.04 17.96,11.76 17.96,\
+                10.18V7.5H19.14M14.86,19.29C14.46,19.29 14.14,19.59 14.14,20.18C14.14,20.77 14.46,\
+                20.89 14.86,20.89A0.71,0.71 0 0,0 15.57,20.18C15.57,19.59 15.25,19.29 14.86,19.29M4.86,\
+                17.5C3.28,17.5 2,16.22 2,14.64V10.86C2,9.28 3.28,8 4.86,8H12C12,7.61 11.68,7.04 11.29,\
+                7.04H7V5.36C7,3.78 8.28,2.5 9.86,2.5H14.14C15.72,2.5 17,3.78 17,5.36V9.11C17,10.69 15.72,\
+                11.96 14.14,11.96H8.89C7.31,11.96 6.04,13.24 6.04,14.82V17.5H4.86M9.14,5.71C9.54,\
+                5.71 9.86,5.41 9.86,4.82C9.86,4.23 9.54,4.11 9.14,4.11C8.75,4.11 8.43,4.23 8.43,\
+                4.82C8.43,5.41 8.75,5.71 9.14,5.71Z",
+            Icon::Launch => "M12,2A10,10 0 0,0 2,12A10,10 0 0,0 12,22A10,10 0 0,0 22,12A10,10 0 0,\
+                0 12,2M12,4A8,8 0 0,1 20,12A8,8 0 0,1 12,20A8,8 0 0,1 4,12A8,8 0 0,1 12,4M10,\
+                16.5L16,12L10,7.5V16.5Z",
+            Icon::Xml => "M14.6,16.6L19.2,12L14.6,7.4L16,6L22,12L16,18L14.6,16.6M9.4,16.6L4.8,12L9.4,\
+                7.4L8,6L2,12L8,18L9.4,16.6Z",
+            Icon::Message => "M20,2H4A2,2 0 0,0 2,4V22L6,18H20A2,2 0 0,0 22,16V4A2,2 0 0,0 20,\
+                2M20,16H5.17L4,17.17V4H20V16M6,7H18V9H6V7M6,11H15V13H6V11Z",
+            Icon::Yaml => "M7,5H21V7H7V5M7,13V11H21V13H7M4,4.5A1.5,1.5 0 0,1 5.5,6A1.5,1.5 0 0,1 4,\
+                7.5A1.5,1.5 0 0,1 2.5,6A1.5,1.5 0 0,1 4,4.5M4,10.5A1.5,1.5 0 0,1 5.5,12A1.5,1.5 0 0,\
+                1 4,13.5A1.5,1.5 0 0,1 2.5,12A1.5,1.5 0 0,1 4,10.5M7,19V17H21V19H7M4,16.5A1.5,1.5 0 0,\
+                1 5.5,18A1.5,1.5 0 0,1 4,19.5A1.5,1.5 0 0,1 2.5,18A1.5,1.5 0 0,1 4,16.5Z",
+            Icon::Json => "M8,3A2,2 0 0,0 6,5V9A2,2 0 0,1 4,11H3V13H4A2,2 0 0,1 6,15V19A2,2 0 0,0 8,\
+                21H10V19H8V14A2,2 0 0,0 6,12A2,2 0 0,0 8,10V5H10V3M16,3A2,2 0 0,1 18,5V9A2,2 0 0,0 20,\
+                11H21V13H20A2,2 0 0,0 18,15V19A2,2 0 0,1 16,21H14V19H16V14A2,2 0 0,1 18,12A2,2 0 0,1 16,\
+                10V5H14V3H16Z",
+            Icon::Markdown => "M20.56,18H3.44C2.65,18 2,17.37 2,16.59V7.41C2,6.63 2.65,6 3.44,6H20.56C21.35,\
+                6 22,6.63 22,7.41V16.59C22,17.37 21.35,18 20.56,18M6.81,15.19V11.53L8.73,13.88L10.65,\
+                11.53V15.19H12.58V8.81H10.65L8.73,11.16L6.81,8.81H4.89V15.19H6.81M19.69,12H17.77V8.81H15.85V12H13.92L16.81,\
+                15.28L19.69,12Z",
+            Icon::Cpp => "M10.5,15.97L10.91,18.41C10.65,18.55 10.23,18.68 9.67,18.8C9.1,18.93 8.43,19 7.66,\
+                19C5.45,18.96 3.79,18.3 2.68,17.04C1.56,15.77 1,14.16 1,12.21C1.05,9.9 1.72,8.13 3,6.89C4.32,\
+                5.64 5.96,5 7.94,5C8.69,5 9.34,5.07 9.88,5.19C10.42,5.31 10.82,5.44 11.08,5.59L10.5,\
+                8.08L9.44,7.74C9.04,7.64 8.58,7.59 8.05,7.59C6.89,7.58 5.93,7.95 5.18,8.69C4.42,9.42 4.03,\
+                10.54 4,12.03C4,13.39 4.37,14.45 5.08,15.23C5.79,16 6.79,16.4 8.07,16.41L9.4,16.29C9.83,\
+                16.21 10.19,16.1 10.5,15.97M11,11H13V9H15V11H17V13H15V15H13V13H11V11M18,11H20V9H22V11H24V13H22V15H20V13H18V11Z",
+            Icon::Image => "M8.5,13.5L11,16.5L14.5,12L19,18H5M21,19V5C21,3.89 20.1,3 19,3H5A2,2 0 0,0 3,\
+                5V19A2,2 0 0,0 5,21H19A2,2 0 0,0 21,19Z",
+            Icon::Binary => "M14,2H6A2,2 0 0,0 4,4V20A2,2 0 0,0 6,22H18A2,2 0 0,0 20,20V8L14,2M8,\
+                12H10V18H8V12M12,12H16V18H12V12M13.5,13.5V16.5H14.5V13.5H13.5M13,9V3.5L18.5,9H13Z",
+            Icon::Generic => "M14,2H6A2,2 0 0,0 4,4V20A2,2 0 \
+                0,0 6,22H18A2,2 0 0,0 20,20V8L14,2M18,20H6V4H13V9H18V20Z",
+        }
+    }
+
+    fn color(&self) -> &'static str {
+        match self {
+            Icon::Python => "#3572a5", // blue
+            Icon::Launch => "#48c774", // green
+            Icon::Xml => "#e37933", // orange
+            Icon::Message => "#a074c4", // purple
+            Icon::Yaml => "#cb171e", // red
+            Icon::Json => "#cbcb41", // yellow
+            Icon::Markdown => "#519aba", // lightblue
+            Icon::Cpp => "#f34b7d", // pink
+            Icon::Image => "#a074c4", // purple
+            Icon::Binary |
+            Icon::Generic => "currentColor",
+        }
+    }
+
+    pub fn render(&self, height: &str) -> Dom {
+        svg!("svg", {
+            .attr("pointer-events", "none")
+            .attr("height", height)
+            .attr("viewBox", "0 0 24 24")
+            .attr("fill", self.color())
+            .child(svg!("path", {
+                .attr("d", self.path())
+            }))
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FileType {
+    pub icon: Icon,
+    pub language: Language,
+}
+
+impl FileType {
+    const fn new(icon: Icon, language: Language) -> FileType {
+        FileType { icon, language }
+    }
+
+    // full file names take precedence over extensions, e.g., launch.xml over .xml
+    pub fn from_name(name: &str) -> FileType {
+        let name = name.to_ascii_lowercase();
+        FILE_NAMES.iter()
+            .find(|(file_name, _)| *file_name == name)
+            .or_else(|| name.rsplit_once('.')
+                .and_then(|(_, extension)| EXTENSIONS.iter()
+                    .find(|(known_extension, _)| *known_extension == extension)))
+            .map(|(_, file_type)| *file_type)
+            .unwrap_or(PLAIN_TEXT)
+    }
+}
+
+const PLAIN_TEXT: FileType = FileType::new(Icon::Generic, Language::PlainText);
+const IMAGE: FileType = FileType::new(Icon::Image, Language::PlainText);
+const BINARY: FileType = FileType::new(Icon::Binary, Language::PlainText);
+
+const FILE_NAMES: &[(&str, FileType)] = &[
+    ("launch.xml", FileType::new(Icon::Launch, Language::Xml)),
+    ("readme", FileType::new(Icon::Markdown, Language::Markdown)),
+];
+
+const EXTENSIONS: &[(&str, FileType)] = &[
+    ("py", FileType::new(Icon::Python, Language::Python)),
+    ("xml", FileType::new(Icon::Xml, Language::Xml)),
+    ("msg", FileType::new(Icon::Message, Language::PlainText)),
+    ("srv", FileType::new(Icon::Message, Language::PlainText)),
+    ("yaml", FileType::new(Icon::Yaml, Language::Yaml)),
+    ("yml", FileType::new(Icon::Yaml, Language::Yaml)),
+    ("json", FileType::new(Icon::Json, Language::Json)),
+    ("md", FileType::new(Icon::Markdown, Language::Markdown)),
+    ("cpp", FileType::new(Icon::Cpp, Language::Cpp)),
+    ("cc", FileType::new(Icon::Cpp, Language::Cpp)),
+    ("cxx", FileType::new(Icon::Cpp, Language::Cpp)),
+    ("hpp", FileType::new(Icon::Cpp, Language::Cpp)),
+    ("hh", FileType::new(Icon::Cpp, Language::Cpp)),
+    ("h", FileType::new(Icon::Cpp, Language::Cpp)),
+    ("txt", PLAIN_TEXT),
+    ("png", IMAGE),
+    ("jpg", IMAGE),
+    ("jpeg", IMAGE),
+    ("gif", IMAGE),
+    ("bmp", IMAGE),
+    ("svg", IMAGE),
+    ("webp", IMAGE),
+    ("bin", BINARY),
+    ("so", BINARY),
+    ("o", BINARY),
+    ("a", BINARY),
+    ("pyc", BINARY),
+    ("zip", BINARY),
+    ("tar", BINARY),
+    ("gz", BINARY),
+];
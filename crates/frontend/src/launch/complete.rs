@@ -0,0 +1,234 @@
+// completions in launch files: the elements and attributes of the schema, the packages and their
+// executables for nodes and the robot models, offsets are in bytes
+use common::completions::{Completion, CompletionKind, Completions};
+
+use super::{element, Package, MODELS, SCHEMA};
+
+// where the cursor is in the markup
+enum Location<'a> {
+    // after `<`
+    ElementName { parent: Option<&'a str> },
+    AttributeName { element: &'a str },
+    AttributeValue { element: &'a str, attribute: &'a str },
+}
+
+struct Scan<'a> {
+    location: Location<'a>,
+    // the start of the word that is completed
+    from: usize,
+    // the other attributes of the tag around the cursor, also those after it
+    attributes: Vec<(&'a str, &'a str)>,
+}
+
+fn is_name_character(character: char) -> bool {
+    character.is_alphanumeric() || matches!(character, '_' | ':' | '-' | '.')
+}
+
+fn name_length(text: &str) -> usize {
+    text.find(|character| !is_name_character(character)).unwrap_or(text.len())
+}
+
+fn skip_whitespace(text: &str, index: usize) -> usize {
+    index + (text[index..].len() - text[index..].trim_start().len())
+}
+
+// the attributes up to the end of a tag, text that is not an attribute is skipped
+fn attributes(text: &str) -> Vec<(&str, &str)> {
+    let mut attributes = Vec::new();
+    let mut index = 0;
+    loop {
+        index = skip_whitespace(text, index);
+        let rest = &text[index..];
+        let Some(character) = rest.chars().next() else {
+            break;
+        };
+        if matches!(character, '>' | '<') || rest.starts_with("/>") {
+            break;
+        }
+        let length = name_length(rest);
+        if length == 0 {
+            index += match character {
+                // the value of an attribute whose name is before the cursor
+                '"' | '\'' => rest[1..].find(character).map_or(rest.len(), |end| end + 2),
+                _ => character.len_utf8(),
+            };
+            continue;
+        }
+        let name = &rest[..length];
+        index = skip_whitespace(text, index + length);
+        let mut value = "";
+        if text[index..].starts_with('=') {
+            index = skip_whitespace(text, index + 1);
+            if let Some(quote @ ('"' | '\'')) = text[index..].chars().next() {
+                let value_start = index + 1;
+                let value_end = text[value_start..].find(quote).map_or(text.len(), |end| value_start + end);
+                value = &text[value_start..value_end];
+                index = (value_end + 1).min(text.len());
+            }
+        }
+        attributes.push((name, value));
+    }
+    attributes
+}
+
+// finds out where the cursor is by reading the markup before it
+fn scan(text: &str, position: usize) -> Option<Scan<'_>> {
+    let before = &text[..position];
+    // the elements that are open at the cursor
+    let mut stack = Vec::new();
+    let mut index = 0;
+    loop {
+        index += before[index..].find('<')?;
+        let rest = &before[index..];
+        // the cursor is in a comment, a processing instruction or a declaration if they are not
+        // closed before it
+        let skipped = [("<!--", "-->"), ("<?", "?>"), ("<!", ">")]
+            .iter()
+            .find(|(opening, _)| rest.starts_with(opening));
+        if let Some((opening, closing)) = skipped {
+            index += opening.len() + rest[opening.len()..].find(closing)? + closing.len();
+            continue;
+        }
+        if let Some(end_tag) = rest.strip_prefix("</") {
+            let name = &end_tag[..name_length(end_tag)];
+            index += 2 + end_tag.find('>')? + 1;
+            if let Some(depth) = stack.iter().rposition(|open| *open == name) {
+                stack.truncate(depth);
+            }
+            continue;
+        }
+        let name_start = index + 1;
+        let name_end = name_start + name_length(&before[name_start..]);
+        if name_end == position {
+            return Some(Scan {
+                location: Location::ElementName { parent: stack.last().copied() },
+                from: name_start,
+                attributes: Vec::new(),
+            });
+        }
+        let element = &before[name_start..name_end];
+        let mut attributes_before = Vec::new();
+        index = name_end;
+        // the attributes up to the end of the tag or the cursor
+        let location = loop {
+            index = skip_whitespace(before, index);
+            let rest = &before[index..];
+            if rest.is_empty() {
+                break Some((Location::AttributeName { element }, index, &text[index..]));
+            }
+            if rest.starts_with("/>") {
+                index += 2;
+                break None;
+            }
+            if rest.starts_with('>') {
+                index += 1;
+                stack.push(element);
+                break None;
+            }
+            // a tag that is not closed
+            if rest.starts_with('<') {
+                break None;
+            }
+            let length = name_length(rest);
+            if length == 0 {
+                index += rest.chars().next().unwrap().len_utf8();
+                continue;
+            }
+            let attribute = &rest[..length];
+            if index + length == position {
+                let after = &text[position..];
+                break Some((Location::AttributeName { element }, index, &after[name_length(after)..]));
+            }
+            index = skip_whitespace(before, index + length);
+            if !before[index..].starts_with('=') {
+                attributes_before.push((attribute, ""));
+                continue;
+            }
+            index = skip_whitespace(before, index + 1);
+            let quote = match before[index..].chars().next() {
+                Some(quote @ ('"' | '\'')) => quote,
+                Some(_) => continue,
+                // a value is expected at the cursor but there are no quotes yet
+                None => return None,
+            };
+            let value_start = index + 1;
+            match before[value_start..].find(quote) {
+                Some(length) => {
+                    attributes_before.push((attribute, &before[value_start..value_start + length]));
+                    index = value_start + length + 1;
+                }
+                None => {
+                    let after = &text[position..];
+                    let value_end = after.find(quote).map_or(after.len(), |end| end + 1);
+                    break Some((Location::AttributeValue { element, attribute }, value_start, &after[value_end..]));
+                }
+            }
+        };
+        if let Some((location, from, after)) = location {
+            attributes_before.extend(attributes(after));
+            return Some(Scan { location, from, attributes: attributes_before });
+        }
+    }
+}
+
+pub fn complete(text: &str, position: usize, explicit: bool, packages: &[Package]) -> Option<Completions> {
+    let scan = scan(text, position)?;
+    let typed = &text[scan.from..position];
+    let options = match scan.location {
+        Location::ElementName { parent } => {
+            let elements = match parent {
+                // the children of a known parent, or anything inside of an unknown one
+                Some(parent) => match element(parent) {
+                    Some(parent) => parent.children.iter().filter_map(|child| element(child)).collect(),
+                    None => SCHEMA.iter().collect(),
+                },
+                None => vec![&SCHEMA[0]],
+            };
+            elements.into_iter()
+                .map(|element| Completion::new(element.name, CompletionKind::Type).info(element.description))
+                .collect()
+        }
+        Location::AttributeName { element: name } => {
+            // the attributes are only suggested while a name is typed or when they are asked for
+            if typed.is_empty() && !explicit {
+                return None;
+            }
+            element(name)?.attributes.iter()
+                .filter(|attribute| !scan.attributes.iter().any(|(present, _)| *present == attribute.name))
+                .map(|attribute| {
+                    let completion = Completion::new(attribute.name, CompletionKind::Property)
+                        .info(attribute.description)
+                        .snippet(format!("{}=\"${{}}\"", attribute.name));
+                    match attribute.required {
+                        true => completion.detail("required").boost(1),
+                        false => completion,
+                    }
+                })
+                .collect()
+        }
+        Location::AttributeValue { element, attribute } => match (element, attribute) {
+            ("node", "pkg") => packages.iter()
+                .map(|package| Completion::new(&package.name, CompletionKind::Module).detail("package"))
+                .collect(),
+            // the executables of the chosen package, or of all packages if none is chosen yet
+            ("node", "exec") => {
+                let pkg = scan.attributes.iter().find(|(name, _)| *name == "pkg").map(|(_, value)| *value);
+                packages.iter()
+                    .filter(|package| pkg.is_none_or(|pkg| package.name == pkg))
+                    .flat_map(|package| package.executables().map(|executable| {
+                        Completion::new(executable, CompletionKind::Function).detail(&package.name)
+                    }))
+                    .collect()
+            }
+            ("model", "type") => MODELS.iter()
+                .map(|model| Completion::new(model.name, CompletionKind::Class).info(model.description))
+                .collect(),
+            _ => return None,
+        },
+    };
+    Some(Completions {
+        from: scan.from,
+        options,
+        valid_for: r"^[\w:.-]*$",
+    })
+}
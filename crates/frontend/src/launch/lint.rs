@@ -0,0 +1,227 @@
+// checks launch files against the schema and the project, e.g., that the executables of the nodes
+// exist and that the models are placed inside of the world
+use std::ops::Range;
+
+use common::diagnostics::Diagnostic;
+
+use super::{xml::{self, Attribute, Element}, Package, MODELS};
+
+struct Linter<'a> {
+    packages: &'a [Package],
+    diagnostics: Vec<Diagnostic>,
+}
+
+// the numbers in a space separated list
+fn numbers(value: &str) -> Option<Vec<f64>> {
+    value.split_whitespace()
+        .map(|number| number.parse::<f64>().ok().filter(|number| number.is_finite()))
+        .collect()
+}
+
+impl Linter<'_> {
+    fn error(&mut self, range: Range<usize>, message: impl Into<String>) {
+        self.diagnostics.push(Diagnostic::error(range, message));
+    }
+
+    fn warning(&mut self, range: Range<usize>, message: impl Into<String>) {
+        self.diagnostics.push(Diagnostic::warning(range, message));
+    }
+
+    // the position of the quotes of a value, so that empty values can be seen
+    fn value_range(attribute: &Attribute) -> Range<usize> {
+        match attribute.value.is_empty() {
+            true => attribute.value_range.start - 1..attribute.value_range.end + 1,
+            false => attribute.value_range.clone(),
+        }
+    }
+
+    // unknown elements and attributes and missing attributes, the children are checked as well
+    fn element(&mut self, element: &Element, parent: Option<&str>, world: Option<(f64, f64)>) {
+        let Some(schema) = super::element(element.name) else {
+            self.warning(element.name_range.clone(), format!("unknown element `<{}>`", element.name));
+            return;
+        };
+        match parent.and_then(super::element) {
+            Some(parent) if !parent.children.contains(&element.name) => {
+                self.warning(element.name_range.clone(), format!("`<{}>` is not expected inside of `<{}>`", element.name, parent.name));
+            }
+            None if parent.is_none() && element.name != "launch" => {
+                self.warning(element.name_range.clone(), "the root element of a launch file is `<launch>`");
+            }
+            _ => {}
+        }
+        for attribute in &element.attributes {
+            if !schema.attributes.iter().any(|candidate| candidate.name == attribute.name) {
+                self.warning(attribute.name_range.clone(), format!("unknown attribute `{}` of `<{}>`", attribute.name, element.name));
+            }
+        }
+        for required in schema.attributes.iter().filter(|attribute| attribute.required) {
+            if element.attribute(required.name).is_none() {
+                self.error(element.name_range.clone(), format!("`<{}>` needs the attribute `{}`", element.name, required.name));
+            }
+        }
+        let world = match element.name {
+            "node" => {
+                self.node(element);
+                world
+            }
+            "world" => self.world(element),
+            "model" => {
+                self.model(element, world);
+                world
+            }
+            _ => world,
+        };
+        for child in &element.children {
+            self.element(child, Some(element.name), world);
+        }
+    }
+
+    fn node(&mut self, element: &Element) {
+        let Some(pkg) = element.attribute("pkg") else {
+            return;
+        };
+        let Some(package) = self.packages.iter().find(|package| package.name == pkg.value) else {
+            self.error(Self::value_range(pkg), format!("there is no package `{}` in the project", pkg.value));
+            return;
+        };
+        let Some(exec) = element.attribute("exec") else {
+            return;
+        };
+        if !package.executables().any(|executable| executable == exec.value) {
+            let message = format!("there is no executable `{}` in the package `{}`", exec.value, package.name);
+            self.error(Self::value_range(exec), message);
+        }
+    }
+
+    // the extent of the world along x and z
+    fn world(&mut self, element: &Element) -> Option<(f64, f64)> {
+        let size = element.attribute("size")?;
+        match numbers(size.value).as_deref() {
+            Some(&[x, z]) if x > 0.0 && z > 0.0 => Some((x, z)),
+            _ => {
+                self.error(Self::value_range(size), "the size is two positive numbers, the extent along x and z, e.g., `3.0 6.0`");
+                None
+            }
+        }
+    }
+
+    fn model(&mut self, element: &Element, world: Option<(f64, f64)>) {
+        if let Some(model_type) = element.attribute("type") {
+            if !MODELS.iter().any(|model| model.name == model_type.value) {
+                let known = MODELS.iter().map(|model| format!("`{}`", model.name)).collect::<Vec<_>>();
+                let message = format!("unknown model type `{}`, the known types are {}", model_type.value, known.join(", "));
+                self.error(Self::value_range(model_type), message);
+            }
+        }
+        let Some(pose) = element.attribute("pose") else {
+            return;
+        };
+        let Some(&[x, _, z, ..]) = numbers(pose.value).filter(|numbers| numbers.len() == 6).as_deref() else {
+            self.error(Self::value_range(pose), "the pose is six numbers, the position x y z and the rotation around x, y and z");
+            return;
+        };
+        if let Some((width, depth)) = world {
+            if x.abs() > width / 2.0 || z.abs() > depth / 2.0 {
+                let message = format!(
+                    "the model is outside of the world, which spans {} to {} along x and {} to {} along z",
+                    -width / 2.0, width / 2.0, -depth / 2.0, depth / 2.0
+                );
+                self.warning(Self::value_range(pose), message);
+            }
+        }
+    }
+}
+
+pub fn lint(text: &str, packages: &[Package]) -> Vec<Diagnostic> {
+    let document = xml::parse(text);
+    let mut linter = Linter {
+        packages,
+        diagnostics: Vec::new(),
+    };
+    for error in document.errors {
+        linter.error(error.range, error.message);
+    }
+    for element in &document.elements {
+        linter.element(element, None, None);
+    }
+    linter.diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use common::diagnostics::Severity;
+
+    use super::*;
+
+    fn packages() -> Vec<Package> {
+        vec![Package {
+            name: "control".to_owned(),
+            files: vec!["run.py".to_owned(), "notes.txt".to_owned()],
+        }]
+    }
+
+    fn messages(text: &str) -> Vec<(Severity, String)> {
+        lint(text, &packages()).into_iter()
+            .map(|diagnostic| (diagnostic.severity, diagnostic.message))
+            .collect()
+    }
+
+    #[test]
+    fn valid_launch_file() {
+        let text = r#"<launch>
+  <node pkg="control" exec="run" />
+  <world size="3.0 6.0">
+    <model type="turtlebot" pose="1 0 -2 0 0 0" />
+  </world>
+</launch>"#;
+        assert!(messages(text).is_empty());
+    }
+
+    #[test]
+    fn nodes_against_the_project() {
+        assert_eq!(messages(r#"<launch><node pkg="missing" exec="run"/></launch>"#), [
+            (Severity::Error, "there is no package `missing` in the project".to_owned()),
+        ]);
+        // only Python files are executables
+        assert_eq!(messages(r#"<launch><node pkg="control" exec="notes.txt"/></launch>"#), [
+            (Severity::Error, "there is no executable `notes.txt` in the package `control`".to_owned()),
+        ]);
+        assert_eq!(messages(r#"<launch><node pkg="control"/></launch>"#), [
+            (Severity::Error, "`<node>` needs the attribute `exec`".to_owned()),
+        ]);
+    }
+
+    #[test]
+    fn schema() {
+        assert_eq!(messages(r#"<launch><robot/></launch>"#), [
+            (Severity::Warning, "unknown element `<robot>`".to_owned()),
+        ]);
+        assert_eq!(messages(r#"<launch><model type="turtlebot"/></launch>"#), [
+            (Severity::Warning, "`<model>` is not expected inside of `<launch>`".to_owned()),
+        ]);
+        assert_eq!(messages(r#"<world size="1 1"/>"#), [
+            (Severity::Warning, "the root element of a launch file is `<launch>`".to_owned()),
+        ]);
+        assert_eq!(messages(r#"<launch color="red"/>"#), [
+            (Severity::Warning, "unknown attribute `color` of `<launch>`".to_owned()),
+        ]);
+    }
+
+    #[test]
+    fn world_and_models() {
+        let found = messages(r#"<launch><world size="2 2"><model type="tank" pose="5 0 0 0 0 0"/></world></launch>"#);
+        assert_eq!(found.len(), 2);
+        assert!(found[0].1.starts_with("unknown model type `tank`"));
+        assert_eq!(found[1].0, Severity::Warning);
+        assert!(found[1].1.starts_with("the model is outside of the world"));
+        assert_eq!(messages(r#"<launch><world size="2 -2"/></launch>"#)[0].0, Severity::Error);
+    }
+
+    #[test]
+    fn empty_values_are_marked_with_their_quotes() {
+        let text = r#"<launch><node pkg="" exec="run"/></launch>"#;
+        let diagnostics = lint(text, &packages());
+        assert_eq!(&text[diagnostics[0].range.clone()], r#""""#);
+    }
+}
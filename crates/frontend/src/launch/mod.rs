@@ -0,0 +1,130 @@
+// launch files start the nodes of the project and describe the simulated world, this module knows
+// what they may contain
+use std::rc::Rc;
+
+use crate::vfs::Directory;
+
+pub mod complete;
+pub mod lint;
+pub mod xml;
+
+pub struct ElementSchema {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub attributes: &'static [AttributeSchema],
+    // the elements that may appear inside of this one
+    pub children: &'static [&'static str],
+}
+
+pub struct AttributeSchema {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub required: bool,
+}
+
+pub static SCHEMA: [ElementSchema; 4] = [
+    ElementSchema {
+        name: "launch",
+        description: "The root element of a launch file",
+        attributes: &[],
+        children: &["node", "world"],
+    },
+    ElementSchema {
+        name: "node",
+        description: "Starts an executable of a package",
+        attributes: &[
+            AttributeSchema {
+                name: "pkg",
+                description: "The package, i.e., a top-level directory of the project",
+                required: true,
+            },
+            AttributeSchema {
+                name: "exec",
+                description: "The executable in the package, e.g., `run` for `run.py`",
+                required: true,
+            },
+            AttributeSchema {
+                name: "name",
+                description: "The name of the node, which replaces the name given in the code",
+                required: false,
+            },
+        ],
+        children: &[],
+    },
+    ElementSchema {
+        name: "world",
+        description: "The simulated world, which is centered on the origin",
+        attributes: &[
+            AttributeSchema {
+                name: "size",
+                description: "The extent of the world along x and z in meters, e.g., `3.0 6.0`",
+                required: true,
+            },
+        ],
+        children: &["model"],
+    },
+    ElementSchema {
+        name: "model",
+        description: "A robot that is placed in the world",
+        attributes: &[
+            AttributeSchema {
+                name: "type",
+                description: "The robot model",
+                required: true,
+            },
+            AttributeSchema {
+                name: "pose",
+                description: "The position x y z in meters followed by the rotation around x, y and z in radians",
+                required: false,
+            },
+        ],
+        children: &[],
+    },
+];
+
+pub fn element(name: &str) -> Option<&'static ElementSchema> {
+    SCHEMA.iter().find(|element| element.name == name)
+}
+
+pub struct Model {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+pub static MODELS: [Model; 1] = [
+    Model {
+        name: "turtlebot",
+        description: "TurtleBot, a differential drive robot",
+    },
+];
+
+// a top-level directory of the project and the names of the files in it
+pub struct Package {
+    pub name: String,
+    pub files: Vec<String>,
+}
+
+impl Package {
+    // `run` is the executable of `run.py`, other files are not executables
+    pub fn executables(&self) -> impl Iterator<Item = &str> {
+        self.files.iter().filter_map(|file| file.strip_suffix(".py").filter(|name| !name.is_empty()))
+    }
+}
+
+pub fn packages(root: &Rc<Directory>) -> Vec<Package> {
+    root.directories.lock_ref()
+        .iter()
+        .map(|directory| Package {
+            name: directory.name.get_cloned(),
+            files: directory.files.lock_ref()
+                .iter()
+                .map(|file| file.name.get_cloned())
+                .collect(),
+        })
+        .collect()
+}
+
+// e.g., launch.xml or robots.launch.xml
+pub fn is_launch_file(name: &str) -> bool {
+    name == "launch.xml" || name.ends_with(".launch.xml")
+}
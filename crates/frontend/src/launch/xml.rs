@@ -0,0 +1,380 @@
+// a small XML parser that keeps the positions of names and values and that reports syntax errors
+// and carries on instead of giving up, offsets are in bytes
+use std::ops::Range;
+
+pub struct Attribute<'a> {
+    pub name: &'a str,
+    pub name_range: Range<usize>,
+    pub value: &'a str,
+    // without the quotes
+    pub value_range: Range<usize>,
+}
+
+pub struct Element<'a> {
+    pub name: &'a str,
+    // from the start tag to the end tag, elements that are not closed end where they are given up
+    pub range: Range<usize>,
+    pub name_range: Range<usize>,
+    pub attributes: Vec<Attribute<'a>>,
+    pub children: Vec<Element<'a>>,
+}
+
+impl<'a> Element<'a> {
+    pub fn attribute(&self, name: &str) -> Option<&Attribute<'a>> {
+        self.attributes.iter().find(|attribute| attribute.name == name)
+    }
+}
+
+pub struct SyntaxError {
+    pub range: Range<usize>,
+    pub message: String,
+}
+
+pub struct Document<'a> {
+    pub elements: Vec<Element<'a>>,
+    pub errors: Vec<SyntaxError>,
+}
+
+fn is_name_start(character: char) -> bool {
+    character.is_alphabetic() || matches!(character, '_' | ':')
+}
+
+fn is_name_character(character: char) -> bool {
+    character.is_alphanumeric() || matches!(character, '_' | ':' | '-' | '.')
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    index: usize,
+    // the elements whose end tag has not been read yet
+    stack: Vec<Element<'a>>,
+    elements: Vec<Element<'a>>,
+    errors: Vec<SyntaxError>,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&mut self, range: Range<usize>, message: impl Into<String>) {
+        self.errors.push(SyntaxError { range, message: message.into() });
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.index..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.index += rest.len() - rest.trim_start().len();
+    }
+
+    fn name(&mut self) -> Option<(&'a str, Range<usize>)> {
+        let start = self.index;
+        if !self.peek().is_some_and(is_name_start) {
+            return None;
+        }
+        let length = self.rest()
+            .find(|character| !is_name_character(character))
+            .unwrap_or(self.rest().len());
+        self.index += length;
+        Some((&self.text[start..self.index], start..self.index))
+    }
+
+    // only the predefined entities and character references are known
+    fn check_references(&mut self, start: usize, end: usize) {
+        let text = self.text;
+        for (offset, _) in text[start..end].match_indices('&') {
+            let position = start + offset;
+            let reference = text[position + 1..end]
+                .split_once(';')
+                .map(|(reference, _)| reference);
+            let valid = reference.is_some_and(|reference| match reference.strip_prefix('#') {
+                Some(number) => match number.strip_prefix('x') {
+                    Some(hex) => !hex.is_empty() && hex.chars().all(|digit| digit.is_ascii_hexdigit()),
+                    None => !number.is_empty() && number.chars().all(|digit| digit.is_ascii_digit()),
+                },
+                None => matches!(reference, "amp" | "lt" | "gt" | "quot" | "apos"),
+            });
+            if !valid {
+                self.error(position..position + 1, "`&` has to be written as `&amp;`");
+            }
+        }
+    }
+
+    // text between tags is only allowed inside of the root element
+    fn text(&mut self, end: usize) {
+        let text = &self.text[self.index..end];
+        if self.stack.is_empty() && !text.trim().is_empty() {
+            let start = self.index + (text.len() - text.trim_start().len());
+            self.error(start..start + text.trim().len(), "text outside of the root element");
+        }
+        self.check_references(self.index, end);
+        self.index = end;
+    }
+
+    // skips a comment, processing instruction, CDATA section or declaration
+    fn skip(&mut self, opening: &str, closing: &str, what: &str) {
+        let start = self.index;
+        match self.text[start + opening.len()..].find(closing) {
+            Some(end) => self.index = start + opening.len() + end + closing.len(),
+            None => {
+                self.index = self.text.len();
+                self.error(start..start + opening.len(), format!("the {what} is not closed with `{closing}`"));
+            }
+        }
+    }
+
+    fn add(&mut self, element: Element<'a>) {
+        match self.stack.last_mut() {
+            Some(parent) => parent.children.push(element),
+            None => {
+                if !self.elements.is_empty() {
+                    self.error(element.name_range.clone(), "there can only be one root element");
+                }
+                self.elements.push(element);
+            }
+        }
+    }
+
+    fn close_innermost(&mut self, end: usize) {
+        if let Some(mut element) = self.stack.pop() {
+            element.range.end = end;
+            self.add(element);
+        }
+    }
+
+    fn end_tag(&mut self) {
+        let start = self.index;
+        self.index += 2;
+        let Some((name, name_range)) = self.name() else {
+            self.error(start..self.index, "expected the name of the element after `</`");
+            return;
+        };
+        self.skip_whitespace();
+        match self.peek() {
+            Some('>') => self.index += 1,
+            _ => self.error(start..self.index, format!("expected `>` after `</{name}`")),
+        }
+        match self.stack.iter().rposition(|element| element.name == name) {
+            Some(depth) => {
+                // the elements that were left open inside of this one
+                while self.stack.len() > depth + 1 {
+                    let range = self.stack.last().unwrap().name_range.clone();
+                    let open = self.stack.last().unwrap().name;
+                    self.error(range, format!("`<{open}>` is not closed before `</{name}>`"));
+                    self.close_innermost(self.index);
+                }
+                self.close_innermost(self.index);
+            }
+            None => self.error(name_range, format!("`</{name}>` does not close any element")),
+        }
+    }
+
+    fn start_tag(&mut self) {
+        let start = self.index;
+        self.index += 1;
+        let Some((name, name_range)) = self.name() else {
+            self.error(start..start + 1, "`<` has to be written as `&lt;` or be followed by the name of an element");
+            return;
+        };
+        let mut element = Element { name, range: start..start, name_range, attributes: Vec::new(), children: Vec::new() };
+        let self_closing = loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.is_empty() || rest.starts_with('<') {
+                // treated as empty so that the rest of the file does not end up inside of it
+                self.error(start..self.index, format!("the tag `<{name}` is not closed with `>`"));
+                break true;
+            }
+            if rest.starts_with("/>") {
+                self.index += 2;
+                break true;
+            }
+            if rest.starts_with('>') {
+                self.index += 1;
+                break false;
+            }
+            self.attribute(&mut element);
+        };
+        element.range.end = self.index;
+        match self_closing {
+            true => self.add(element),
+            false => {
+                if self.stack.is_empty() && !self.elements.is_empty() {
+                    self.error(element.name_range.clone(), "there can only be one root element");
+                }
+                self.stack.push(element);
+            }
+        }
+    }
+
+    fn attribute(&mut self, element: &mut Element<'a>) {
+        let Some((name, name_range)) = self.name() else {
+            let character = self.peek().unwrap();
+            let start = self.index;
+            self.index += character.len_utf8();
+            self.error(start..self.index, format!("unexpected `{character}` in the tag `<{}>`", element.name));
+            return;
+        };
+        self.skip_whitespace();
+        if self.peek() != Some('=') {
+            self.error(name_range, format!("the attribute `{name}` has no value"));
+            return;
+        }
+        self.index += 1;
+        self.skip_whitespace();
+        let quote = match self.peek() {
+            Some(quote @ ('"' | '\'')) => quote,
+            _ => {
+                // skips the unquoted value
+                let start = self.index;
+                let length = self.rest()
+                    .find(|character: char| character.is_whitespace() || matches!(character, '>' | '<' | '/'))
+                    .unwrap_or(self.rest().len());
+                self.index += length;
+                self.error(start..self.index.max(start + 1).min(self.text.len()), format!("the value of `{name}` has to be quoted"));
+                return;
+            }
+        };
+        let value_start = self.index + 1;
+        let Some(length) = self.text[value_start..].find(quote) else {
+            self.error(self.index..self.text.len(), format!("the value of `{name}` is not closed with {quote}"));
+            self.index = self.text.len();
+            return;
+        };
+        let value_end = value_start + length;
+        self.index = value_end + 1;
+        if let Some(offset) = self.text[value_start..value_end].find('<') {
+            let position = value_start + offset;
+            self.error(position..position + 1, "`<` has to be written as `&lt;` in a value");
+        }
+        self.check_references(value_start, value_end);
+        if element.attribute(name).is_some() {
+            self.error(name_range.clone(), format!("the attribute `{name}` is repeated"));
+        }
+        element.attributes.push(Attribute {
+            name,
+            name_range,
+            value: &self.text[value_start..value_end],
+            value_range: value_start..value_end,
+        });
+    }
+}
+
+pub fn parse(text: &str) -> Document<'_> {
+    let mut parser = Parser {
+        text,
+        index: 0,
+        stack: Vec::new(),
+        elements: Vec::new(),
+        errors: Vec::new(),
+    };
+    while parser.index < text.len() {
+        let Some(offset) = parser.rest().find('<') else {
+            parser.text(text.len());
+            break;
+        };
+        parser.text(parser.index + offset);
+        let rest = parser.rest();
+        if rest.starts_with("<!--") {
+            parser.skip("<!--", "-->", "comment");
+        } else if rest.starts_with("<?") {
+            parser.skip("<?", "?>", "processing instruction");
+        } else if rest.starts_with("<![CDATA[") {
+            parser.skip("<![CDATA[", "]]>", "CDATA section");
+        } else if rest.starts_with("<!") {
+            parser.skip("<!", ">", "declaration");
+        } else if rest.starts_with("</") {
+            parser.end_tag();
+        } else {
+            parser.start_tag();
+        }
+    }
+    while let Some(element) = parser.stack.last() {
+        let (range, name) = (element.name_range.clone(), element.name);
+        parser.error(range, format!("`<{name}>` is not closed"));
+        parser.close_innermost(text.len());
+    }
+    if parser.elements.is_empty() && parser.errors.is_empty() {
+        parser.error(0..0, "the file has no root element");
+    }
+    Document {
+        elements: parser.elements,
+        errors: parser.errors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(text: &str) -> Vec<String> {
+        parse(text).errors.into_iter().map(|error| error.message).collect::<Vec<_>>()
+    }
+
+    #[test]
+    fn elements_and_attributes() {
+        let text = r#"<launch><node pkg="a" exec='b'/><!-- c --><world/></launch>"#;
+        let document = parse(text);
+        assert!(document.errors.is_empty());
+        let launch = &document.elements[0];
+        assert_eq!(launch.name, "launch");
+        assert_eq!(launch.children.iter().map(|child| child.name).collect::<Vec<_>>(), ["node", "world"]);
+        let exec = launch.children[0].attribute("exec").unwrap();
+        assert_eq!(exec.value, "b");
+        assert_eq!(&text[exec.value_range.clone()], "b");
+        assert_eq!(&text[exec.name_range.clone()], "exec");
+        assert_eq!(&text[launch.children[0].range.clone()], r#"<node pkg="a" exec='b'/>"#);
+        assert_eq!(launch.range, 0..text.len());
+    }
+
+    #[test]
+    fn unclosed_elements() {
+        assert_eq!(messages("<launch><node>"), ["`<node>` is not closed", "`<launch>` is not closed"]);
+        assert_eq!(messages("<launch><node></launch>"), ["`<node>` is not closed before `</launch>`"]);
+        assert_eq!(messages("<launch></node></launch>"), ["`</node>` does not close any element"]);
+        // elements that are left open end where they are closed by force
+        assert_eq!(parse("<launch><node>").elements[0].children[0].range, 8..14);
+        assert_eq!(parse("<launch><node></launch> ").elements[0].children[0].range, 8..23);
+    }
+
+    #[test]
+    fn unclosed_tag_does_not_swallow_the_rest() {
+        let document = parse("<launch>\n<node pkg=\"a\"\n<world/>\n</launch>");
+        assert_eq!(document.errors.len(), 1);
+        assert_eq!(document.elements[0].children.len(), 2);
+    }
+
+    #[test]
+    fn attribute_errors() {
+        assert_eq!(messages("<a b=c/>"), ["the value of `b` has to be quoted"]);
+        assert_eq!(messages("<a b/>"), ["the attribute `b` has no value"]);
+        assert_eq!(messages(r#"<a b="1" b="2"/>"#), ["the attribute `b` is repeated"]);
+        assert_eq!(messages(r#"<a b="<"/>"#), ["`<` has to be written as `&lt;` in a value"]);
+    }
+
+    #[test]
+    fn references() {
+        assert!(messages(r#"<a b="&amp;&#38;&#x26;">&lt;</a>"#).is_empty());
+        assert_eq!(messages("<a>&</a>"), ["`&` has to be written as `&amp;`"]);
+    }
+
+    #[test]
+    fn document_errors() {
+        assert_eq!(messages(""), ["the file has no root element"]);
+        assert_eq!(messages("<a/><b/>"), ["there can only be one root element"]);
+        assert_eq!(messages("text<a/>"), ["text outside of the root element"]);
+        assert_eq!(messages("<a><!-- </a>"), ["the comment is not closed with `-->`", "`<a>` is not closed"]);
+    }
+
+    #[test]
+    fn non_ascii_ranges() {
+        let text = r#"<a ä="ö"/>"#;
+        let document = parse(text);
+        let attribute = &document.elements[0].attributes[0];
+        assert_eq!(&text[attribute.name_range.clone()], "ä");
+        assert_eq!(&text[attribute.value_range.clone()], "ö");
+    }
+}
@@ -11,14 +11,38 @@ mod sidebar;
 mod workspace;
 mod vfs;
 mod contextmenu;
+mod codemirror;
+mod filetype;
+mod launch;
+mod settings;
 mod styles;
+mod symbols;
+mod tooltips;
+mod worker;
 
 enum WorkspaceCommand {
     OpenFile(Rc<vfs::File>),
+    // opens the file and moves the cursor to the position
+    OpenFileAt(Rc<vfs::File>, usize),
+    // opens the file in a regular tab instead of the preview tab
+    KeepFileOpen(Rc<vfs::File>),
+    // opens the file in the editor group next to the active one
+    OpenFileToSide(Rc<vfs::File>),
+    ActivateActivity(Rc<workspace::activity_panel::Activity>),
+    CloseActivity(Rc<workspace::activity_panel::Activity>),
 }
 type WorkspaceCommandSender = mpsc::UnboundedSender<WorkspaceCommand>;
 type WorkspaceCommandReceiver = mpsc::UnboundedReceiver<WorkspaceCommand>;
 
+// events from the workspace that the sidebar follows
+enum SidebarCommand {
+    ActiveActivityChanged(Option<Rc<workspace::activity_panel::Activity>>),
+    RevealFile(Rc<vfs::File>),
+    OpenActivitiesChanged(Vec<Rc<workspace::activity_panel::Activity>>),
+}
+type SidebarCommandSender = mpsc::UnboundedSender<SidebarCommand>;
+type SidebarCommandReceiver = mpsc::UnboundedReceiver<SidebarCommand>;
+
 #[wasm_bindgen(start)]
 pub async fn main() {
     console_error_panic_hook::set_once();
@@ -35,6 +59,10 @@ pub async fn main() {
 
     let (workspace_command_tx, workspace_command_rx) = mpsc::unbounded();
 
+    contextmenu::register_menu_items();
+    sidebar::explorer::register_menu_items(&workspace_command_tx);
+    let (sidebar_command_tx, sidebar_command_rx) = mpsc::unbounded();
+
     let sidebar: Rc<Sidebar> = Default::default();
     let workspace: Rc<Workspace> = Default::default();
     
@@ -51,8 +79,8 @@ pub async fn main() {
     let outer = html!("div", {
         .apply(styles::default_layout)
         .class("grid-cols-[auto_1fr]")
-        .child(Sidebar::render(&sidebar, &workspace_command_tx))
-        .child(Workspace::render(&workspace, workspace_command_rx, workspace_width, window_height))
+        .child(Sidebar::render(&sidebar, &workspace_command_tx, sidebar_command_rx))
+        .child(Workspace::render(&workspace, workspace_command_rx, &sidebar_command_tx, workspace_width, window_height))
     });
 
     dominator::append_dom(&dominator::body(), outer);
@@ -110,13 +138,9 @@ thread_local! {
     pub static PROJECT: Lazy<Rc<vfs::Directory>> = Lazy::new(|| {
         vfs::Directory {
             name: "project".to_owned().into(),
-            mode: Default::default(),
+            mode: DEFAULT_DIRECTORY_MODE.into(),
             files: vec![
-                vfs::File {
-                    name: "launch.xml".to_owned().into(),
-                    mode: DEFAULT_FILE_MODE.into(),
-                    data: LAUNCH_XML.as_bytes().to_vec().into()
-                }.into(),
+                vfs::File::new("launch.xml", DEFAULT_FILE_MODE, LAUNCH_XML.as_bytes().to_vec()).into(),
             ].into(),
             directories: vec![
                 vfs::Directory {
@@ -124,11 +148,7 @@ thread_local! {
                     mode: DEFAULT_DIRECTORY_MODE.into(),
                     directories: vec![].into(),
                     files: vec![
-                        vfs::File {
-                            name: "run.py".to_owned().into(),
-                            mode: DEFAULT_FILE_MODE.into(),
-                            data: VELOCITY_CONTROL_PY.as_bytes().to_vec().into()
-                        }.into(),
+                        vfs::File::new("run.py", DEFAULT_FILE_MODE, VELOCITY_CONTROL_PY.as_bytes().to_vec()).into(),
                     ].into(),
                 }.into()
             ].into()
@@ -0,0 +1,288 @@
+use std::{fmt, str::FromStr};
+
+use futures_signals::{map_ref, signal::{Mutable, Signal}};
+use strum::{AsRefStr, Display, EnumIter, EnumString, IntoEnumIterator};
+
+use crate::filetype::Language;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, AsRefStr, EnumIter, EnumString)]
+pub enum Autosave {
+    // only Ctrl+S writes the buffer to the file
+    Off,
+    // once no key was pressed for a moment
+    AfterDelay,
+    // when the editor loses the focus
+    OnFocusChange,
+    #[default]
+    OnKeystroke,
+}
+
+impl Autosave {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Autosave::Off => "Off",
+            Autosave::AfterDelay => "After Delay",
+            Autosave::OnFocusChange => "On Focus Change",
+            Autosave::OnKeystroke => "On Every Keystroke",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display, EnumIter, EnumString)]
+pub enum RenderWhitespace {
+    None,
+    // only the whitespace at the end of lines
+    Trailing,
+    All,
+}
+
+impl RenderWhitespace {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RenderWhitespace::None => "None",
+            RenderWhitespace::Trailing => "Trailing",
+            RenderWhitespace::All => "All",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display, EnumIter, EnumString)]
+pub enum LineNumbers {
+    On,
+    Off,
+    // the distance to the line of the cursor
+    Relative,
+}
+
+impl LineNumbers {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LineNumbers::On => "On",
+            LineNumbers::Off => "Off",
+            LineNumbers::Relative => "Relative",
+        }
+    }
+}
+
+// the columns at which vertical lines are drawn, e.g., "80,120"
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Rulers(pub Vec<u32>);
+
+impl fmt::Display for Rulers {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let columns = self.0.iter().map(u32::to_string).collect::<Vec<_>>();
+        formatter.write_str(&columns.join(","))
+    }
+}
+
+impl FromStr for Rulers {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(text: &str) -> Result<Rulers, Self::Err> {
+        text.split(',')
+            .map(str::trim)
+            .filter(|column| !column.is_empty())
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map(Rulers)
+    }
+}
+
+// a setting that can be overridden per language, an override is stored under the key of the
+// setting prefixed with the language, e.g., "[python].editor.tab_size"
+pub struct Setting<T> {
+    key: &'static str,
+    value: Mutable<T>,
+    overrides: Vec<(Language, Mutable<Option<T>>)>,
+}
+
+fn override_key(key: &str, language: Language) -> String {
+    format!("[{}].{key}", language.as_ref())
+}
+
+impl<T: Clone + PartialEq + FromStr + fmt::Display + 'static> Setting<T> {
+    fn new(key: &'static str, default: T) -> Setting<T> {
+        Setting {
+            key,
+            value: Mutable::new(load(key).unwrap_or(default)),
+            overrides: Language::iter()
+                .map(|language| (language, Mutable::new(load(&override_key(key, language)))))
+                .collect(),
+        }
+    }
+
+    fn language_override(&self, language: Language) -> &Mutable<Option<T>> {
+        self.overrides.iter()
+            .find(|(candidate, _)| *candidate == language)
+            .map(|(_, value)| value)
+            .unwrap()
+    }
+
+    pub fn get(&self, language: Language) -> T {
+        self.get_override(language).unwrap_or_else(|| self.value.get_cloned())
+    }
+
+    pub fn get_default(&self) -> T {
+        self.value.get_cloned()
+    }
+
+    pub fn get_override(&self, language: Language) -> Option<T> {
+        self.language_override(language).get_cloned()
+    }
+
+    pub fn signal(&self, language: Language) -> impl Signal<Item = T> {
+        map_ref! {
+            let value = self.value.signal_cloned(),
+            let language_override = self.language_override(language).signal_cloned() => {
+                language_override.clone().unwrap_or_else(|| value.clone())
+            }
+        }
+    }
+
+    pub fn set(&self, value: T) {
+        store(self.key, &value.to_string());
+        self.value.set_neq(value);
+    }
+
+    // None removes the override so that the language follows the setting again
+    pub fn set_override(&self, language: Language, value: Option<T>) {
+        let key = override_key(self.key, language);
+        match &value {
+            Some(value) => store(&key, &value.to_string()),
+            None => remove(&key),
+        }
+        self.language_override(language).set_neq(value);
+    }
+}
+
+// the settings that apply to an editor of a language
+#[derive(Clone, Debug, PartialEq)]
+pub struct EditorOptions {
+    pub tab_size: u32,
+    pub insert_spaces: bool,
+    pub detect_indentation: bool,
+    pub word_wrap: bool,
+    pub font_family: String,
+    pub font_size: u32,
+    pub rulers: Rulers,
+    pub render_whitespace: RenderWhitespace,
+    pub line_numbers: LineNumbers,
+}
+
+const EXPLORER_AUTO_REVEAL_KEY: &str = "explorer.auto_reveal";
+const EXPLORER_COMPACT_FOLDERS_KEY: &str = "explorer.compact_folders";
+const EDITOR_AUTOSAVE_KEY: &str = "editor.autosave";
+
+pub struct Settings {
+    // follow the active editor in the explorer
+    pub explorer_auto_reveal: Mutable<bool>,
+    // show directories that only contain a single directory on one row, e.g., a/b/c
+    pub explorer_compact_folders: Mutable<bool>,
+    // when the editors write their buffers to the files
+    pub editor_autosave: Mutable<Autosave>,
+    // the width of a tab in columns
+    pub editor_tab_size: Setting<u32>,
+    // whether pressing Tab inserts spaces or a tab
+    pub editor_insert_spaces: Setting<bool>,
+    // whether the two settings above are replaced by the indentation found in the file
+    pub editor_detect_indentation: Setting<bool>,
+    pub editor_word_wrap: Setting<bool>,
+    pub editor_font_family: Setting<String>,
+    // in pixels
+    pub editor_font_size: Setting<u32>,
+    pub editor_rulers: Setting<Rulers>,
+    pub editor_render_whitespace: Setting<RenderWhitespace>,
+    pub editor_line_numbers: Setting<LineNumbers>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            explorer_auto_reveal: Mutable::new(load(EXPLORER_AUTO_REVEAL_KEY).unwrap_or(true)),
+            explorer_compact_folders: Mutable::new(load(EXPLORER_COMPACT_FOLDERS_KEY).unwrap_or(true)),
+            editor_autosave: Mutable::new(load(EDITOR_AUTOSAVE_KEY).unwrap_or_default()),
+            editor_tab_size: Setting::new("editor.tab_size", 4),
+            editor_insert_spaces: Setting::new("editor.insert_spaces", true),
+            editor_detect_indentation: Setting::new("editor.detect_indentation", true),
+            editor_word_wrap: Setting::new("editor.word_wrap", false),
+            editor_font_family: Setting::new("editor.font_family", "monospace".to_owned()),
+            editor_font_size: Setting::new("editor.font_size", 14),
+            editor_rulers: Setting::new("editor.rulers", Rulers::default()),
+            editor_render_whitespace: Setting::new("editor.render_whitespace", RenderWhitespace::None),
+            editor_line_numbers: Setting::new("editor.line_numbers", LineNumbers::On),
+        }
+    }
+}
+
+impl Settings {
+    pub fn set_explorer_auto_reveal(&self, auto_reveal: bool) {
+        store(EXPLORER_AUTO_REVEAL_KEY, &auto_reveal.to_string());
+        self.explorer_auto_reveal.set_neq(auto_reveal);
+    }
+
+    pub fn set_explorer_compact_folders(&self, compact_folders: bool) {
+        store(EXPLORER_COMPACT_FOLDERS_KEY, &compact_folders.to_string());
+        self.explorer_compact_folders.set_neq(compact_folders);
+    }
+
+    pub fn set_editor_autosave(&self, autosave: Autosave) {
+        store(EDITOR_AUTOSAVE_KEY, autosave.as_ref());
+        self.editor_autosave.set_neq(autosave);
+    }
+
+    pub fn editor_options(&self, language: Language) -> impl Signal<Item = EditorOptions> {
+        map_ref! {
+            let tab_size = self.editor_tab_size.signal(language),
+            let insert_spaces = self.editor_insert_spaces.signal(language),
+            let detect_indentation = self.editor_detect_indentation.signal(language),
+            let word_wrap = self.editor_word_wrap.signal(language),
+            let font_family = self.editor_font_family.signal(language),
+            let font_size = self.editor_font_size.signal(language),
+            let rulers = self.editor_rulers.signal(language),
+            let render_whitespace = self.editor_render_whitespace.signal(language),
+            let line_numbers = self.editor_line_numbers.signal(language) => {
+                EditorOptions {
+                    tab_size: *tab_size,
+                    insert_spaces: *insert_spaces,
+                    detect_indentation: *detect_indentation,
+                    word_wrap: *word_wrap,
+                    font_family: font_family.clone(),
+                    font_size: *font_size,
+                    rulers: rulers.clone(),
+                    render_whitespace: *render_whitespace,
+                    line_numbers: *line_numbers,
+                }
+            }
+        }
+    }
+}
+
+thread_local! {
+    pub static SETTINGS: Settings = Settings::default();
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+pub fn load<T: FromStr>(key: &str) -> Option<T> {
+    local_storage()?
+        .get_item(key).ok()??
+        .parse().ok()
+}
+
+pub fn remove(key: &str) {
+    if let Some(storage) = local_storage() {
+        if let Err(error) = storage.remove_item(key) {
+            web_sys::console::error_1(&error);
+        }
+    }
+}
+
+pub fn store(key: &str, value: &str) {
+    if let Some(storage) = local_storage() {
+        if let Err(error) = storage.set_item(key, value) {
+            web_sys::console::error_1(&error);
+        }
+    }
+}
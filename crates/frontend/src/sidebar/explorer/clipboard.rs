@@ -0,0 +1,153 @@
+use std::rc::Rc;
+
+use futures_signals::signal::{Mutable, Signal};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::{contextmenu::Target, vfs::Directory, workspace::activity_panel::view_state};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["navigator", "clipboard"], js_name = writeText)]
+    fn clipboard_write_text(text: &str) -> js_sys::Promise;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Cut,
+    Copy,
+}
+
+#[derive(Clone)]
+pub struct Entry {
+    pub operation: Operation,
+    pub target: Target,
+}
+
+thread_local! {
+    pub static CLIPBOARD: Mutable<Option<Entry>> = Mutable::new(None);
+}
+
+pub fn cut(target: &Target) {
+    CLIPBOARD.with(|clipboard| clipboard.set(Some(Entry {
+        operation: Operation::Cut,
+        target: target.clone()
+    })));
+}
+
+pub fn copy(target: &Target) {
+    CLIPBOARD.with(|clipboard| clipboard.set(Some(Entry {
+        operation: Operation::Copy,
+        target: target.clone()
+    })));
+}
+
+// cut items are dimmed in the explorer until they are pasted
+pub fn is_cut(target: Target) -> impl Signal<Item = bool> {
+    CLIPBOARD.with(|clipboard| clipboard.signal_ref(move |entry| entry
+        .as_ref()
+        .is_some_and(|entry| entry.operation == Operation::Cut && entry.target.ptr_eq(&target))))
+}
+
+// pasting onto a file pastes into the directory that contains it
+pub fn paste(destination: &Target, root: &Rc<Directory>) {
+    let Some(entry) = CLIPBOARD.with(|clipboard| clipboard.get_cloned()) else {
+        return;
+    };
+    let destination = match destination {
+        Target::Directory(directory) => directory.clone(),
+        Target::File(_) => match destination.ancestors(root).and_then(|mut ancestors| ancestors.pop()) {
+            Some(parent) => parent,
+            None => return,
+        }
+    };
+    match entry.operation {
+        Operation::Cut => {
+            if let Target::Directory(directory) = &entry.target {
+                let destination_chain = directory_chain(&destination, root);
+                if destination_chain.iter().any(|ancestor| Rc::ptr_eq(ancestor, directory)) {
+                    web_sys::console::warn_1(&"Cannot move a folder into itself".into());
+                    return;
+                }
+            }
+            let is_same_parent = entry.target.ancestors(root)
+                .and_then(|mut ancestors| ancestors.pop())
+                .is_some_and(|parent| Rc::ptr_eq(&parent, &destination));
+            if !is_same_parent {
+                view_state::forget(&entry.target);
+                super::find_and_remove_from_parent(&entry.target, root);
+                match &entry.target {
+                    Target::File(file) => {
+                        file.name.set(unique_name(&destination, &file.name.get_cloned()));
+                        destination.files.lock_mut().push_cloned(file.clone());
+                    }
+                    Target::Directory(directory) => {
+                        directory.name.set(unique_name(&destination, &directory.name.get_cloned()));
+                        destination.directories.lock_mut().push_cloned(directory.clone());
+                    }
+                }
+            }
+            // a cut item can only be pasted once
+            CLIPBOARD.with(|clipboard| clipboard.set(None));
+        }
+        Operation::Copy => match &entry.target {
+            Target::File(file) => {
+                let file = file.duplicate();
+                file.name.set(unique_name(&destination, &file.name.get_cloned()));
+                destination.files.lock_mut().push_cloned(Rc::new(file));
+            }
+            Target::Directory(directory) => {
+                // the contents are taken before the copy is added, so pasting into the folder
+                // itself or into one of its subfolders does not copy the copy again
+                let directory = directory.duplicate();
+                directory.name.set(unique_name(&destination, &directory.name.get_cloned()));
+                destination.directories.lock_mut().push_cloned(Rc::new(directory));
+            }
+        }
+    }
+}
+
+// the chain of directories from the root to and including the destination
+fn directory_chain(destination: &Rc<Directory>, root: &Rc<Directory>) -> Vec<Rc<Directory>> {
+    let mut chain = Target::Directory(destination.clone())
+        .ancestors(root)
+        .unwrap_or_default();
+    chain.push(destination.clone());
+    chain
+}
+
+// appends " copy", " copy 2", etc. before the extension until the name is free
+fn unique_name(destination: &Directory, name: &str) -> String {
+    if !destination.contains_name(name) {
+        return name.to_owned();
+    }
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{extension}")),
+        _ => (name, String::new()),
+    };
+    (1..)
+        .map(|index| match index {
+            1 => format!("{stem} copy{extension}"),
+            _ => format!("{stem} copy {index}{extension}"),
+        })
+        .find(|candidate| !destination.contains_name(candidate))
+        .unwrap()
+}
+
+pub fn copy_path(target: &Target, root: &Rc<Directory>, relative: bool) {
+    let path = match relative {
+        true => target.relative_path(root),
+        false => target.path(root),
+    };
+    if let Some(path) = path {
+        write_text(path);
+    }
+}
+
+pub fn write_text(text: String) {
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(error) = JsFuture::from(clipboard_write_text(&text)).await {
+            web_sys::console::error_1(&error);
+        }
+    });
+}
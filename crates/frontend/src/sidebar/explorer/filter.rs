@@ -0,0 +1,159 @@
+use std::{collections::{HashMap, HashSet}, rc::Rc};
+
+use regex::Regex;
+
+use crate::vfs::{Directory, File};
+
+pub enum Filter {
+    // case-insensitive subsequence match on the file name
+    Fuzzy(Vec<char>),
+    // glob patterns containing a slash are matched against the path relative to the
+    // project, otherwise against the file name (as in .gitignore)
+    Glob { pattern: Regex, match_path: bool },
+}
+
+impl Filter {
+    pub fn new(query: &str, glob: bool) -> Option<Filter> {
+        let query = query.trim();
+        if query.is_empty() {
+            return None;
+        }
+        match glob {
+            true => Regex::new(&glob_to_regex(query)).ok()
+                .map(|pattern| Filter::Glob { pattern, match_path: query.contains('/') }),
+            false => Some(Filter::Fuzzy(query.to_lowercase().chars().collect())),
+        }
+    }
+
+    // returns the indices of the characters in the name that should be highlighted
+    fn matches(&self, path: &str, name: &str) -> Option<Vec<usize>> {
+        match self {
+            Filter::Fuzzy(query) => {
+                // each character is lowercased by itself, lowercasing the whole name can change
+                // the number of characters, e.g., of `İ`, and the indices would drift
+                let mut remaining = query.as_slice();
+                let mut indices = Vec::new();
+                for (index, character) in name.chars().enumerate() {
+                    let lowercase = character.to_lowercase();
+                    let length = lowercase.len();
+                    if remaining.len() >= length && remaining[..length].iter().copied().eq(lowercase) {
+                        indices.push(index);
+                        remaining = &remaining[length..];
+                    }
+                }
+                remaining.is_empty().then_some(indices)
+            }
+            Filter::Glob { pattern, match_path } => {
+                let subject = if *match_path { path } else { name };
+                pattern.is_match(subject).then(|| (0..name.chars().count()).collect())
+            }
+        }
+    }
+}
+
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    let mut characters = glob.chars().peekable();
+    while let Some(character) = characters.next() {
+        match character {
+            '*' if characters.next_if_eq(&'*').is_some() => {
+                match characters.next_if_eq(&'/') {
+                    // `**/` matches zero or more directories
+                    Some(_) => regex.push_str("(?:.*/)?"),
+                    None => regex.push_str(".*"),
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            _ => regex.push_str(&regex::escape(&character.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+// the result of applying a filter to the project, keyed by the address of the vfs nodes
+#[derive(Default)]
+pub struct Matches {
+    files: HashMap<*const File, Vec<usize>>,
+    directories: HashSet<*const Directory>,
+}
+
+impl Matches {
+    pub fn search(root: &Rc<Directory>, filter: &Filter) -> Matches {
+        let mut matches = Matches::default();
+        matches.search_directory(root, "", filter);
+        matches
+    }
+
+    // returns true if anything inside this directory matched
+    fn search_directory(&mut self, directory: &Rc<Directory>, path: &str, filter: &Filter) -> bool {
+        let mut found = false;
+        for file in directory.files.lock_ref().iter() {
+            let name = file.name.lock_ref();
+            if let Some(indices) = filter.matches(&format!("{path}{name}"), &name) {
+                self.files.insert(Rc::as_ptr(file), indices);
+                found = true;
+            }
+        }
+        for child in directory.directories.lock_ref().iter() {
+            let path = format!("{path}{}/", child.name.lock_ref());
+            found |= self.search_directory(child, &path, filter);
+        }
+        if found {
+            self.directories.insert(Rc::as_ptr(directory));
+        }
+        found
+    }
+
+    pub fn file(&self, file: &Rc<File>) -> Option<&[usize]> {
+        self.files.get(&Rc::as_ptr(file)).map(Vec::as_slice)
+    }
+
+    pub fn contains_directory(&self, directory: &Rc<Directory>) -> bool {
+        self.directories.contains(&Rc::as_ptr(directory))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(query: &str, glob: bool, path: &str) -> Option<Vec<usize>> {
+        let name = path.rsplit('/').next().unwrap();
+        Filter::new(query, glob).unwrap().matches(path, name)
+    }
+
+    #[test]
+    fn empty_query() {
+        assert!(Filter::new("  ", false).is_none());
+    }
+
+    #[test]
+    fn fuzzy() {
+        assert_eq!(matches("tlk", false, "src/talker.py"), Some(vec![0, 2, 3]));
+        assert_eq!(matches("TaLk", false, "src/Talker.py"), Some(vec![0, 1, 2, 3]));
+        assert_eq!(matches("klt", false, "src/talker.py"), None);
+        // the directories are not part of the subsequence
+        assert_eq!(matches("srct", false, "src/talker.py"), None);
+    }
+
+    #[test]
+    fn fuzzy_indices_are_characters() {
+        // `İ` lowercases to two characters, the indices after it must not shift
+        assert_eq!(matches("x", false, "İx.py"), Some(vec![1]));
+        assert_eq!(matches("ä", false, "Ärger.py"), Some(vec![0]));
+    }
+
+    #[test]
+    fn glob() {
+        assert_eq!(matches("*.py", true, "src/a.py"), Some(vec![0, 1, 2, 3]));
+        assert_eq!(matches("*.py", true, "src/a.xml"), None);
+        // patterns with a slash match the path
+        assert!(matches("src/*.py", true, "src/a.py").is_some());
+        assert!(matches("src/*.py", true, "src/nested/a.py").is_none());
+        assert!(matches("**/*.py", true, "a.py").is_some());
+        assert!(matches("**/*.py", true, "src/nested/a.py").is_some());
+        assert!(matches("?.py", true, "ab.py").is_none());
+    }
+}
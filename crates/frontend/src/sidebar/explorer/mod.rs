@@ -0,0 +1,856 @@
+use std::{cell::Cell, collections::HashSet, pin::Pin, rc::Rc};
+
+use dominator::{clone, events::{self, MouseButton}, html, svg, Dom, EventOptions, with_node};
+use futures_signals::{map_ref, signal::{self, Mutable, Signal, SignalExt}, signal_vec::{MutableVec, SignalVecExt}};
+
+use strum::IntoEnumIterator;
+
+use crate::{contextmenu::{registry::{self, MenuItem}, ContextMenu, Target}, filetype::FileType, settings::{self, Settings, SETTINGS}, styles::{self, vfs_item::ROW_HEIGHT_PX}, vfs::{self, Directory, File}, workspace::activity_panel::{view_state, Activity}};
+
+use filter::{Filter, Matches};
+use sort::{FolderPlacement, SortOrder};
+use tree::{Path, Row};
+
+pub mod clipboard;
+mod filter;
+mod open_editors;
+mod sort;
+mod tree;
+
+const ICON_SVG_PATH: &str =
+    "M16 0H8C6.9 0 6 .9 6 2V18C6 19.1 6.9 20 8 20H20C21.1 20 22 19.1 22 \
+     18V6L16 0M20 18H8V2H15V7H20V18M4 4V22H20V24H4C2.9 24 2 23.1 2 22V4H4Z";
+
+const TITLE_HEIGHT_PX: u32 = 35;
+// rows rendered above and below the viewport so that scrolling does not show gaps
+const OVERSCAN_ROWS: usize = 10;
+
+thread_local! {
+    static DRAGGED_ITEM: Mutable<Option<Target>> = Mutable::new(None);
+    pub static RENAME: Mutable<Option<Target>> = Mutable::new(None);
+}
+     
+fn folder_open_icon() -> Dom {
+    // downward arrow
+    const FOLDER_OPEN_ICON: &str = "M2,7 12,17 22,7Z";
+    svg!("svg", {
+        .attr("pointer-events", "none")
+        .attr("height", "1em")
+        .attr("viewBox", "0 0 27 27")
+        .child(svg!("path", {
+            .attr("d", FOLDER_OPEN_ICON)
+        }))
+    })
+}
+
+fn folder_closed_icon() -> Dom {
+    // sideways arrow
+    const FOLDER_CLOSED_ICON: &str = "M 7,22 17,12 7,2 Z";
+    svg!("svg", {
+        .attr("pointer-events", "none")
+        .attr("height", "1em")
+        .attr("viewBox", "0 0 27 27")
+        .child(svg!("path", {
+            .attr("d", FOLDER_CLOSED_ICON)
+        }))
+    })
+}
+
+fn file_icon(file: &Rc<File>) -> impl Signal<Item = Option<Dom>> {
+    file.name.signal_ref(|name| Some(FileType::from_name(name).icon.render("1em")))
+}
+
+fn find_and_remove_from_parent(target: &Target, root: &Rc<Directory>) {
+    match target {
+        Target::File(file) => {
+            // Search for the parent directory containing the file
+            let mut files = root.files.lock_mut();
+            if let Some(pos) = files.iter().position(|f| Rc::ptr_eq(f, file)) {
+                files.remove(pos); // Remove the file
+                return;
+            }
+        }
+        Target::Directory(dragged_dir) => {
+            // Search for the parent directory containing the directory
+            let mut directories = root.directories.lock_mut();
+            if let Some(pos) = directories.iter().position(|d| Rc::ptr_eq(d, dragged_dir)) {
+                directories.remove(pos); // Remove the directory
+                return;
+            }
+        }
+    }
+
+    // Recursively search child directories
+    for child in root.directories.lock_ref().iter() {
+        find_and_remove_from_parent(target, child);
+    }
+}
+
+// splits the name into spans so that the characters matched by the filter can be highlighted
+fn highlight_name(name: &str, indices: &[usize]) -> Vec<Dom> {
+    let mut segments: Vec<(bool, String)> = Vec::new();
+    for (index, character) in name.chars().enumerate() {
+        let highlighted = indices.contains(&index);
+        match segments.last_mut() {
+            Some((last_highlighted, text)) if *last_highlighted == highlighted => text.push(character),
+            _ => segments.push((highlighted, character.to_string())),
+        }
+    }
+    segments.into_iter()
+        .map(|(highlighted, text)| html!("span", {
+            .apply_if(highlighted, styles::vfs_item::highlight)
+            .text(&text)
+        }))
+        .collect()
+}
+
+fn render_file_name(this: &Rc<Explorer>, file: &Rc<File>) -> Dom {
+    let file = file.clone();
+    let segments = map_ref! {
+        let name = file.name.signal_cloned(),
+        let matches = this.matches.signal_cloned() => {
+            let indices = matches.as_ref()
+                .and_then(|matches| matches.file(&file))
+                .unwrap_or_default();
+            highlight_name(name, indices)
+        }
+    };
+    html!("span", {
+        .children_signal_vec(segments.to_signal_vec())
+    })
+}
+
+// moves the dragged item into the directory unless the directory is the item itself or inside it
+fn drop_into(directory: &Rc<Directory>) {
+    DRAGGED_ITEM.with(|dragged| {
+        if let Some(target) = dragged.get_cloned() {
+            crate::PROJECT.with(|root| {
+                if let Target::Directory(dragged_dir) = &target {
+                    let mut chain = Target::Directory(directory.clone())
+                        .ancestors(root)
+                        .unwrap_or_default();
+                    chain.push(directory.clone());
+                    if chain.iter().any(|ancestor| Rc::ptr_eq(ancestor, dragged_dir)) {
+                        return;
+                    }
+                }
+                // Remove the dragged item from its original parent
+                view_state::forget(&target);
+                find_and_remove_from_parent(&target, root);
+
+                // Add the dragged item to the target directory
+                match target {
+                    Target::File(file) => directory.files.lock_mut().push_cloned(file),
+                    Target::Directory(dragged_dir) => directory.directories.lock_mut().push_cloned(dragged_dir),
+                }
+            });
+        }
+    });
+}
+
+fn render_rename_input(target: &Target) -> Dom {
+    let target = target.clone();
+    // before the first change of the name, while the path still leads to the view states
+    let forgotten = Cell::new(false);
+    let name = match target {
+        Target::File(file) => file.name.clone(),
+        Target::Directory(directory) => directory.name.clone(),
+    };
+    html!("input" => web_sys::HtmlInputElement, {
+        .apply(styles::input)
+        .attr("type", "text")
+        .attr("value", &name.get_cloned())
+        .focused(true)
+        .with_node!(element => {
+            .event(move |_: events::Input| {
+                if !forgotten.replace(true) {
+                    view_state::forget(&target);
+                }
+                name.set(element.value());
+            })
+            .event(|_: events::Blur| {
+                RENAME.with(|rename| rename.set(None));
+            })
+            .event(|event: events::KeyDown| {
+                if event.key() == "Enter" {
+                    RENAME.with(|rename| rename.set(None));
+                }
+            })
+        })
+    })
+}
+
+fn render_name(this: &Rc<Explorer>, target: &Target) -> Dom {
+    html!("div", {
+        // input box for renaming
+        .child_signal(RENAME.with(|rename| rename.signal_cloned().map(clone!(this, target => move |renaming| {
+            match renaming {
+                Some(renaming) if renaming.ptr_eq(&target) => Some(render_rename_input(&target)),
+                _ => Some(match &target {
+                    Target::File(file) => render_file_name(&this, file),
+                    Target::Directory(directory) => html!("span", {
+                        .text_signal(directory.name.signal_cloned())
+                    }),
+                }),
+            }
+        }))))
+    })
+}
+
+// the compacted directories in front of the name of the row, each of them accepts drops itself
+fn render_chain(row: &Row) -> Vec<Dom> {
+    row.chain.iter()
+        .map(|directory| html!("span", {
+            .class("hover:underline")
+            .text_signal(directory.name.signal_ref(|name| format!("{name}/")))
+            .event_with_options(&EventOptions::preventable(), clone!(directory => move |event: events::Drop| {
+                event.prevent_default();
+                // the row would move the item into its own directory otherwise
+                event.stop_propagation();
+                drop_into(&directory);
+            }))
+        }))
+        .collect()
+}
+
+// the rendered rows follow the window with as few changes as possible, so that scrolling only
+// renders the rows that come into view and removes the ones that leave it
+fn update_window(window: &MutableVec<Row>, window_start: &Cell<usize>, start: usize, rows: &[Row]) {
+    let mut window = window.lock_mut();
+    let old_start = window_start.replace(start);
+    let old_end = old_start + window.len();
+    let end = start + rows.len();
+    let overlap = start.max(old_start)..end.min(old_end);
+    let unchanged = !overlap.is_empty() && overlap.clone()
+        .all(|index| window[index - old_start].is_same(&rows[index - start]));
+    // the tree changed within the window
+    if !unchanged {
+        window.replace_cloned(rows.to_vec());
+        return;
+    }
+    for _ in overlap.end..old_end {
+        window.pop();
+    }
+    for _ in old_start..overlap.start {
+        window.remove(0);
+    }
+    for (index, row) in rows[..overlap.start - start].iter().enumerate() {
+        window.insert_cloned(index, row.clone());
+    }
+    for row in &rows[overlap.end - start..] {
+        window.push_cloned(row.clone());
+    }
+}
+
+fn render_directory_row(this: &Rc<Explorer>, row: &Row, directory: &Rc<Directory>, path: &Path) -> Dom {
+    let target = row.node.clone();
+    html!("div", {
+        .apply(|dom| styles::vfs_item::row(dom, row.depth))
+        // the root of the project cannot be moved
+        .attr("draggable", if row.parent.is_some() { "true" } else { "false" })
+        .event(clone!(target => move |_: events::DragStart| {
+            DRAGGED_ITEM.with(|dragged| {
+                dragged.set(Some(target.clone()));
+            })
+        }))
+        .event_with_options(&EventOptions::preventable(), |event: events::DragOver| {
+            event.prevent_default(); // Allow drop
+        })
+        .event_with_options(&EventOptions::preventable(), clone!(directory => move |event: events::Drop| {
+            event.prevent_default();
+            drop_into(&directory);
+        }))
+        .event(|_: events::DragEnd| {
+            DRAGGED_ITEM.with(|dragged| {
+                dragged.set(None);
+            });
+        })
+        .apply(styles::vfs_item::body)
+        .apply(|dom| styles::vfs_item::selected(dom, this.is_selected(target.clone())))
+        .class_signal("opacity-50", clipboard::is_cut(target.clone()))
+        .event(clone!(this, target => move |_: events::MouseDown| {
+            this.selected.set(Some(target.clone()));
+        }))
+        .event(clone!(this, target => move |event: events::MouseDown| {
+            // left click to expand directory
+            let rename = RENAME.with(|rename| rename.get_cloned().is_some());
+            let is_drag_and_drop = DRAGGED_ITEM.with(|dragged| dragged.get_cloned().is_some());
+            if !rename && !is_drag_and_drop && event.button() == MouseButton::Left {
+                if let Some(path) = crate::PROJECT.with(|root| target.path(root)) {
+                    let mut expanded = this.expanded.lock_mut();
+                    if !expanded.remove(&path) {
+                        expanded.insert(path);
+                    }
+                }
+            }
+        }))
+        .children(&mut [
+            html!("div", {
+                .apply(styles::vfs_item::icon)
+                .child_signal(this.expanded_signal(path).map(|expanded| match expanded {
+                    true => folder_open_icon(),
+                    false => folder_closed_icon(),
+                }.into()))
+            }),
+            html!("div", {
+                .class("flex")
+                .children(render_chain(row))
+                .child(render_name(this, &target))
+            }),
+        ])
+        // event listener for right click
+        .event(clone!(this, target => move |event: events::ContextMenu| {
+            web_sys::console::log_1(&"Right-clicked".into());
+            this.context_menu.set(Some(ContextMenu::new(
+                (event.x(), event.y()),
+                target.clone(),
+            )));
+        }))
+    })
+}
+
+fn render_file_row(
+    this: &Rc<Explorer>,
+    row: &Row,
+    file: &Rc<File>,
+    workspace_command_tx: &crate::WorkspaceCommandSender
+) -> Dom {
+    let target = row.node.clone();
+    html!("div", {
+        .apply(|dom| styles::vfs_item::row(dom, row.depth))
+        .attr("draggable", "true")
+        .event(clone!(target => move |_: events::DragStart| {
+            DRAGGED_ITEM.with(|dragged| {
+                dragged.set(Some(target.clone()));
+            })
+        }))
+        .event_with_options(&EventOptions::preventable(), |event: events::DragOver| {
+            event.prevent_default(); // Allow drop
+        })
+        // dropping onto a file moves the item into the directory that contains the file
+        .event_with_options(&EventOptions::preventable(), clone!(row => move |event: events::Drop| {
+            event.prevent_default();
+            if let Some(parent) = &row.parent {
+                drop_into(parent);
+            }
+        }))
+        .event(|_: events::DragEnd| {
+            DRAGGED_ITEM.with(|dragged| {
+                dragged.set(None);
+            });
+        })
+        .apply(styles::vfs_item::body)
+        .apply(|dom| styles::vfs_item::selected(dom, this.is_selected(target.clone())))
+        .class_signal("opacity-50", clipboard::is_cut(target.clone()))
+        .class_signal("font-semibold", this.active_file.signal_ref(clone!(file => move |active_file| active_file
+            .as_ref()
+            .is_some_and(|active_file| Rc::ptr_eq(active_file, &file)))))
+        .event(clone!(this, target => move |_: events::MouseDown| {
+            this.selected.set(Some(target.clone()));
+        }))
+        .event(clone!(workspace_command_tx, file => move |event: events::MouseDown| {
+            // left-click to open file in workspace
+            let rename = RENAME.with(|rename| rename.get_cloned().is_some());
+            let is_drag_and_drop = DRAGGED_ITEM.with(|dragged| dragged.get_cloned().is_some());
+            if !rename && !is_drag_and_drop && event.button() == MouseButton::Left {
+                // alt-click opens the file next to the active editor group
+                let command = match event.alt_key() {
+                    true => crate::WorkspaceCommand::OpenFileToSide(file.clone()),
+                    false => crate::WorkspaceCommand::OpenFile(file.clone()),
+                };
+                workspace_command_tx
+                    .unbounded_send(command)
+                    .unwrap()
+            }
+        }))
+        .event(clone!(workspace_command_tx, file => move |_: events::DoubleClick| {
+            workspace_command_tx
+                .unbounded_send(crate::WorkspaceCommand::KeepFileOpen(file.clone()))
+                .unwrap()
+        }))
+        .children(&mut [
+            html!("div", {
+                .apply(styles::vfs_item::icon)
+                .child_signal(file_icon(file))
+            }),
+            render_name(this, &target),
+        ])
+        // event listener for right click
+        .event(clone!(this, target => move |event: events::ContextMenu| {
+            web_sys::console::log_1(&"Right-clicked".into());
+            this.context_menu.set(Some(ContextMenu::new(
+                (event.x(), event.y()),
+                target.clone(),
+            )));
+        }))
+    })
+}
+
+fn render_row(this: &Rc<Explorer>, row: &Row, workspace_command_tx: &crate::WorkspaceCommandSender) -> Dom {
+    match &row.node {
+        Target::Directory(directory) => {
+            let path = row.path.as_ref().expect("directory rows have a path");
+            render_directory_row(this, row, directory, path)
+        }
+        Target::File(file) => render_file_row(this, row, file, workspace_command_tx),
+    }
+}
+
+fn sort_order_key(project: &str) -> String {
+    format!("explorer.sort_order.{project}")
+}
+
+fn folder_placement_key(project: &str) -> String {
+    format!("explorer.folder_placement.{project}")
+}
+
+// the clipboard entries of the context menu, the explorer runs them on their shortcuts too
+pub fn register_menu_items(workspace_command_tx: &crate::WorkspaceCommandSender) {
+    registry::register(MenuItem::new("Open to the Side")
+        .group("0_navigation", 1)
+        .keybinding("Alt+Click")
+        .when("isFile")
+        .action(clone!(workspace_command_tx => move |target| {
+            if let Target::File(file) = target {
+                workspace_command_tx
+                    .unbounded_send(crate::WorkspaceCommand::OpenFileToSide(file.clone()))
+                    .unwrap();
+            }
+        })));
+    registry::register(MenuItem::new("Cut")
+        .group("5_cutcopypaste", 1)
+        .keybinding("Ctrl+X")
+        .when("!isRoot")
+        .enabled_when("!isReadonly")
+        .action(clipboard::cut));
+    registry::register(MenuItem::new("Copy")
+        .group("5_cutcopypaste", 2)
+        .keybinding("Ctrl+C")
+        .when("!isRoot")
+        .action(clipboard::copy));
+    registry::register(MenuItem::new("Paste")
+        .group("5_cutcopypaste", 3)
+        .keybinding("Ctrl+V")
+        .enabled_when("fileCopied")
+        .action(|target| crate::PROJECT.with(|root| clipboard::paste(target, root))));
+    registry::register(MenuItem::new("Copy Path")
+        .group("6_copypath", 1)
+        .action(|target| crate::PROJECT.with(|root| clipboard::copy_path(target, root, false))));
+    registry::register(MenuItem::new("Copy Relative Path")
+        .group("6_copypath", 2)
+        .action(|target| crate::PROJECT.with(|root| clipboard::copy_path(target, root, true))));
+}
+
+pub struct Explorer {
+    workspace: Rc<Directory>,
+    // context menu
+    context_menu: Mutable<Option<ContextMenu>>,
+    // filter
+    filter_query: Mutable<String>,
+    filter_glob: Mutable<bool>,
+    matches: Mutable<Option<Rc<Matches>>>,
+    // the item that the clipboard shortcuts act on
+    selected: Mutable<Option<Target>>,
+    // the activity of the active group and its file, the same file can be open in several groups
+    active_activity: Mutable<Option<Rc<Activity>>>,
+    active_file: Mutable<Option<Rc<File>>>,
+    // the file that should be scrolled into view once its row is rendered
+    reveal_request: Mutable<Option<Rc<File>>>,
+    // scroll offset of the project listing in pixels
+    scroll_top: Mutable<u32>,
+    // sorting, stored per project
+    sort_order: Mutable<SortOrder>,
+    folder_placement: Mutable<FolderPlacement>,
+    sort_menu: Mutable<Option<(i32, i32)>>,
+    // the activities that are open in the workspace
+    open_activities: MutableVec<Rc<Activity>>,
+    open_editors_expanded: Mutable<bool>,
+    // the paths of the expanded directories, so that the project tree does not hold the state of
+    // the view
+    expanded: Mutable<HashSet<String>>,
+}
+
+impl Default for Explorer {
+    fn default() -> Self {
+        let workspace = crate::PROJECT.with(|workspace| Rc::clone(workspace));
+        let project = workspace.name.get_cloned();
+        Self {
+            sort_order: Mutable::new(settings::load(&sort_order_key(&project)).unwrap_or_default()),
+            folder_placement: Mutable::new(settings::load(&folder_placement_key(&project)).unwrap_or_default()),
+            sort_menu: Mutable::new(None),
+            open_activities: MutableVec::new(),
+            open_editors_expanded: Mutable::new(true),
+            workspace,
+            context_menu: Mutable::new(None),
+            filter_query: Mutable::new(String::new()),
+            filter_glob: Mutable::new(false),
+            matches: Mutable::new(None),
+            selected: Mutable::new(None),
+            active_activity: Mutable::new(None),
+            active_file: Mutable::new(None),
+            reveal_request: Mutable::new(None),
+            scroll_top: Mutable::new(0),
+            expanded: Mutable::new(HashSet::from([format!("/{project}")])),
+        }
+    }
+}
+
+impl Explorer {
+    pub fn set_active_activity(&self, activity: Option<Rc<Activity>>) {
+        let file = activity.as_ref().and_then(|activity| activity.file().cloned());
+        self.active_activity.set(activity);
+        if let Some(file) = &file {
+            if SETTINGS.with(|settings| settings.explorer_auto_reveal.get()) {
+                self.reveal(file);
+            }
+        }
+        self.active_file.set(file);
+    }
+
+    pub fn set_open_activities(&self, activities: Vec<Rc<Activity>>) {
+        self.open_activities.lock_mut().replace_cloned(activities);
+    }
+
+    // expands the ancestors of the file, selects it and scrolls it into view
+    pub fn reveal(&self, file: &Rc<File>) {
+        if let Some(ancestors) = vfs::ancestors_of_file(&self.workspace, file) {
+            let mut expanded = self.expanded.lock_mut();
+            let mut path = String::new();
+            for directory in ancestors {
+                path = format!("{path}/{}", directory.name.lock_ref());
+                expanded.insert(path.clone());
+            }
+            drop(expanded);
+            self.selected.set(Some(Target::File(file.clone())));
+            self.reveal_request.set(Some(file.clone()));
+        }
+    }
+
+    fn is_selected(&self, target: Target) -> impl Signal<Item = bool> {
+        self.selected.signal_ref(move |selected| selected
+            .as_ref()
+            .is_some_and(|selected| selected.ptr_eq(&target)))
+    }
+
+    fn handle_shortcut(&self, event: &events::KeyDown) {
+        let rename = RENAME.with(|rename| rename.lock_ref().is_some());
+        let Some(target) = self.selected.get_cloned() else {
+            return;
+        };
+        if rename {
+            return;
+        }
+        let key = event.key();
+        let keybinding = match event.ctrl_key() || event.meta_key() {
+            true => format!("Ctrl+{}", key.to_uppercase()),
+            false => key,
+        };
+        if registry::run_keybinding(&keybinding, &target, &self.workspace) {
+            event.prevent_default();
+        }
+    }
+
+    // searched again on each change of the filter and of the project tree, so that created, pasted
+    // and renamed files are filtered too
+    fn matches_signal(&self) -> impl Signal<Item = Option<Rc<Matches>>> {
+        let workspace = Rc::clone(&self.workspace);
+        map_ref! {
+            let query = self.filter_query.signal_cloned(),
+            let glob = self.filter_glob.signal() => Filter::new(query, *glob).map(Rc::new)
+        }.switch(move |filter| -> Pin<Box<dyn Signal<Item = Option<Rc<Matches>>>>> {
+            match filter {
+                Some(filter) => Box::pin(workspace.changes().map(clone!(workspace => move |_| {
+                    Some(Rc::new(Matches::search(&workspace, &filter)))
+                }))),
+                None => Box::pin(signal::always(None)),
+            }
+        })
+    }
+
+    // directories are kept expanded while a filter is active so that all matches are visible
+    fn expanded_signal(&self, path: &Path) -> impl Signal<Item = bool> {
+        let expanded = self.expanded.clone();
+        let expanded = path.signal_cloned()
+            .switch(move |path| expanded.signal_ref(move |expanded| expanded.contains(&path)));
+        signal::or(expanded, self.matches.signal_ref(Option::is_some))
+    }
+
+    fn render_setting_toggle(setting: Mutable<bool>, set: fn(&Settings, bool), title: &str, icon_path: &str) -> Dom {
+        html!("div", {
+            .apply(|dom| styles::panel::toggle(dom, setting.signal()))
+            .class("ml-1")
+            .attr("title", title)
+            .child(svg!("svg", {
+                .attr("pointer-events", "none")
+                .attr("height", "1.25em")
+                .attr("viewBox", "0 0 24 24")
+                .attr("fill", "currentColor")
+                .child(svg!("path", {
+                    .attr("d", icon_path)
+                }))
+            }))
+            .event(move |_: events::MouseDown| {
+                let value = !setting.get();
+                SETTINGS.with(|settings| set(settings, value));
+            })
+        })
+    }
+
+    fn render_auto_reveal_toggle() -> Dom {
+        // crosshairs
+        const ICON_PATH: &str = "M12,8A4,4 0 0,1 16,12A4,4 0 0,1 12,16A4,4 0 0,1 8,12A4,4 0 0,1 12,\
+            8M3.05,13H1V11H3.05C3.5,6.83 6.83,3.5 11,3.05V1H13V3.05C17.17,3.5 20.5,6.83 20.95,\
+            11H23V13H20.95C20.5,17.17 17.17,20.5 13,20.95V23H11V20.95C6.83,20.5 3.5,17.17 3.05,\
+            13M12,5A7,7 0 0,0 5,12A7,7 0 0,0 12,19A7,7 0 0,0 19,12A7,7 0 0,0 12,5Z";
+        let auto_reveal = SETTINGS.with(|settings| settings.explorer_auto_reveal.clone());
+        Self::render_setting_toggle(auto_reveal, Settings::set_explorer_auto_reveal, "Reveal the active file automatically", ICON_PATH)
+    }
+
+    fn render_compact_folders_toggle() -> Dom {
+        // stacked folders
+        const ICON_PATH: &str = "M22,4H14L12,2H6A2,2 0 0,0 4,4V16A2,2 0 0,0 6,18H22A2,2 0 0,0 24,\
+            16V6A2,2 0 0,0 22,4M2,6H0V11H0V20A2,2 0 0,0 2,22H20V20H2V6Z";
+        let compact_folders = SETTINGS.with(|settings| settings.explorer_compact_folders.clone());
+        Self::render_setting_toggle(compact_folders, Settings::set_explorer_compact_folders, "Compact folders that only contain a single folder", ICON_PATH)
+    }
+
+    fn render_filter(this: &Rc<Explorer>) -> Dom {
+        html!("div", {
+            .apply(styles::panel::filter)
+            .child(html!("input" => web_sys::HtmlInputElement, {
+                .apply(styles::input)
+                .attr("type", "text")
+                .attr("placeholder", "Filter")
+                .attr("value", &this.filter_query.lock_ref())
+                .with_node!(element => {
+                    .event(clone!(this, element => move |_: events::Input| {
+                        this.filter_query.set(element.value());
+                    }))
+                    .event(clone!(this => move |event: events::KeyDown| {
+                        if event.key() == "Escape" {
+                            element.set_value("");
+                            this.filter_query.set(String::new());
+                        }
+                    }))
+                })
+            }))
+            .child(html!("div", {
+                .apply(|dom| styles::panel::toggle(dom, this.filter_glob.signal()))
+                .attr("title", "Use glob pattern, e.g., **/*.py")
+                .text("**")
+                .event(clone!(this => move |_: events::MouseDown| {
+                    this.filter_glob.set(!this.filter_glob.get());
+                }))
+            }))
+        })
+    }
+
+    fn set_sort_order(&self, order: SortOrder) {
+        settings::store(&sort_order_key(&self.workspace.name.lock_ref()), order.as_ref());
+        self.sort_order.set_neq(order);
+    }
+
+    fn set_folder_placement(&self, placement: FolderPlacement) {
+        settings::store(&folder_placement_key(&self.workspace.name.lock_ref()), placement.as_ref());
+        self.folder_placement.set_neq(placement);
+    }
+
+    fn render_sort_button(this: &Rc<Explorer>) -> Dom {
+        // lines of decreasing length
+        const ICON_PATH: &str = "M3,13H15V11H3M3,6V8H21V6M3,18H9V16H3V18Z";
+        html!("div", {
+            .apply(|dom| styles::panel::toggle(dom, this.sort_menu.signal_ref(Option::is_some)))
+            .class("ml-1")
+            .attr("title", "Sort Order")
+            .child(svg!("svg", {
+                .attr("pointer-events", "none")
+                .attr("height", "1.25em")
+                .attr("viewBox", "0 0 24 24")
+                .attr("fill", "currentColor")
+                .child(svg!("path", {
+                    .attr("d", ICON_PATH)
+                }))
+            }))
+            .event(clone!(this => move |event: events::Click| {
+                // the global click listener would close the menu again straight away
+                event.stop_propagation();
+                let position = match this.sort_menu.get() {
+                    Some(_) => None,
+                    None => Some((event.x(), event.y())),
+                };
+                this.sort_menu.set(position);
+            }))
+        })
+    }
+
+    fn render_sort_option(label: &str, checked: impl Signal<Item = bool> + 'static, select: impl Fn() + 'static) -> Dom {
+        html!("div", {
+            .apply(styles::contextmenu::option)
+            .class("relative")
+            .child(html!("span", {
+                .class("absolute")
+                .class("left-1")
+                .visible_signal(checked)
+                .text("✓")
+            }))
+            .text(label)
+            .event(move |_: events::MouseDown| select())
+        })
+    }
+
+    fn render_sort_menu(this: &Rc<Explorer>, (x, y): (i32, i32)) -> Dom {
+        html!("div", {
+            .apply(|dom| styles::contextmenu::body(dom, &x, &y))
+            .children(SortOrder::iter().map(|order| Self::render_sort_option(
+                order.label(),
+                this.sort_order.signal_ref(move |current| *current == order),
+                clone!(this => move || this.set_sort_order(order)),
+            )))
+            .child(html!("hr", {
+                .class("my-1")
+                .class("opacity-20")
+            }))
+            .children(FolderPlacement::iter().map(|placement| Self::render_sort_option(
+                placement.label(),
+                this.folder_placement.signal_ref(move |current| *current == placement),
+                clone!(this => move || this.set_folder_placement(placement)),
+            )))
+        })
+    }
+
+    pub fn render(this: &Rc<Explorer>, workspace_command_tx: &crate::WorkspaceCommandSender) -> dominator::Dom {
+        let rows = tree::directory_rows(this, &this.workspace, None, tree::root_path(&this.workspace), 0).broadcast();
+        let open_editors_height = open_editors::height(this).broadcast();
+        let viewport_height = map_ref! {
+            let size = dominator::window_size(),
+            let open_editors_height = open_editors_height.signal() =>
+            (size.height.max(0.0) as u32).saturating_sub(TITLE_HEIGHT_PX + open_editors_height)
+        };
+        let renamed_row = map_ref! {
+            let rows = rows.signal_cloned(),
+            let renaming = RENAME.with(|rename| rename.signal_cloned()) => {
+                renaming.as_ref().and_then(|target| rows.position(&|row| row.node.ptr_eq(target)))
+            }
+        };
+        // only the rows in the viewport (and a few around it) are rendered
+        let visible_rows = map_ref! {
+            let rows = rows.signal_cloned(),
+            let scroll_top = this.scroll_top.signal(),
+            let viewport_height = viewport_height,
+            let renamed_row = renamed_row => {
+                let first = (*scroll_top / ROW_HEIGHT_PX) as usize;
+                let count = (*viewport_height / ROW_HEIGHT_PX) as usize + 1;
+                let start = first.saturating_sub(OVERSCAN_ROWS).min(rows.len());
+                let end = (first + count + OVERSCAN_ROWS).min(rows.len());
+                // the row that is being renamed stays rendered, its input would lose the name
+                // that is being typed otherwise
+                let (start, end) = match renamed_row {
+                    Some(index) => (start.min(*index), end.max(index + 1)),
+                    None => (start, end),
+                };
+                (start, rows.slice(start..end))
+            }
+        }.broadcast();
+        let window = MutableVec::new();
+        let window_start = Cell::new(0);
+        let reveal = map_ref! {
+            let rows = rows.signal_cloned(),
+            let request = this.reveal_request.signal_cloned() => {
+                request.as_ref().and_then(|file| rows
+                    .position(&|row| matches!(&row.node, Target::File(candidate) if Rc::ptr_eq(candidate, file))))
+            }
+        };
+
+        html!("div", {
+            .apply(styles::panel::body)
+            .future(this.matches_signal().for_each(clone!(this => move |matches| {
+                this.matches.set(matches);
+                async {}
+            })))
+            .child(html!("div", {
+                .apply(styles::panel::title)
+                .child(html!("span", {
+                    .apply(styles::panel::title_text)
+                    .text("Explorer")
+                }))
+                .child(Self::render_filter(this))
+                .child(Self::render_auto_reveal_toggle())
+                .child(Self::render_compact_folders_toggle())
+                .child(Self::render_sort_button(this))
+            }))
+            .child(open_editors::render(this, workspace_command_tx))
+            // project listing
+            .child(html!("div", {
+                .apply(styles::vfs_item::viewport)
+                .style_signal("height", open_editors_height.signal_ref(|height| {
+                    format!("calc(100% - {}px)", TITLE_HEIGHT_PX + height)
+                }))
+                // focusable so that the clipboard shortcuts reach the explorer
+                .attr("tabindex", "0")
+                .event_with_options(&EventOptions::preventable(), clone!(this => move |event: events::KeyDown| {
+                    this.handle_shortcut(&event);
+                }))
+                .with_node!(element => {
+                    .event(clone!(this, element => move |_: events::Scroll| {
+                        this.scroll_top.set_neq(element.scroll_top().max(0) as u32);
+                    }))
+                    // scroll a revealed file into view once its row exists
+                    .future(reveal.for_each(clone!(this => move |index| {
+                        if let Some(index) = index {
+                            let top = index as i32 * ROW_HEIGHT_PX as i32;
+                            let scroll_top = element.scroll_top();
+                            let height = element.client_height();
+                            if top < scroll_top || top + ROW_HEIGHT_PX as i32 > scroll_top + height {
+                                element.set_scroll_top((top - height / 2).max(0));
+                            }
+                            this.reveal_request.set(None);
+                        }
+                        async {}
+                    })))
+                })
+                .child(html!("div", {
+                    .style_signal("height", rows.signal_ref(|rows| format!("{}px", rows.len() as u32 * ROW_HEIGHT_PX)))
+                    .style_signal("padding-top", visible_rows.signal_ref(|(start, _)| format!("{}px", *start as u32 * ROW_HEIGHT_PX)))
+                    .future(visible_rows.signal_cloned().for_each(clone!(window => move |(start, rows)| {
+                        update_window(&window, &window_start, start, &rows);
+                        async {}
+                    })))
+                    .children_signal_vec(window.signal_vec_cloned()
+                        .map(clone!(this, workspace_command_tx => move |row| render_row(&this, &row, &workspace_command_tx))))
+                }))
+            }))
+            // check for update in show to render context menu
+            .child_signal(this.context_menu.signal_ref(clone!(this => move |menu_state| {
+                menu_state.as_ref().map(|context_menu| ContextMenu::render(
+                    context_menu,
+                    Rc::new(clone!(this => move || this.context_menu.set(None)))
+                ))
+            })))
+            .child_signal(this.sort_menu.signal().map(clone!(this => move |position| {
+                position.map(|position| Self::render_sort_menu(&this, position))
+            })))
+            // prevents default chrome context menu for the whole vfs structure
+            .event_with_options(&EventOptions::preventable(), |event: events::ContextMenu| {
+                event.prevent_default();
+            })
+            // global event listener to close the sort menu, the context menu closes itself
+            .global_event(clone!(this => move |_:events::Click| {
+                this.sort_menu.set(None);
+            }))
+        })
+    }
+
+    pub fn tooltip(&self) -> &'static str {
+        "Explorer"
+    }
+
+    pub fn icon(&self, active: impl Signal<Item = bool> + 'static) -> Dom {
+        svg!("svg", {
+            .apply(|dom| styles::menu::button_toggle(dom, active))
+            .attr("viewBox", "0 0 27 27")
+            .child(svg!("path", {
+                .attr("d", ICON_SVG_PATH)
+            }))
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,125 @@
+use std::rc::Rc;
+
+use dominator::{clone, events, html, svg, Dom, EventOptions};
+use futures_signals::{map_ref, signal::{Mutable, Signal, SignalExt}, signal_vec::SignalVecExt};
+
+use crate::{styles::{self, vfs_item::ROW_HEIGHT_PX}, workspace::activity_panel::{Activity, CHANGED_ICON_PATH, CLOSE_ICON_PATH}};
+
+use super::{folder_closed_icon, folder_open_icon, Explorer};
+
+// the height of the section, the header is always shown
+pub fn height(this: &Explorer) -> impl Signal<Item = u32> {
+    map_ref! {
+        let expanded = this.open_editors_expanded.signal(),
+        let count = this.open_activities.signal_vec_cloned().len() => {
+            ROW_HEIGHT_PX * (1 + if *expanded { *count as u32 } else { 0 })
+        }
+    }
+}
+
+fn render_close_button(activity: &Rc<Activity>, workspace_command_tx: &crate::WorkspaceCommandSender) -> Dom {
+    let mouse_over = Mutable::new(false);
+    // a dirty activity shows a dot until the pointer is over the button
+    let icon_path = map_ref! {
+        let dirty = activity.dirty(),
+        let mouse_over = mouse_over.signal() => {
+            match *dirty && !*mouse_over {
+                true => CHANGED_ICON_PATH,
+                false => CLOSE_ICON_PATH,
+            }
+        }
+    };
+    html!("div", {
+        .apply(styles::icon)
+        .attr("title", "Close")
+        .event(clone!(mouse_over => move |_: events::PointerOver| {
+            mouse_over.set_neq(true);
+        }))
+        .event(clone!(mouse_over => move |_: events::PointerOut| {
+            mouse_over.set_neq(false);
+        }))
+        .event_with_options(&EventOptions::preventable(), clone!(activity, workspace_command_tx => move |event: events::MouseDown| {
+            event.stop_propagation();
+            workspace_command_tx
+                .unbounded_send(crate::WorkspaceCommand::CloseActivity(activity.clone()))
+                .unwrap();
+        }))
+        .child(svg!("svg", {
+            .attr("pointer-events", "none")
+            .attr("height", "1em")
+            .attr("viewBox", "0 0 24 24")
+            .child(svg!("path", {
+                .attr_signal("d", icon_path)
+            }))
+        }))
+    })
+}
+
+fn render_activity(
+    this: &Rc<Explorer>,
+    activity: &Rc<Activity>,
+    workspace_command_tx: &crate::WorkspaceCommandSender
+) -> Dom {
+    let is_active = this.active_activity.signal_ref(clone!(activity => move |active_activity| active_activity
+        .as_ref()
+        .is_some_and(|active_activity| Rc::ptr_eq(active_activity, &activity))));
+    html!("div", {
+        .apply(|dom| styles::vfs_item::row(dom, 1))
+        .apply(styles::vfs_item::body)
+        .apply(|dom| styles::vfs_item::selected(dom, is_active))
+        .event(clone!(activity, workspace_command_tx => move |event: events::MouseDown| {
+            if event.button() == events::MouseButton::Left {
+                workspace_command_tx
+                    .unbounded_send(crate::WorkspaceCommand::ActivateActivity(activity.clone()))
+                    .unwrap();
+            }
+        }))
+        .child(html!("div", {
+            .apply(styles::vfs_item::icon)
+            .child_signal(activity.icon())
+        }))
+        .child(html!("div", {
+            .class("flex")
+            .class("justify-between")
+            .child(html!("div", {
+                .class("truncate")
+                .child(activity.label())
+            }))
+            // like the tab bar, the welcome page cannot be closed
+            .apply_if(activity.file().is_some(), |dom| {
+                dom.child(render_close_button(activity, workspace_command_tx))
+            })
+        }))
+    })
+}
+
+pub fn render(this: &Rc<Explorer>, workspace_command_tx: &crate::WorkspaceCommandSender) -> Dom {
+    html!("div", {
+        .child(html!("div", {
+            .apply(|dom| styles::vfs_item::row(dom, 0))
+            .apply(styles::vfs_item::body)
+            .event(clone!(this => move |_: events::MouseDown| {
+                this.open_editors_expanded.set(!this.open_editors_expanded.get());
+            }))
+            .child(html!("div", {
+                .apply(styles::vfs_item::icon)
+                .child_signal(this.open_editors_expanded.signal().map(|expanded| match expanded {
+                    true => folder_open_icon(),
+                    false => folder_closed_icon(),
+                }.into()))
+            }))
+            .child(html!("span", {
+                .apply(styles::panel::title_text)
+                .class("font-semibold")
+                .text("Open Editors")
+            }))
+        }))
+        .child(html!("div", {
+            .visible_signal(this.open_editors_expanded.signal())
+            .children_signal_vec(this.open_activities.signal_vec_cloned()
+                .map(clone!(this, workspace_command_tx => move |activity| {
+                    render_activity(&this, &activity, &workspace_command_tx)
+                })))
+        }))
+    })
+}
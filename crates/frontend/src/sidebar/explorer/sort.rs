@@ -0,0 +1,188 @@
+use std::cmp::Ordering;
+
+use strum::{AsRefStr, EnumIter, EnumString};
+
+use crate::vfs::{Directory, File};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, AsRefStr, EnumIter, EnumString)]
+pub enum SortOrder {
+    // byte-wise comparison of the names
+    #[default]
+    Name,
+    // runs of digits are compared by their value, e.g., file2 before file10
+    Natural,
+    CaseInsensitive,
+    // by extension and then by name
+    Type,
+    // most recently modified first
+    Modified,
+}
+
+impl SortOrder {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortOrder::Name => "Name",
+            SortOrder::Natural => "Natural Name",
+            SortOrder::CaseInsensitive => "Name (Case-Insensitive)",
+            SortOrder::Type => "Type",
+            SortOrder::Modified => "Modified",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, AsRefStr, EnumIter, EnumString)]
+pub enum FolderPlacement {
+    #[default]
+    First,
+    Mixed,
+}
+
+impl FolderPlacement {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FolderPlacement::First => "Folders First",
+            FolderPlacement::Mixed => "Folders Mixed with Files",
+        }
+    }
+}
+
+pub struct SortKey {
+    name: String,
+    is_directory: bool,
+    modified: f64,
+}
+
+impl SortKey {
+    pub fn file(file: &File) -> SortKey {
+        SortKey {
+            name: file.name.get_cloned(),
+            is_directory: false,
+            modified: file.modified.get(),
+        }
+    }
+
+    // a directory counts as modified when any file inside of it was modified
+    pub fn directory(directory: &Directory, order: SortOrder) -> SortKey {
+        SortKey {
+            name: directory.name.get_cloned(),
+            is_directory: true,
+            modified: match order {
+                SortOrder::Modified => last_modified(directory),
+                _ => 0.0,
+            },
+        }
+    }
+}
+
+fn last_modified(directory: &Directory) -> f64 {
+    let files = directory.files.lock_ref()
+        .iter()
+        .map(|file| file.modified.get())
+        .fold(0.0, f64::max);
+    directory.directories.lock_ref()
+        .iter()
+        .map(|directory| last_modified(directory))
+        .fold(files, f64::max)
+}
+
+pub fn compare(
+    order: SortOrder,
+    placement: FolderPlacement,
+    left: &SortKey,
+    right: &SortKey
+) -> Ordering {
+    let placement = match placement {
+        FolderPlacement::First => right.is_directory.cmp(&left.is_directory),
+        FolderPlacement::Mixed => Ordering::Equal,
+    };
+    placement.then_with(|| match order {
+        SortOrder::Name => left.name.cmp(&right.name),
+        SortOrder::Natural => natural_cmp(&left.name, &right.name),
+        SortOrder::CaseInsensitive => left.name.to_lowercase().cmp(&right.name.to_lowercase())
+            .then_with(|| left.name.cmp(&right.name)),
+        SortOrder::Type => extension(&left.name).cmp(extension(&right.name))
+            .then_with(|| natural_cmp(&left.name, &right.name)),
+        SortOrder::Modified => right.modified.total_cmp(&left.modified)
+            .then_with(|| natural_cmp(&left.name, &right.name)),
+    })
+}
+
+fn extension(name: &str) -> &str {
+    match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => extension,
+        _ => "",
+    }
+}
+
+// compares runs of digits by their value and everything else by character
+fn natural_cmp(left: &str, right: &str) -> Ordering {
+    let mut left = left.chars().peekable();
+    let mut right = right.chars().peekable();
+    loop {
+        match (left.peek().copied(), right.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(l), Some(r)) if l.is_ascii_digit() && r.is_ascii_digit() => {
+                let left_digits = take_digits(&mut left);
+                let right_digits = take_digits(&mut right);
+                let left_value = left_digits.trim_start_matches('0');
+                let right_value = right_digits.trim_start_matches('0');
+                let ordering = left_value.len().cmp(&right_value.len())
+                    .then_with(|| left_value.cmp(right_value))
+                    // with equal values, fewer leading zeros come first
+                    .then_with(|| left_digits.len().cmp(&right_digits.len()));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(l), Some(r)) => {
+                let ordering = l.to_lowercase().cmp(r.to_lowercase())
+                    .then_with(|| l.cmp(&r));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                left.next();
+                right.next();
+            }
+        }
+    }
+}
+
+fn take_digits(characters: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(digit) = characters.next_if(char::is_ascii_digit) {
+        digits.push(digit);
+    }
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(names: &[&str]) -> Vec<String> {
+        let mut names = names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+        names.sort_by(|left, right| natural_cmp(left, right));
+        names
+    }
+
+    #[test]
+    fn numbers_by_value() {
+        assert_eq!(sorted(&["file10", "file2", "file1"]), ["file1", "file2", "file10"]);
+        assert_eq!(sorted(&["v1.10", "v1.9", "v1.09"]), ["v1.9", "v1.09", "v1.10"]);
+        assert_eq!(natural_cmp("file", "file1"), Ordering::Less);
+    }
+
+    #[test]
+    fn letters_ignore_case_first() {
+        assert_eq!(sorted(&["b", "A", "a", "B"]), ["A", "a", "B", "b"]);
+        assert_eq!(natural_cmp("node.py", "node.py"), Ordering::Equal);
+    }
+
+    #[test]
+    fn leading_zeros() {
+        assert_eq!(sorted(&["007", "7", "07"]), ["7", "07", "007"]);
+        assert_eq!(natural_cmp("0", "00"), Ordering::Less);
+    }
+}
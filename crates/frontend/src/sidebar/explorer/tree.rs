@@ -0,0 +1,274 @@
+use std::{ops::Range, pin::Pin, rc::Rc};
+
+use dominator::clone;
+use futures_signals::{map_ref, signal::{self, Broadcaster, Signal, SignalExt}, signal_vec::SignalVecExt};
+
+use crate::{contextmenu::Target, settings::SETTINGS, vfs::{Directory, File}};
+
+use super::{sort::{self, SortKey}, Explorer, RENAME};
+
+// a visible entry of the explorer once the expanded part of the project has been flattened
+#[derive(Clone)]
+pub struct Row {
+    pub node: Target,
+    // directories that only contain the directory of the row and are shown compactly in front of
+    // it, outermost first
+    pub chain: Vec<Rc<Directory>>,
+    // None for the root of the project
+    pub parent: Option<Rc<Directory>>,
+    // the path of the directory of the row, None for files
+    pub path: Option<Path>,
+    pub depth: usize,
+}
+
+impl Row {
+    // whether the rows show the same node in the same place, the names and the path are rendered
+    // from signals
+    pub fn is_same(&self, other: &Row) -> bool {
+        self.node.ptr_eq(&other.node)
+            && self.depth == other.depth
+            && self.chain.len() == other.chain.len()
+            && self.chain.iter().zip(&other.chain).all(|(left, right)| Rc::ptr_eq(left, right))
+            && match (&self.parent, &other.parent) {
+                (Some(left), Some(right)) => Rc::ptr_eq(left, right),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+}
+
+// the rows of a directory, the rows of its subdirectories are shared rather than copied, so that a
+// change deep down the project only builds the parts on the way up to the root
+#[derive(Clone, Default)]
+pub struct Rows(Rc<Parts>);
+
+#[derive(Default)]
+struct Parts {
+    parts: Vec<Part>,
+    // the number of rows up to the end of each part
+    ends: Vec<usize>,
+}
+
+enum Part {
+    Row(Row),
+    Rows(Rows),
+}
+
+impl Part {
+    fn len(&self) -> usize {
+        match self {
+            Part::Row(_) => 1,
+            Part::Rows(rows) => rows.len(),
+        }
+    }
+}
+
+impl Rows {
+    fn new(parts: Vec<Part>) -> Rows {
+        let ends = parts.iter()
+            .scan(0, |end, part| {
+                *end += part.len();
+                Some(*end)
+            })
+            .collect();
+        Rows(Rc::new(Parts { parts, ends }))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.ends.last().copied().unwrap_or(0)
+    }
+
+    // copies the rows in the range, which only visits the parts that overlap it
+    pub fn slice(&self, range: Range<usize>) -> Vec<Row> {
+        let mut rows = Vec::with_capacity(range.len());
+        self.collect(range, &mut rows);
+        rows
+    }
+
+    fn collect(&self, range: Range<usize>, rows: &mut Vec<Row>) {
+        let first = self.0.ends.partition_point(|end| *end <= range.start);
+        for (index, part) in self.0.parts.iter().enumerate().skip(first) {
+            let start = self.0.ends[index] - part.len();
+            if start >= range.end {
+                break;
+            }
+            match part {
+                Part::Row(row) => rows.push(row.clone()),
+                Part::Rows(part) => {
+                    part.collect(range.start.saturating_sub(start)..range.end - start, rows);
+                }
+            }
+        }
+    }
+
+    // the index of the first row that satisfies the predicate
+    pub fn position(&self, predicate: &impl Fn(&Row) -> bool) -> Option<usize> {
+        self.0.parts.iter().enumerate().find_map(|(index, part)| {
+            let start = self.0.ends[index] - part.len();
+            match part {
+                Part::Row(row) => predicate(row).then_some(start),
+                Part::Rows(rows) => rows.position(predicate).map(|position| start + position),
+            }
+        })
+    }
+}
+
+type RowsSignal = Pin<Box<dyn Signal<Item = Rows>>>;
+
+// the path of a directory like `Target::path`, which follows renames of the directory and of its
+// ancestors
+pub type Path = Rc<Broadcaster<Pin<Box<dyn Signal<Item = String>>>>>;
+
+pub fn root_path(root: &Rc<Directory>) -> Path {
+    let path: Pin<Box<dyn Signal<Item = String>>> = Box::pin(root.name.signal_ref(|name| format!("/{name}")));
+    Rc::new(path.broadcast())
+}
+
+fn child_path(parent: &Path, child: &Rc<Directory>) -> Path {
+    let path: Pin<Box<dyn Signal<Item = String>>> = Box::pin(map_ref! {
+        let parent = parent.signal_cloned(),
+        let name = child.name.signal_cloned() => format!("{parent}/{name}")
+    });
+    Rc::new(path.broadcast())
+}
+
+// a child of a directory together with the key it is sorted by
+enum Entry<'a> {
+    Directory(SortKey, &'a Rows),
+    File(SortKey, &'a Rc<File>),
+}
+
+impl Entry<'_> {
+    fn key(&self) -> &SortKey {
+        match self {
+            Entry::Directory(key, _) | Entry::File(key, _) => key,
+        }
+    }
+}
+
+// the row of the directory followed by the rows of its contents if it is expanded
+pub fn directory_rows(
+    explorer: &Rc<Explorer>,
+    directory: &Rc<Directory>,
+    parent: Option<Rc<Directory>>,
+    path: Path,
+    depth: usize
+) -> RowsSignal {
+    chain_rows(explorer, Vec::new(), directory, parent, path, depth)
+}
+
+// a directory whose only content is another directory is merged into the row of that directory,
+// the root of the project is never merged
+fn chain_rows(
+    explorer: &Rc<Explorer>,
+    chain: Vec<Rc<Directory>>,
+    directory: &Rc<Directory>,
+    parent: Option<Rc<Directory>>,
+    path: Path,
+    depth: usize
+) -> RowsSignal {
+    let compact = SETTINGS.with(|settings| settings.explorer_compact_folders.signal());
+    let only_child = map_ref! {
+        let compact = compact,
+        let directories = directory.directories.signal_vec_cloned().to_signal_cloned(),
+        let has_files = directory.files.signal_vec_cloned().is_empty().map(|empty| !empty) => {
+            match (*compact, directories.as_slice(), *has_files) {
+                (true, [child], false) => Some(child.clone()),
+                _ => None,
+            }
+        }
+    };
+    let is_root = parent.is_none();
+    Box::pin(only_child.switch(clone!(explorer, directory => move |only_child| -> RowsSignal {
+        match only_child {
+            Some(child) if !is_root => {
+                let mut chain = chain.clone();
+                chain.push(directory.clone());
+                chain_rows(&explorer, chain, &child, parent.clone(), child_path(&path, &child), depth)
+            }
+            _ => row_and_contents(&explorer, chain.clone(), &directory, parent.clone(), path.clone(), depth),
+        }
+    })))
+}
+
+fn row_and_contents(
+    explorer: &Rc<Explorer>,
+    chain: Vec<Rc<Directory>>,
+    directory: &Rc<Directory>,
+    parent: Option<Rc<Directory>>,
+    path: Path,
+    depth: usize
+) -> RowsSignal {
+    let contents = explorer.expanded_signal(&path)
+        .switch(clone!(explorer, directory, path => move |expanded| -> RowsSignal {
+            match expanded {
+                true => contents_rows(&explorer, &directory, &path, depth + 1),
+                false => Box::pin(signal::always(Rows::default())),
+            }
+        }));
+    let row = Row {
+        node: Target::Directory(directory.clone()),
+        chain,
+        parent,
+        path: Some(path),
+        depth
+    };
+    Box::pin(contents.map(move |contents| Rows::new(vec![Part::Row(row.clone()), Part::Rows(contents)])))
+}
+
+fn contents_rows(
+    explorer: &Rc<Explorer>,
+    directory: &Rc<Directory>,
+    path: &Path,
+    depth: usize
+) -> RowsSignal {
+    let directories = directory.directories
+        .signal_vec_cloned()
+        .map_signal(clone!(explorer, directory, path => move |child| {
+            directory_rows(&explorer, &child, Some(directory.clone()), child_path(&path, &child), depth)
+                .map(move |rows| (child.clone(), rows))
+        }))
+        .to_signal_cloned();
+    let files = directory.files
+        .signal_vec_cloned()
+        .to_signal_cloned();
+    // names are not tracked while typing, the contents are sorted again once a rename completes,
+    // likewise modification times are only read when the structure changes so that rows do not
+    // move around while a file is being edited
+    let renaming = RENAME.with(|rename| rename.signal_ref(Option::is_some));
+
+    let directory = directory.clone();
+    Box::pin(map_ref! {
+        let directories = directories,
+        let files = files,
+        let matches = explorer.matches.signal_cloned(),
+        let order = explorer.sort_order.signal(),
+        let placement = explorer.folder_placement.signal(),
+        let _renaming = renaming => {
+            let mut entries = directories.iter()
+                // while filtering, only directories that contain matches are shown
+                .filter(|(child, _)| matches.as_ref()
+                    .is_none_or(|matches| matches.contains_directory(child)))
+                .map(|(child, rows)| Entry::Directory(SortKey::directory(child, *order), rows))
+                .chain(files.iter()
+                    .filter(|file| matches.as_ref()
+                        .is_none_or(|matches| matches.file(file).is_some()))
+                    .map(|file| Entry::File(SortKey::file(file), file)))
+                .collect::<Vec<_>>();
+            entries.sort_by(|left, right| sort::compare(*order, *placement, left.key(), right.key()));
+
+            Rows::new(entries.into_iter()
+                .map(|entry| match entry {
+                    Entry::Directory(_, child_rows) => Part::Rows(child_rows.clone()),
+                    Entry::File(_, file) => Part::Row(Row {
+                        node: Target::File(file.clone()),
+                        chain: Vec::new(),
+                        parent: Some(directory.clone()),
+                        path: None,
+                        depth
+                    }),
+                })
+                .collect())
+        }
+    })
+}
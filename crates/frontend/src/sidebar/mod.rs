@@ -2,9 +2,11 @@ use std::rc::Rc;
 
 use dominator::{clone, events, Dom, EventOptions, html};
 use crate::styles;
+use futures::StreamExt;
 use futures_signals::{map_ref, signal::{self, Mutable, Signal, SignalExt}};
 
 pub mod explorer;
+pub mod outline;
 pub mod search;
 
 const DEFAULT_PANEL_SIZE: u32 = 200;
@@ -14,7 +16,8 @@ const RESIZER_PX: u32 = 3;
 enum Panel {
     // Not sure if this Rc is necessary?
     Explorer(Rc<explorer::Explorer>),
-    Search(search::Search)
+    Search(search::Search),
+    Outline(Rc<outline::Outline>),
 }
 
 impl Panel {
@@ -22,6 +25,7 @@ impl Panel {
         match self {
             Panel::Explorer(explorer) => explorer.tooltip(),
             Panel::Search(search) => search.tooltip(),
+            Panel::Outline(outline) => outline.tooltip(),
         }
     }
     
@@ -29,6 +33,7 @@ impl Panel {
         match self {
             Panel::Explorer(explorer) => explorer.icon(active),
             Panel::Search(search) => search.icon(active),
+            Panel::Outline(outline) => outline.icon(active),
         }
     }
 
@@ -36,11 +41,14 @@ impl Panel {
         match self {
             Panel::Explorer(explorer) => explorer::Explorer::render(explorer, workspace_command_tx),
             Panel::Search(search) => search.render(),
+            Panel::Outline(outline) => outline::Outline::render(outline, workspace_command_tx),
         }
     }
 }
 
 pub struct Sidebar {
+    explorer: Rc<explorer::Explorer>,
+    outline: Rc<outline::Outline>,
     panels: Vec<Rc<Panel>>,
     active_panel: Mutable<Option<Rc<Panel>>>,
     panel_size: Mutable<u32>,
@@ -51,15 +59,20 @@ pub struct Sidebar {
 impl Default for Sidebar {
     fn default() -> Self {
         // hack
-        let explorer = Rc::new(Panel::Explorer(explorer::Explorer::default().into()));
+        let explorer: Rc<explorer::Explorer> = Default::default();
+        let explorer_panel = Rc::new(Panel::Explorer(explorer.clone()));
+        let outline: Rc<outline::Outline> = Default::default();
 
         Self {
+            explorer,
+            outline: outline.clone(),
             panels: vec![
-                explorer.clone(),
-                Rc::new(Panel::Search(search::Search::default()))
+                explorer_panel.clone(),
+                Rc::new(Panel::Search(search::Search::default())),
+                Rc::new(Panel::Outline(outline)),
             ],
             // hack
-            active_panel: Mutable::new(Some(explorer)),
+            active_panel: Mutable::new(Some(explorer_panel)),
             panel_size: Mutable::new(DEFAULT_PANEL_SIZE),
             resize_active: Mutable::new(false),
             resizer_hover: Mutable::new(false),
@@ -80,11 +93,31 @@ impl Sidebar {
         }
     }
 
-    pub fn render(this: &Rc<Sidebar>, workspace_command_tx: &crate::WorkspaceCommandSender) -> Dom {
+    pub fn render(
+        this: &Rc<Sidebar>,
+        workspace_command_tx: &crate::WorkspaceCommandSender,
+        sidebar_command_rx: crate::SidebarCommandReceiver
+    ) -> Dom {
         html!("div", {
             .apply(styles::default_layout)
             .class("grid-cols-[auto_auto_auto]")
 
+            .future(sidebar_command_rx.for_each(clone!(this => move |command| clone!(this => async move {
+                match command {
+                    crate::SidebarCommand::ActiveActivityChanged(activity) => {
+                        this.outline.set_active_activity(activity.clone());
+                        this.explorer.set_active_activity(activity);
+                    },
+                    crate::SidebarCommand::RevealFile(file) => {
+                        this.show_explorer();
+                        this.explorer.reveal(&file);
+                    },
+                    crate::SidebarCommand::OpenActivitiesChanged(activities) => {
+                        this.explorer.set_open_activities(activities);
+                    },
+                }
+            }))))
+
             // menu
             .child(Self::render_menu(this))
             
@@ -149,6 +182,16 @@ impl Sidebar {
         })
     }
 
+    fn show_explorer(&self) {
+        let explorer_panel = self.panels.iter()
+            .find(|panel| matches!(***panel, Panel::Explorer(_)))
+            .cloned();
+        let mut active_panel = self.active_panel.lock_mut();
+        if !matches!(active_panel.as_deref(), Some(Panel::Explorer(_))) {
+            *active_panel = explorer_panel;
+        }
+    }
+
     fn render_menu(this: &Rc<Sidebar>) -> Dom {
         let buttons = this.panels.iter()
             .map(clone!(this => move |panel| {
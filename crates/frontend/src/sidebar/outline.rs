@@ -0,0 +1,144 @@
+use std::rc::Rc;
+
+use dominator::{clone, events, html, svg, Dom};
+use futures_signals::{signal::{Mutable, Signal, SignalExt}, signal_vec::SignalVecExt};
+
+use crate::{styles, symbols::{Symbol, SymbolKind}, workspace::activity_panel::{editor::Editor, Activity}};
+
+const ICON_SVG_PATH: &str =
+    "M3,4H7V8H3V4M9,5V7H21V5H9M3,10H7V14H3V10M9,11V13H21V11H9M3,16H7V20H3V16M9,17V19H21V17H9Z";
+
+fn kind_icon(kind: SymbolKind) -> Dom {
+    let (text, color) = match kind {
+        SymbolKind::Class => ("C", "#e37933"),
+        SymbolKind::Method => ("m", "#652d90"),
+        SymbolKind::Function => ("ƒ", "#652d90"),
+        SymbolKind::Element => ("<>", "#007acc"),
+    };
+    html!("span", {
+        .class("font-mono")
+        .class("text-xs")
+        .style("color", color)
+        .attr("title", kind.label())
+        .text(text)
+    })
+}
+
+// the symbols in the order they appear in the file together with their depth
+fn flatten(symbols: &[Symbol], depth: usize, rows: &mut Vec<(Symbol, usize)>) {
+    for symbol in symbols {
+        rows.push((symbol.clone(), depth));
+        flatten(&symbol.children, depth + 1, rows);
+    }
+}
+
+#[derive(Default)]
+pub struct Outline {
+    // the editor of the active group
+    active_editor: Mutable<Option<Rc<Editor>>>,
+}
+
+impl Outline {
+    pub fn set_active_activity(&self, activity: Option<Rc<Activity>>) {
+        self.active_editor.set(activity.and_then(|activity| match activity.as_ref() {
+            Activity::Editor(editor) => Some(editor.clone()),
+            Activity::Welcome(_) => None,
+        }));
+    }
+
+    pub fn tooltip(&self) -> &'static str {
+        "Outline"
+    }
+
+    pub fn icon(&self, active: impl Signal<Item = bool> + 'static) -> Dom {
+        svg!("svg", {
+            .apply(|dom| styles::menu::button_toggle(dom, active))
+            .attr("viewBox", "0 0 24 24")
+            .child(svg!("path", {
+                .attr("d", ICON_SVG_PATH)
+            }))
+        })
+    }
+
+    fn render_symbol(
+        editor: &Rc<Editor>,
+        symbol: &Symbol,
+        depth: usize,
+        workspace_command_tx: &crate::WorkspaceCommandSender
+    ) -> Dom {
+        html!("div", {
+            .apply(|dom| styles::vfs_item::row(dom, depth))
+            .apply(styles::vfs_item::body)
+            .attr("title", &format!("{} {}", symbol.name, symbol.detail))
+            .child(html!("div", {
+                .apply(styles::vfs_item::icon)
+                .child(kind_icon(symbol.kind))
+            }))
+            .child(html!("div", {
+                .class("truncate")
+                .child(html!("span", {
+                    .text(&symbol.name)
+                }))
+                .child(html!("span", {
+                    .class("ml-2")
+                    .class("text-xs")
+                    .class("opacity-60")
+                    .text(&symbol.detail)
+                }))
+            }))
+            .event(clone!(editor, workspace_command_tx, symbol => move |_: events::MouseDown| {
+                workspace_command_tx
+                    .unbounded_send(crate::WorkspaceCommand::OpenFileAt(editor.file.clone(), symbol.selection))
+                    .unwrap();
+            }))
+        })
+    }
+
+    fn render_symbols(editor: &Rc<Editor>, workspace_command_tx: &crate::WorkspaceCommandSender) -> Dom {
+        // the symbols of the buffer, so the outline follows the editor as the user types
+        let rows = editor.symbols()
+            .map(|symbols| {
+                let mut rows = Vec::new();
+                flatten(&symbols, 0, &mut rows);
+                rows
+            })
+            .broadcast();
+        html!("div", {
+            .apply(styles::vfs_item::viewport)
+            .children_signal_vec(rows.signal_cloned()
+                .to_signal_vec()
+                .map(clone!(editor, workspace_command_tx => move |(symbol, depth)| {
+                    Self::render_symbol(&editor, &symbol, depth, &workspace_command_tx)
+                })))
+            .child_signal(rows.signal_ref(|rows| rows.is_empty().then(Self::render_empty)))
+        })
+    }
+
+    fn render_empty() -> Dom {
+        html!("div", {
+            .class("px-5")
+            .class("text-[0.9rem]")
+            .class("opacity-60")
+            .text("The active editor cannot provide outline information.")
+        })
+    }
+
+    pub fn render(this: &Rc<Outline>, workspace_command_tx: &crate::WorkspaceCommandSender) -> Dom {
+        html!("div", {
+            .apply(styles::panel::body)
+            .child(html!("div", {
+                .apply(styles::panel::title)
+                .child(html!("span", {
+                    .apply(styles::panel::title_text)
+                    .text("Outline")
+                }))
+            }))
+            .child_signal(this.active_editor.signal_cloned().map(clone!(workspace_command_tx => move |editor| {
+                Some(match editor {
+                    Some(editor) => Self::render_symbols(&editor, &workspace_command_tx),
+                    None => Self::render_empty(),
+                })
+            })))
+        })
+    }
+}
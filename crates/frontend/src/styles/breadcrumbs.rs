@@ -0,0 +1,31 @@
+use dominator::DomBuilder;
+use web_sys::HtmlElement;
+
+pub fn bar(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("flex")
+        .class("items-center")
+        .class("h-[22px]")
+        .class("pl-3")
+        .class("text-[0.8rem]")
+        .class("overflow-hidden")
+        .class("whitespace-nowrap")
+        .style("color", super::TITLE_COLOR)
+        .style("background-color", super::FOREGROUND_COLOR)
+}
+
+pub fn segment(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("inline-flex")
+        .class("items-center")
+        .class("gap-1")
+        .class("cursor-pointer")
+        .class("hover:text-black")
+}
+
+pub fn separator(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("px-1")
+}
+
+pub fn dropdown(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("max-h-80")
+        .class("overflow-y-auto")
+}
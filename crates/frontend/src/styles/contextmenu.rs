@@ -1,4 +1,5 @@
 use dominator::DomBuilder;
+use futures_signals::signal::{Signal, SignalExt};
 use web_sys::HtmlElement;
 
 pub fn body(
@@ -28,3 +29,45 @@ pub fn option(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
         .class("text-[0.9rem]")
         .class("cursor-pointer")
 }
+
+// highlighted while the pointer is over it or it is selected with the keyboard
+pub fn item(
+    dom: DomBuilder<HtmlElement>,
+    active: impl Signal<Item = bool> + 'static,
+    enabled: bool
+) -> DomBuilder<HtmlElement> {
+    let active = active.broadcast();
+    dom.class("relative")
+        .class("pl-5")
+        .class("pt-0.5")
+        .class("rounded")
+        .class("text-[0.9rem]")
+        .class(if enabled { "cursor-pointer" } else { "cursor-default" })
+        .apply_if(!enabled, |dom| dom.class("opacity-50"))
+        .class_signal("bg-coreblue", active.signal())
+        .class_signal("text-white", active.signal())
+}
+
+// opens to the right of the option that contains it
+pub fn submenu(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("absolute")
+        .class("left-full")
+        .class("top-0")
+        .class("w-60")
+        .class("p-1")
+        .class("rounded")
+        .class("shadow-md")
+        .style("color", super::TEXT_COLOR)
+        .style("background-color", super::FOREGROUND_COLOR)
+}
+
+pub fn separator(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("my-1")
+        .class("border-t")
+        .class("border-gray-200")
+}
+
+pub fn keybinding(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("pr-2")
+        .class("opacity-60")
+}
@@ -0,0 +1,46 @@
+use dominator::DomBuilder;
+use web_sys::HtmlElement;
+
+// dims whatever is behind the dialog
+pub fn overlay(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("absolute")
+        .class("inset-0")
+        .class("z-[1000]")
+        .class("flex")
+        .class("items-start")
+        .class("justify-center")
+        .class("pt-16")
+        .style("background-color", "rgba(0, 0, 0, 0.2)")
+}
+
+pub fn body(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("flex")
+        .class("flex-col")
+        .class("gap-2")
+        .class("w-96")
+        .class("p-4")
+        .class("rounded")
+        .class("shadow-md")
+        .class("outline-none")
+        .style("color", super::TEXT_COLOR)
+        .style("background-color", super::FOREGROUND_COLOR)
+}
+
+pub fn buttons(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("flex")
+        .class("justify-end")
+        .class("gap-2")
+        .class("pt-2")
+}
+
+pub fn button(dom: DomBuilder<HtmlElement>, primary: bool) -> DomBuilder<HtmlElement> {
+    dom.class("px-3")
+        .class("py-1")
+        .class("rounded")
+        .class("cursor-pointer")
+        .apply_if(primary, |dom| dom
+            .class("text-white")
+            .style("background-color", super::FEATURE_COLOR))
+        .apply_if(!primary, |dom| dom
+            .style("background-color", super::BACKGROUND_COLOR))
+}
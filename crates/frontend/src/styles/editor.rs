@@ -0,0 +1,67 @@
+use dominator::DomBuilder;
+use web_sys::HtmlElement;
+
+// covers the top of the editor until the conflict is resolved
+pub fn conflict(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("absolute")
+        .class("inset-x-0")
+        .class("top-0")
+        .class("z-10")
+        .class("flex")
+        .class("items-center")
+        .class("gap-2")
+        .class("px-3")
+        .class("py-1.5")
+        .class("text-[0.85rem]")
+        .class("shadow-md")
+        .style("color", super::TEXT_COLOR)
+        .style("background-color", "#fff4ce")
+}
+
+pub fn conflict_button(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("px-2")
+        .class("py-0.5")
+        .class("rounded")
+        .class("text-white")
+        .class("cursor-pointer")
+        .style("background-color", super::FEATURE_COLOR)
+}
+
+// shown instead of the text of images, binaries and files that are not UTF-8
+pub fn not_text(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("flex")
+        .class("h-full")
+        .class("items-center")
+        .class("justify-center")
+        .class("px-4")
+        .class("text-[0.85rem]")
+        .class("text-center")
+        .style("color", super::TEXT_COLOR)
+}
+
+// the documentation in the hover and signature help tooltips
+pub fn tooltip(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("max-w-[36rem]")
+        .class("px-2")
+        .class("py-1")
+        .class("text-[0.85rem]")
+        .style("color", super::TEXT_COLOR)
+}
+
+pub fn tooltip_signature(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("font-mono")
+        .class("whitespace-pre-wrap")
+}
+
+// the parameter of the argument at the cursor
+pub fn tooltip_active_parameter(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("font-bold")
+        .style("color", super::FEATURE_COLOR)
+}
+
+pub fn tooltip_description(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("mt-1")
+        .class("pt-1")
+        .class("border-t")
+        .class("whitespace-pre-line")
+}
@@ -4,9 +4,14 @@ use web_sys::{HtmlElement, HtmlInputElement, SvgElement};
 
 pub mod menu;
 pub mod tab;
+pub mod breadcrumbs;
+pub mod editor;
 pub mod panel;
 pub mod console;
 pub mod contextmenu;
+pub mod dialog;
+pub mod status_bar;
+pub mod switcher;
 pub mod vfs_item;
 
 const BACKGROUND_COLOR: &str = "#f3f3f3"; // lightgray
@@ -14,6 +19,7 @@ const FEATURE_COLOR: &str = "#007acc"; // blue
 const FOREGROUND_COLOR: &str = "#ffffff"; // TOGGLE_ACTIVE_COLOR -- white
 const TITLE_COLOR: &str = "#828282"; // TOGGLE_INACTIVE_COLOR -- darkgray
 const TEXT_COLOR: &str = "#2c2c2c"; // mineshaft
+pub const RULER_COLOR: &str = "#d4d4d4"; // the vertical lines in the editor
 
 pub fn default_layout(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
     dom.class("grid")
@@ -1,4 +1,5 @@
 use dominator::DomBuilder;
+use futures_signals::signal::{Signal, SignalExt};
 use web_sys::HtmlElement;
 
 pub fn body(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
@@ -20,4 +21,29 @@ pub fn title_text(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
         .class("tracking-tight")
         .class("uppercase")
         .style("color", super::TITLE_COLOR)
+}
+
+pub fn filter(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("inline-flex")
+        .class("items-center")
+        .class("gap-1")
+        .class("ml-3")
+        .class("text-xs")
+}
+
+pub fn toggle(
+    dom: DomBuilder<HtmlElement>,
+    active: impl Signal<Item = bool> + 'static
+) -> DomBuilder<HtmlElement> {
+    dom.class("px-1")
+        .class("rounded")
+        .class("cursor-pointer")
+        .class("font-mono")
+        .style_signal("color", active.map(|active| {
+            if active {
+                super::FEATURE_COLOR
+            } else {
+                super::TITLE_COLOR
+            }
+        }))
 }
\ No newline at end of file
@@ -0,0 +1,22 @@
+use dominator::DomBuilder;
+use web_sys::HtmlElement;
+
+pub fn bar(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("flex")
+        .class("justify-end")
+        .class("items-center")
+        .class("h-[22px]")
+        .class("pr-2")
+        .class("text-[0.8rem]")
+        .class("text-white")
+        .class("whitespace-nowrap")
+        .style("background-color", super::FEATURE_COLOR)
+}
+
+pub fn item(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("px-2")
+        .class("h-full")
+        .class("leading-[22px]")
+        .class("cursor-pointer")
+        .class("hover:bg-white/20")
+}
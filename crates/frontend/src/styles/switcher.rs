@@ -0,0 +1,26 @@
+use dominator::DomBuilder;
+use web_sys::HtmlElement;
+
+// floats over the top of the editor area
+pub fn body(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("absolute")
+        .class("z-[1000]")
+        .class("top-2")
+        .class("left-1/2")
+        .class("-translate-x-1/2")
+        .class("w-96")
+        .class("max-h-96")
+        .class("overflow-y-auto")
+        .class("p-1")
+        .class("rounded")
+        .class("shadow-md")
+        .style("color", super::TEXT_COLOR)
+        .style("background-color", super::FOREGROUND_COLOR)
+}
+
+pub fn title(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("px-2")
+        .class("pb-1")
+        .class("text-[0.8rem]")
+        .style("color", super::TITLE_COLOR)
+}
@@ -3,19 +3,32 @@ use futures_signals::signal::{self, Signal, SignalExt};
 use web_sys::HtmlElement;
 
 pub fn bar(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
-    dom.class("inline-flex")
+    dom.class("flex")
+        .class("items-center")
         .class("h-[35px]")
         .class("gap-0")
         .class("bg-lightgray")
         .style("background-color", super::BACKGROUND_COLOR)
 }
 
+// scrolls sideways when the tabs do not fit, without showing a scrollbar
+pub fn tabs(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("flex")
+        .class("flex-1")
+        .class("min-w-0")
+        .class("h-full")
+        .class("overflow-x-auto")
+        .class("overflow-y-hidden")
+        .style("scrollbar-width", "none")
+}
+
 pub fn body(dom: DomBuilder<HtmlElement>,
     is_active: impl Signal<Item = bool> + 'static,
     mouse_over: impl Signal<Item = bool> + 'static
 ) -> DomBuilder<HtmlElement> {
     dom.class("block")
         .class("h-full")
+        .class("whitespace-nowrap")
         .class("pt-1.5")
         .class("pl-1")
         .class("pr-2")
@@ -33,10 +46,10 @@ pub fn body(dom: DomBuilder<HtmlElement>,
 pub fn icon(
     dom: DomBuilder<HtmlElement>,
     mouse_over_close: impl Signal<Item = bool> + 'static,
-    mouse_over: impl Signal<Item = bool> + 'static
+    visible: impl Signal<Item = bool> + 'static
 ) -> DomBuilder<HtmlElement> {
     dom.apply(super::icon)
-        .class_signal("invisible", signal::not(mouse_over))
+        .class_signal("invisible", signal::not(visible))
         .style_signal("background-color", mouse_over_close.map(|flag| {
             if flag {
                 super::BACKGROUND_COLOR
@@ -1,9 +1,19 @@
 use dominator::DomBuilder;
+use futures_signals::signal::{Signal, SignalExt};
 use web_sys::HtmlElement;
 
-pub fn list(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
-    dom.class("pl-5")
-        .class("pt-0")
+pub const ROW_HEIGHT_PX: u32 = 24;
+const INDENT_PX: usize = 20;
+
+pub fn viewport(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("block")
+        .class("overflow-y-auto")
+        .class("outline-none")
+}
+
+pub fn row(dom: DomBuilder<HtmlElement>, depth: usize) -> DomBuilder<HtmlElement> {
+    dom.style("height", &format!("{ROW_HEIGHT_PX}px"))
+        .style("padding-left", &format!("{}px", depth * INDENT_PX + 2))
 }
 
 pub fn body(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
@@ -14,7 +24,25 @@ pub fn body(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
         .class("cursor-pointer")
 }
 
+pub fn selected(
+    dom: DomBuilder<HtmlElement>,
+    selected: impl Signal<Item = bool> + 'static
+) -> DomBuilder<HtmlElement> {
+    dom.style_signal("background-color", selected.map(|selected| {
+        if selected {
+            super::FOREGROUND_COLOR
+        } else {
+            "transparent"
+        }
+    }))
+}
+
 pub fn icon(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
     dom.apply(super::icon)
         .class("mr-0")
+}
+
+pub fn highlight(dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+    dom.class("font-bold")
+        .style("color", super::FEATURE_COLOR)
 }
\ No newline at end of file
@@ -0,0 +1,131 @@
+// the symbols of Python files come from the parser of the python crate in the worker and those of
+// launch files from the parser in `launch`, so that the outline agrees with the diagnostics
+use std::ops::Range;
+
+use common::offsets;
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::launch::xml;
+
+// offsets are in UTF-16 code units so that they can be used as CodeMirror positions
+#[derive(Clone, Debug, PartialEq)]
+pub struct Symbol {
+    pub name: String,
+    pub detail: String,
+    pub kind: SymbolKind,
+    // the range of the whole definition
+    pub from: usize,
+    pub to: usize,
+    // where the cursor is placed when navigating to the symbol
+    pub selection: usize,
+    pub children: Vec<Symbol>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymbolKind {
+    Class,
+    Method,
+    Function,
+    Element,
+}
+
+impl SymbolKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SymbolKind::Class => "class",
+            SymbolKind::Method => "method",
+            SymbolKind::Function => "function",
+            SymbolKind::Element => "element",
+        }
+    }
+}
+
+// the elements of a launch file that are listed, other elements only contribute their children
+const ELEMENTS: [&str; 3] = ["node", "world", "model"];
+
+// the symbols of a launch file, which are found straight away
+pub fn launch_symbols(text: &str) -> Vec<Symbol> {
+    let mut symbols = element_symbols(&xml::parse(text).elements);
+    let mut ranges = Vec::new();
+    byte_ranges(&symbols, &mut ranges);
+    to_utf16(&mut symbols, &mut offsets::utf16_ranges(text, &ranges).into_iter());
+    symbols
+}
+
+// the symbols that the Python worker answers with, which are in UTF-16 code units already
+pub fn from_js(symbols: &JsValue) -> Vec<Symbol> {
+    if symbols.is_null() {
+        return Vec::new();
+    }
+    let get = |symbol: &JsValue, key: &str| js_sys::Reflect::get(symbol, &key.into()).unwrap();
+    symbols.unchecked_ref::<js_sys::Array>().iter().map(|symbol| Symbol {
+        name: get(&symbol, "name").as_string().unwrap_or_default(),
+        detail: String::new(),
+        kind: match get(&symbol, "kind").as_string().as_deref() {
+            Some("class") => SymbolKind::Class,
+            Some("method") => SymbolKind::Method,
+            _ => SymbolKind::Function,
+        },
+        from: get(&symbol, "from").as_f64().unwrap_or_default() as usize,
+        to: get(&symbol, "to").as_f64().unwrap_or_default() as usize,
+        selection: get(&symbol, "selection").as_f64().unwrap_or_default() as usize,
+        children: from_js(&get(&symbol, "children")),
+    }).collect()
+}
+
+// the offsets of the symbols are in bytes until they are converted all at once, each symbol adds
+// its range and its selection as an empty range
+fn byte_ranges(symbols: &[Symbol], ranges: &mut Vec<Range<usize>>) {
+    for symbol in symbols {
+        ranges.push(symbol.from..symbol.to);
+        ranges.push(symbol.selection..symbol.selection);
+        byte_ranges(&symbol.children, ranges);
+    }
+}
+
+fn to_utf16(symbols: &mut [Symbol], ranges: &mut impl Iterator<Item = Range<usize>>) {
+    for symbol in symbols {
+        let range = ranges.next().unwrap();
+        (symbol.from, symbol.to) = (range.start, range.end);
+        symbol.selection = ranges.next().unwrap().start;
+        to_utf16(&mut symbol.children, ranges);
+    }
+}
+
+// the offsets are in bytes
+fn element_symbols(elements: &[xml::Element]) -> Vec<Symbol> {
+    let mut symbols = Vec::new();
+    for element in elements {
+        let children = element_symbols(&element.children);
+        if !ELEMENTS.contains(&element.name) {
+            symbols.extend(children);
+            continue;
+        }
+        symbols.push(Symbol {
+            name: element.name.to_owned(),
+            detail: element.attributes.iter()
+                .map(|attribute| format!("{}=\"{}\"", attribute.name, attribute.value))
+                .collect::<Vec<_>>()
+                .join(" "),
+            kind: SymbolKind::Element,
+            from: element.range.start,
+            to: element.range.end,
+            selection: element.name_range.start,
+            children,
+        });
+    }
+    symbols
+}
+
+// the chain of symbols from the outermost to the innermost one that contains the position
+pub fn enclosing(symbols: &[Symbol], position: usize) -> Vec<&Symbol> {
+    let mut chain = Vec::new();
+    let mut level = symbols;
+    while let Some(symbol) = level.iter()
+        .find(|symbol| symbol.from <= position && position <= symbol.to)
+    {
+        chain.push(symbol);
+        level = &symbol.children;
+    }
+    chain
+}
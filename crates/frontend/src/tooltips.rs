@@ -0,0 +1,159 @@
+// the tooltips of the editor with the documentation of the name under the mouse and the signature
+// of the call around the cursor, the sources answer with the objects of the `python` crate, whose
+// positions are in UTF-16 code units
+use std::future::Future;
+
+use common::js::set;
+use dominator::{html, Dom};
+use wasm_bindgen::prelude::*;
+
+use crate::{codemirror, styles};
+
+fn get(object: &JsValue, key: &str) -> JsValue {
+    js_sys::Reflect::get(object, &key.into()).unwrap()
+}
+
+// a tooltip at the position, CodeMirror places the element and draws its frame
+fn tooltip(position: JsValue, content: Dom) -> js_sys::Object {
+    let element = web_sys::window().unwrap().document().unwrap().create_element("div").unwrap();
+    dominator::append_dom(&element, content);
+    let create = move |_view: JsValue| {
+        let tooltip_view = js_sys::Object::new();
+        set(&tooltip_view, "dom", element.clone().into());
+        tooltip_view
+    };
+    let tooltip = js_sys::Object::new();
+    set(&tooltip, "pos", position);
+    set(&tooltip, "above", true.into());
+    set(&tooltip, "create", Closure::<dyn Fn(JsValue) -> js_sys::Object>::new(create).into_js_value());
+    tooltip
+}
+
+fn documentation(signature: Dom, descriptions: &[String]) -> Dom {
+    html!("div", {
+        .apply(styles::editor::tooltip)
+        .child(html!("div", {
+            .apply(styles::editor::tooltip_signature)
+            .child(signature)
+        }))
+        .children(descriptions.iter().filter(|description| !description.is_empty()).map(|description| html!("div", {
+            .apply(styles::editor::tooltip_description)
+            .text(description)
+        })))
+    })
+}
+
+// the documentation that the source answers for the name under the mouse
+pub fn hover<S, F>(source: S) -> JsValue
+where
+    S: Fn(String, u32) -> F + 'static,
+    F: Future<Output = JsValue> + 'static,
+{
+    let hover_source = move |view: JsValue, position: u32| {
+        let text = view.unchecked_into::<codemirror::View>().state().doc().text();
+        let hover = source(text, position);
+        wasm_bindgen_futures::future_to_promise(async move {
+            let hover = hover.await;
+            if hover.is_null() {
+                return Ok(JsValue::NULL);
+            }
+            let signature = html!("span", { .text(&get(&hover, "signature").as_string().unwrap()) });
+            let description = get(&hover, "description").as_string().unwrap();
+            let tooltip = tooltip(get(&hover, "from"), documentation(signature, &[description]));
+            set(&tooltip, "end", get(&hover, "to"));
+            Ok(tooltip.into())
+        })
+    };
+    codemirror::hover_tooltip(&Closure::<dyn Fn(JsValue, u32) -> js_sys::Promise>::new(hover_source).into_js_value())
+}
+
+// the label of the signature with the active parameter highlighted and the descriptions of the
+// parameter and the function
+fn signature_help_tooltip(help: &JsValue) -> js_sys::Object {
+    let label = get(help, "label").as_string().unwrap();
+    let parameters = get(help, "parameters").unchecked_into::<js_sys::Array>();
+    let active = get(help, "active").as_f64().map(|active| parameters.get(active as u32));
+    // the range of the parameter is in bytes of the label
+    let range = active.as_ref().map(|parameter| get(parameter, "from").as_f64().unwrap() as usize..get(parameter, "to").as_f64().unwrap() as usize);
+    let signature = match range {
+        Some(range) => html!("span", {
+            .text(&label[..range.start])
+            .child(html!("span", {
+                .apply(styles::editor::tooltip_active_parameter)
+                .text(&label[range.clone()])
+            }))
+            .text(&label[range.end..])
+        }),
+        None => html!("span", { .text(&label) }),
+    };
+    let descriptions = [
+        active.map_or_else(String::new, |parameter| get(&parameter, "description").as_string().unwrap()),
+        get(help, "description").as_string().unwrap(),
+    ];
+    tooltip(get(help, "position"), documentation(signature, &descriptions))
+}
+
+// the signature of the call around the cursor, which the source answers after each edit and each
+// move of the cursor
+pub fn signature_help<S, F>(source: S) -> js_sys::Array
+where
+    S: Fn(String, u32) -> F + 'static,
+    F: Future<Output = JsValue> + 'static,
+{
+    let effect = codemirror::define_state_effect();
+    let update = {
+        let effect = effect.clone();
+        move |tooltip: JsValue, transaction: JsValue| {
+            let transaction = transaction.unchecked_into::<codemirror::Transaction>();
+            for transaction_effect in transaction.effects().iter() {
+                let transaction_effect = transaction_effect.unchecked_into::<codemirror::StateEffect>();
+                if transaction_effect.is(&effect) {
+                    return transaction_effect.value();
+                }
+            }
+            if tooltip.is_null() || !transaction.doc_changed() {
+                return tooltip;
+            }
+            // the tooltip stays at its call until the help for the edit arrives
+            let moved = js_sys::Object::assign(&js_sys::Object::new(), tooltip.unchecked_ref());
+            let position = transaction.changes().map_pos(get(&tooltip, "pos").as_f64().unwrap() as u32);
+            set(&moved, "pos", position.into());
+            moved.into()
+        }
+    };
+    let provide = |field: JsValue| codemirror::SHOW_TOOLTIP.from_field(&field);
+    let config = js_sys::Object::new();
+    set(&config, "create", Closure::<dyn Fn() -> JsValue>::new(|| JsValue::NULL).into_js_value());
+    set(&config, "update", Closure::<dyn Fn(JsValue, JsValue) -> JsValue>::new(update).into_js_value());
+    set(&config, "provide", Closure::<dyn Fn(JsValue) -> JsValue>::new(provide).into_js_value());
+    let field = codemirror::define_state_field(&config);
+
+    let listener = move |update: JsValue| {
+        let update = update.unchecked_into::<codemirror::Update>();
+        let view = update.view();
+        // editors of the same file in other groups receive the edits too
+        if !(update.doc_changed() || update.selection_set()) || !view.has_focus() {
+            return;
+        }
+        let state = update.state();
+        let help = source(state.doc().text(), codemirror::cursor(&state) as u32);
+        let effect = effect.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let help = help.await;
+            // the help for an older state is dropped, the request for the newer one follows
+            if !js_sys::Object::is(&view.state(), &state) {
+                return;
+            }
+            let tooltip = match help.is_null() {
+                true => JsValue::NULL,
+                false => signature_help_tooltip(&help).into(),
+            };
+            let transaction = js_sys::Object::new();
+            set(&transaction, "effects", effect.of(&tooltip));
+            view.dispatch(&transaction);
+        });
+    };
+    let listener = codemirror_sys::view::EditorView::update_listener()
+        .of(&Closure::<dyn Fn(JsValue)>::new(listener).into_js_value());
+    js_sys::Array::of2(&field, &listener)
+}
@@ -1,12 +1,42 @@
-use std::rc::Rc;
+use std::{pin::Pin, rc::Rc};
 
-use futures_signals::{signal::Mutable, signal_vec::MutableVec};
+use futures_signals::{map_ref, signal::{Mutable, Signal, SignalExt}, signal_vec::{MutableVec, SignalVecExt}};
+
+pub type ChangesSignal = Pin<Box<dyn Signal<Item = ()>>>;
 
 #[derive(Clone)]
 pub struct File {
     pub name: Mutable<String>,
     pub mode: Mutable<u32>,
-    pub data: Mutable<Vec<u8>>
+    pub data: Mutable<Vec<u8>>,
+    // milliseconds since the epoch
+    pub modified: Mutable<f64>
+}
+
+impl File {
+    pub fn new(name: &str, mode: u32, data: Vec<u8>) -> File {
+        File {
+            name: name.to_owned().into(),
+            mode: mode.into(),
+            data: data.into(),
+            modified: js_sys::Date::now().into()
+        }
+    }
+
+    pub fn write(&self, data: Vec<u8>) {
+        self.data.set(data);
+        self.modified.set(js_sys::Date::now());
+    }
+
+    // unlike clone, this does not share the underlying mutables
+    pub fn duplicate(&self) -> File {
+        File {
+            name: Mutable::new(self.name.get_cloned()),
+            mode: Mutable::new(self.mode.get()),
+            data: Mutable::new(self.data.get_cloned()),
+            modified: Mutable::new(js_sys::Date::now()),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -14,6 +44,81 @@ pub struct Directory {
     pub name: Mutable<String>,
     pub mode: Mutable<u32>,
     pub directories: MutableVec<Rc<Directory>>,
-    pub files: MutableVec<Rc<File>>
+    pub files: MutableVec<Rc<File>>,
+}
+
+impl Directory {
+    // unlike clone, this does not share the underlying mutables
+    pub fn duplicate(&self) -> Directory {
+        Directory {
+            name: Mutable::new(self.name.get_cloned()),
+            mode: Mutable::new(self.mode.get()),
+            directories: self.directories.lock_ref()
+                .iter()
+                .map(|directory| Rc::new(directory.duplicate()))
+                .collect::<Vec<_>>()
+                .into(),
+            files: self.files.lock_ref()
+                .iter()
+                .map(|file| Rc::new(file.duplicate()))
+                .collect::<Vec<_>>()
+                .into(),
+        }
+    }
+
+    // fires once straight away and again whenever a file or a directory below the directory is
+    // added, removed or renamed, the contents of files are not followed
+    pub fn changes(&self) -> ChangesSignal {
+        let files = self.files.signal_vec_cloned()
+            .map_signal(|file| file.name.signal_ref(|_| ()))
+            .to_signal_map(|_| ());
+        let directories = self.directories.signal_vec_cloned()
+            .map_signal(|directory| directory.changes())
+            .to_signal_map(|_| ());
+        Box::pin(map_ref! {
+            let _name = self.name.signal_ref(|_| ()),
+            let _files = files,
+            let _directories = directories => ()
+        })
+    }
+
+    pub fn contains_name(&self, name: &str) -> bool {
+        self.directories.lock_ref().iter().any(|directory| *directory.name.lock_ref() == name) ||
+            self.files.lock_ref().iter().any(|file| *file.name.lock_ref() == name)
+    }
+}
+
+// returns the chain of directories from the root to the directory that satisfies the predicate
+fn find_chain(
+    root: &Rc<Directory>,
+    predicate: &impl Fn(&Rc<Directory>) -> bool
+) -> Option<Vec<Rc<Directory>>> {
+    if predicate(root) {
+        return Some(vec![root.clone()]);
+    }
+    root.directories.lock_ref()
+        .iter()
+        .find_map(|directory| find_chain(directory, predicate))
+        .map(|mut chain| {
+            chain.insert(0, root.clone());
+            chain
+        })
 }
 
+// returns the directories from the root down to and including the parent of the file
+pub fn ancestors_of_file(root: &Rc<Directory>, file: &Rc<File>) -> Option<Vec<Rc<Directory>>> {
+    find_chain(root, &|directory| directory.files.lock_ref()
+        .iter()
+        .any(|candidate| Rc::ptr_eq(candidate, file)))
+}
+
+// returns the directories from the root down to and including the parent of the directory, the
+// chain is empty if the directory is the root itself
+pub fn ancestors_of_directory(root: &Rc<Directory>, directory: &Rc<Directory>) -> Option<Vec<Rc<Directory>>> {
+    if Rc::ptr_eq(root, directory) {
+        return Some(Vec::new());
+    }
+    find_chain(root, &|parent| parent.directories.lock_ref()
+        .iter()
+        .any(|candidate| Rc::ptr_eq(candidate, directory)))
+}
@@ -0,0 +1,175 @@
+// the web worker that checks, completes and outlines Python files with the parser of the `python`
+// crate, so that analyzing a large file does not block the editor
+use std::{cell::{Cell, RefCell}, collections::HashMap, rc::Rc};
+
+use common::js::set;
+use futures::channel::oneshot;
+use wasm_bindgen::prelude::*;
+
+use crate::vfs;
+
+// the path is relative to the snippet that wasm-bindgen writes into the artifacts, next to which
+// the build places the script of the worker
+#[wasm_bindgen(inline_js = "export function spawn() { return new Worker(new URL('../../python_worker.js', import.meta.url)); }")]
+extern "C" {
+    fn spawn() -> web_sys::Worker;
+}
+
+type Pending = Rc<RefCell<HashMap<u32, oneshot::Sender<JsValue>>>>;
+
+struct Worker {
+    worker: web_sys::Worker,
+    next_id: Cell<u32>,
+    // the requests that have not been answered yet
+    pending: Pending,
+    // set once the worker failed, e.g., its script could not be loaded, later requests are not sent
+    failed: Rc<Cell<bool>>,
+    _on_message: Closure<dyn Fn(web_sys::MessageEvent)>,
+    _on_error: Closure<dyn Fn(web_sys::Event)>,
+}
+
+impl Worker {
+    fn new() -> Worker {
+        let worker = spawn();
+        let pending = Pending::default();
+        let on_message = Closure::<dyn Fn(web_sys::MessageEvent)>::new({
+            let pending = pending.clone();
+            move |event: web_sys::MessageEvent| {
+                let response = event.data();
+                let Some(id) = js_sys::Reflect::get(&response, &"id".into()).ok().and_then(|id| id.as_f64()) else {
+                    return;
+                };
+                let result = js_sys::Reflect::get(&response, &"result".into()).unwrap_or(JsValue::NULL);
+                if let Some(sender) = pending.borrow_mut().remove(&(id as u32)) {
+                    let _ = sender.send(result);
+                }
+            }
+        });
+        worker.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        let failed = Rc::new(Cell::new(false));
+        // dropping the senders answers the waiting requests with null
+        let on_error = Closure::<dyn Fn(web_sys::Event)>::new({
+            let pending = pending.clone();
+            let failed = failed.clone();
+            move |event: web_sys::Event| {
+                web_sys::console::error_2(&"the Python worker failed".into(), &event);
+                failed.set(true);
+                pending.borrow_mut().clear();
+            }
+        });
+        worker.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+        Worker {
+            worker,
+            next_id: Cell::new(0),
+            pending,
+            failed,
+            _on_message: on_message,
+            _on_error: on_error,
+        }
+    }
+}
+
+thread_local! {
+    static WORKER: Worker = Worker::new();
+    // the package, name and modification time of the `.msg` files that the worker last received
+    static SENT_MESSAGES: RefCell<Vec<(String, String, f64)>> = const { RefCell::new(Vec::new()) };
+}
+
+// posts a request to the worker, the receiver gets its result, which is null if the worker stopped
+fn send(request: &str, fields: &[(&str, JsValue)]) -> oneshot::Receiver<JsValue> {
+    WORKER.with(|worker| {
+        let (sender, receiver) = oneshot::channel();
+        if worker.failed.get() {
+            return receiver;
+        }
+        let id = worker.next_id.get();
+        worker.next_id.set(id.wrapping_add(1));
+        worker.pending.borrow_mut().insert(id, sender);
+        let message = js_sys::Object::new();
+        set(&message, "id", id);
+        set(&message, "request", request);
+        for (key, value) in fields {
+            set(&message, key, value.clone());
+        }
+        worker.worker.post_message(&message).unwrap();
+        receiver
+    })
+}
+
+// sends a request to the worker and waits for its result, which is null if the worker stopped
+async fn request(request: &str, fields: &[(&str, JsValue)]) -> JsValue {
+    send(request, fields).await.unwrap_or(JsValue::NULL)
+}
+
+// the `.msg` files in a directory and in its subdirectories
+fn add_message_files(files: &mut Vec<(String, String, Rc<vfs::File>)>, package: &str, directory: &vfs::Directory) {
+    for file in directory.files.lock_ref().iter() {
+        if let Some(name) = file.name.lock_ref().strip_suffix(".msg") {
+            files.push((package.to_owned(), name.to_owned(), file.clone()));
+        }
+    }
+    for subdirectory in directory.directories.lock_ref().iter() {
+        add_message_files(files, package, subdirectory);
+    }
+}
+
+// the message types of the project are imported from `<package>.msg` where the package is the
+// top-level directory that they are in, the worker keeps them, so they are only sent again once a
+// `.msg` file is added, removed, renamed or written
+fn update_messages() {
+    let mut files = Vec::new();
+    crate::PROJECT.with(|project| {
+        for package in project.directories.lock_ref().iter() {
+            add_message_files(&mut files, &package.name.get_cloned(), package);
+        }
+    });
+    let fingerprint = files.iter()
+        .map(|(package, name, file)| (package.clone(), name.clone(), file.modified.get()))
+        .collect::<Vec<_>>();
+    if SENT_MESSAGES.with(|sent| sent.replace(fingerprint.clone())) == fingerprint {
+        return;
+    }
+    let messages = files.iter()
+        .map(|(package, name, file)| {
+            let message = js_sys::Object::new();
+            set(&message, "package", package.as_str());
+            set(&message, "name", name.as_str());
+            set(&message, "text", String::from_utf8_lossy(&file.data.lock_ref()).into_owned());
+            message
+        })
+        .collect::<js_sys::Array>();
+    // requests are handled in order, so the following ones see the new messages
+    let _ = send("setMessages", &[("messages", messages.into())]);
+}
+
+// the diagnostics of a Python file in the form that the lint source of CodeMirror returns
+pub async fn check_python(text: String) -> js_sys::Array {
+    match request("check", &[("text", text.into())]).await {
+        result if result.is_null() => js_sys::Array::new(),
+        result => result.unchecked_into(),
+    }
+}
+
+// the classes and functions of a Python file, nested in the order they appear in the file, or null
+pub async fn symbols_python(text: String) -> JsValue {
+    request("symbols", &[("text", text.into())]).await
+}
+
+// the completions at a position in the form that the completion sources of CodeMirror return, or
+// null
+pub async fn complete_python(text: String, position: u32, explicit: bool) -> JsValue {
+    update_messages();
+    request("complete", &[("text", text.into()), ("position", position.into()), ("explicit", explicit.into())]).await
+}
+
+// the signature and the description of the name at a position with its range, or null
+pub async fn hover_python(text: String, position: u32) -> JsValue {
+    update_messages();
+    request("hover", &[("text", text.into()), ("position", position.into())]).await
+}
+
+// the signature of the call around a position with its active parameter, or null
+pub async fn signature_help_python(text: String, position: u32) -> JsValue {
+    update_messages();
+    request("signatureHelp", &[("text", text.into()), ("position", position.into())]).await
+}
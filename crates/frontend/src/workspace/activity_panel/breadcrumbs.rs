@@ -0,0 +1,197 @@
+use std::rc::Rc;
+
+use dominator::{clone, events, html, with_node, Dom};
+use futures_signals::{map_ref, signal::{Mutable, SignalExt}};
+
+use crate::{filetype::FileType, styles, symbols::{self, Symbol}, vfs::{self, Directory, File}};
+
+use super::{editor::Editor, ActivityPanel};
+
+pub const BREADCRUMBS_HEIGHT: u32 = 22;
+
+#[derive(Clone)]
+enum Entry {
+    Directory(Rc<Directory>),
+    File(Rc<File>),
+    Symbol(Symbol),
+}
+
+impl Entry {
+    fn name(&self) -> String {
+        match self {
+            Entry::Directory(directory) => directory.name.get_cloned(),
+            Entry::File(file) => file.name.get_cloned(),
+            Entry::Symbol(symbol) => symbol.name.clone(),
+        }
+    }
+
+    fn render(&self) -> Dom {
+        html!("div", {
+            .apply(styles::breadcrumbs::segment)
+            .apply_if(matches!(self, Entry::File(_)), |dom| {
+                dom.child(FileType::from_name(&self.name()).icon.render("1em"))
+            })
+            .child(html!("span", {
+                .text(&self.name())
+            }))
+            .apply(|dom| match self {
+                Entry::Symbol(symbol) => dom.child(html!("span", {
+                    .class("opacity-60")
+                    .text(symbol.kind.label())
+                })),
+                _ => dom,
+            })
+        })
+    }
+}
+
+// a segment of the bar and the entries that its dropdown offers instead
+struct Segment {
+    entry: Entry,
+    siblings: Vec<Entry>,
+}
+
+#[derive(Clone)]
+struct Dropdown {
+    position: (i32, i32),
+    entries: Vec<Entry>,
+}
+
+// folders first, each sorted by name
+fn contents(directory: &Directory) -> Vec<Entry> {
+    let mut directories = directory.directories.lock_ref().to_vec();
+    directories.sort_by_key(|directory| directory.name.get_cloned());
+    let mut files = directory.files.lock_ref().to_vec();
+    files.sort_by_key(|file| file.name.get_cloned());
+    directories.into_iter()
+        .map(Entry::Directory)
+        .chain(files.into_iter().map(Entry::File))
+        .collect()
+}
+
+fn path_segments(file: &Rc<File>) -> Vec<Segment> {
+    crate::PROJECT.with(|root| {
+        let Some(ancestors) = vfs::ancestors_of_file(root, file) else {
+            return Vec::new();
+        };
+        let mut segments = ancestors.iter()
+            .enumerate()
+            .map(|(index, directory)| Segment {
+                entry: Entry::Directory(directory.clone()),
+                // the root has no siblings, so its dropdown lists its contents instead
+                siblings: contents(&ancestors[index.saturating_sub(1)]),
+            })
+            .collect::<Vec<_>>();
+        if let Some(parent) = ancestors.last() {
+            segments.push(Segment {
+                entry: Entry::File(file.clone()),
+                siblings: contents(parent),
+            });
+        }
+        segments
+    })
+}
+
+// the symbols that enclose the cursor, from the outermost to the innermost one
+fn symbol_segments(symbols: &[Symbol], cursor: usize) -> Vec<Segment> {
+    let mut siblings = symbols;
+    let mut segments = Vec::new();
+    for symbol in symbols::enclosing(symbols, cursor) {
+        segments.push(Segment {
+            entry: Entry::Symbol(symbol.clone()),
+            siblings: siblings.iter().cloned().map(Entry::Symbol).collect(),
+        });
+        siblings = &symbol.children;
+    }
+    segments
+}
+
+fn render_segment(segment: Segment, dropdown: &Mutable<Option<Dropdown>>) -> Dom {
+    html!("div", {
+        .child(segment.entry.render())
+        .with_node!(element => {
+            .event(clone!(dropdown => move |event: events::Click| {
+                // the global click listener would close the dropdown again straight away
+                event.stop_propagation();
+                let rect = element.get_bounding_client_rect();
+                dropdown.set(Some(Dropdown {
+                    position: (rect.left() as i32, rect.bottom() as i32),
+                    entries: segment.siblings.clone(),
+                }));
+            }))
+        })
+    })
+}
+
+fn render_dropdown(
+    state: &Mutable<Option<Dropdown>>,
+    dropdown: &Dropdown,
+    panel: &Rc<ActivityPanel>,
+    editor: &Rc<Editor>
+) -> Dom {
+    let position = dropdown.position;
+    html!("div", {
+        .apply(|dom| styles::contextmenu::body(dom, &position.0, &position.1))
+        .apply(styles::breadcrumbs::dropdown)
+        .children(dropdown.entries.iter().map(|entry| html!("div", {
+            .apply(styles::contextmenu::option)
+            .child(entry.render())
+            .event(clone!(state, panel, editor, entry => move |event: events::Click| {
+                event.stop_propagation();
+                match &entry {
+                    // folders are opened in place so that the dropdown can be used to navigate
+                    Entry::Directory(directory) => state.set(Some(Dropdown {
+                        position,
+                        entries: contents(directory),
+                    })),
+                    Entry::File(file) => {
+                        state.set(None);
+                        panel.open_file(file.clone());
+                    }
+                    Entry::Symbol(symbol) => {
+                        state.set(None);
+                        editor.select(symbol.selection);
+                    }
+                }
+            }))
+        })))
+    })
+}
+
+pub fn render(panel: &Rc<ActivityPanel>, editor: &Rc<Editor>) -> Dom {
+    let dropdown = Mutable::new(None);
+    let file = editor.file.clone();
+    let segments = map_ref! {
+        let symbols = editor.symbols(),
+        let cursor = editor.cursor.signal() => {
+            let mut segments = path_segments(&file);
+            segments.extend(symbol_segments(symbols, *cursor));
+            segments
+        }
+    };
+
+    html!("div", {
+        .apply(styles::breadcrumbs::bar)
+        .children_signal_vec(segments
+            .map(clone!(dropdown => move |segments| {
+                let mut children = Vec::new();
+                for (index, segment) in segments.into_iter().enumerate() {
+                    if index > 0 {
+                        children.push(html!("span", {
+                            .apply(styles::breadcrumbs::separator)
+                            .text("›")
+                        }));
+                    }
+                    children.push(render_segment(segment, &dropdown));
+                }
+                children
+            }))
+            .to_signal_vec())
+        .child_signal(dropdown.signal_cloned().map(clone!(dropdown, panel, editor => move |state| {
+            state.map(|state| render_dropdown(&dropdown, &state, &panel, &editor))
+        })))
+        .global_event(clone!(dropdown => move |_: events::Click| {
+            dropdown.set(None);
+        }))
+    })
+}
@@ -1,10 +1,16 @@
-use std::rc::Rc;
+use std::{cell::{Cell, RefCell}, rc::{Rc, Weak}};
 
-use codemirror_sys::{autocomplete, commands, lang_python, language, search, state, view};
-use dominator::{clone, html, stylesheet, svg, Dom};
-use futures_signals::signal::{self, Signal, SignalExt};
+use codemirror_sys::{autocomplete, commands, language, search, state, view};
+use common::offsets;
+use dominator::{clone, events, html, stylesheet, Dom};
+use futures_signals::{map_ref, signal::{self, Mutable, Signal, SignalExt}};
+use gloo_timers::{callback::Timeout, future::TimeoutFuture};
 use wasm_bindgen::prelude::*;
 
+use crate::{codemirror, filetype::{FileType, Icon, Language}, launch, settings::{Autosave, EditorOptions, LineNumbers, RenderWhitespace, SETTINGS}, styles, symbols::{self, Symbol}, tooltips, worker};
+
+use super::{indentation::{self, Indentation}, view_state::{self, ViewState}};
+
 // remove this
 macro_rules! object(
     { $($key:expr => $value:expr),+ $(,)?} => {
@@ -20,16 +26,630 @@ macro_rules! object(
     }
 );
 
+const AUTOSAVE_DELAY_MS: u32 = 1000;
+// how long typing has to pause before the worker is asked for the symbols of a Python file
+const SYMBOLS_DELAY_MS: u32 = 300;
+
+thread_local! {
+    // the open editors, editors of the same file in different groups share their edits
+    static EDITORS: RefCell<Vec<Weak<Editor>>> = const { RefCell::new(Vec::new()) };
+}
+
+// what the linter looks for and what is completed
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Analysis {
+    None,
+    // the launch file against the schema and the project, completions of the schema and the project
+    LaunchFile,
+    // syntax errors and names that are not defined, completions, signature help and hover
+    // documentation of rclpy and the message types
+    Python,
+}
+
+// the parts of the configuration that follow the editor settings
+struct Compartments {
+    indentation: state::Compartment,
+    wrapping: state::Compartment,
+    font: state::Compartment,
+    rulers: state::Compartment,
+    whitespace: state::Compartment,
+    line_numbers: state::Compartment,
+}
+
+impl Compartments {
+    fn new() -> Compartments {
+        Compartments {
+            indentation: state::Compartment::new(),
+            wrapping: state::Compartment::new(),
+            font: state::Compartment::new(),
+            rulers: state::Compartment::new(),
+            whitespace: state::Compartment::new(),
+            line_numbers: state::Compartment::new(),
+        }
+    }
+
+    // the compartments start out empty and are filled once the editor is rendered
+    fn extensions(&self) -> [JsValue; 6] {
+        let empty = JsValue::from(js_sys::Array::new());
+        [
+            self.indentation.of(&empty),
+            self.wrapping.of(&empty),
+            self.font.of(&empty),
+            self.rulers.of(&empty),
+            self.whitespace.of(&empty),
+            self.line_numbers.of(&empty),
+        ]
+    }
+}
+
 pub struct Editor {
     pub file: Rc<crate::vfs::File>,
+    // the text of the buffer, which can be ahead of the file until it is saved
+    buffer: Mutable<Rc<String>>,
+    // set for images, binaries and files that are not UTF-8, which are neither shown nor written
+    // since decoding them as text would corrupt them
+    not_text: bool,
+    // the symbols of the buffer, the last ones are kept while the worker finds the new ones
+    symbols: Mutable<Rc<Vec<Symbol>>>,
+    // the position of the primary cursor in UTF-16 code units
+    pub cursor: Mutable<usize>,
+    // set while the buffer has changes that are not written to the file yet, shared with the other
+    // editors of the file
+    pub dirty: Mutable<bool>,
+    // set once the file is edited in this editor, which keeps a preview tab open
+    pub edited: Mutable<bool>,
+    // the view lives as long as the tab, so that switching tabs keeps the selection, the folded
+    // ranges and the undo history
+    view: view::EditorView,
+    // the scroll offset is lost while the view is not in the document, so it is tracked here
+    scroll_top: Cell<i32>,
+    // the contents of the file after it was changed elsewhere while the buffer had unsaved edits,
+    // shared with the other editors of the file
+    conflict: Mutable<Option<String>>,
+    // the modification time of the file that the buffer is based on, a hidden editor does not
+    // follow the file, so it compares before writing, shared with the other editors of the file
+    based_on: Rc<Cell<f64>>,
+    // set while changes of the file are applied to the buffer, these are not written back
+    following_file: Cell<bool>,
+    // set while the edits of another editor of the file are applied, these are not passed on again
+    mirroring: Cell<bool>,
+    // pending save when saving after a delay, dropping it cancels the save
+    save_timeout: RefCell<Option<Timeout>>,
+    // holds the language support, which follows the name of the file unless the user chose one
+    language: state::Compartment,
+    // holds the linter and the completions, which depend on the language and, for launch files, on
+    // the file name
+    analysis: state::Compartment,
+    language_override: Mutable<Option<Language>>,
+    compartments: Compartments,
+    // the options that the compartments were last configured with
+    applied_options: RefCell<Option<EditorOptions>>,
+    // the indentation found in the file when it was opened
+    detected_indentation: Option<Indentation>,
+    // the indentation chosen in the status bar, which takes precedence over the settings
+    indentation: Mutable<Option<Indentation>>,
 }
 
 impl Editor {
     // pass signals for saving?
-    pub fn new(file: Rc<crate::vfs::File>) -> Editor {
-        Editor {
-            file
+    pub fn new(file: Rc<crate::vfs::File>) -> Rc<Editor> {
+        Rc::new_cyclic(|this: &Weak<Editor>| {
+            let update_closure = clone!(this => move |update: view::ViewUpdate| {
+                let Some(this) = this.upgrade() else {
+                    return;
+                };
+                let doc_changed = update.doc_changed();
+                let autosave = SETTINGS.with(|settings| settings.editor_autosave.get());
+                let update = update.unchecked_ref::<codemirror::Update>();
+                if doc_changed {
+                    this.buffer.set(Rc::new(update.state().doc().text()));
+                }
+                if doc_changed && !this.mirroring.get() {
+                    let changes = update.changes();
+                    for sibling in this.siblings() {
+                        sibling.mirror(&changes);
+                    }
+                }
+                if doc_changed && !this.following_file.get() && !this.mirroring.get() {
+                    this.dirty.set_neq(true);
+                    this.edited.set_neq(true);
+                    match autosave {
+                        Autosave::OnKeystroke => {
+                            this.save();
+                        }
+                        Autosave::AfterDelay => Self::save_later(&this),
+                        Autosave::OnFocusChange | Autosave::Off => {}
+                    }
+                }
+                if autosave == Autosave::OnFocusChange && update.focus_changed() && !update.view().has_focus() {
+                    this.save();
+                }
+                if doc_changed || update.selection_set() {
+                    this.cursor.set_neq(codemirror::cursor(&update.state()));
+                    // the view cannot be updated again while it handles this update
+                    if this.applied_options.borrow().as_ref().is_some_and(|options| options.line_numbers == LineNumbers::Relative) {
+                        wasm_bindgen_futures::spawn_local(clone!(this => async move {
+                            this.refresh_line_numbers();
+                        }));
+                    }
+                }
+            });
+
+            // saves regardless of the autosave setting
+            let save_binding = object! {
+                "key" => "Mod-s",
+                "preventDefault" => true,
+                "run" => Closure::<dyn Fn() -> bool>::new(clone!(this => move || {
+                    if let Some(this) = this.upgrade() {
+                        this.save();
+                    }
+                    true
+                })).into_js_value(),
+            };
+
+            // another editor of the file may have unsaved edits, later changes of the file are
+            // followed in `follow_file`
+            let sibling = Self::editors_of(&file).into_iter().next();
+            let not_text = matches!(FileType::from_name(&file.name.lock_ref()).icon, Icon::Image | Icon::Binary)
+                || std::str::from_utf8(&file.data.lock_ref()).is_err();
+            let data = match &sibling {
+                Some(sibling) => sibling.text(),
+                None if not_text => String::new(),
+                None => String::from_utf8_lossy(&file.data.lock_ref()).into_owned(),
+            };
+            let view_state = view_state::load(&file)
+                .map(|view_state| view_state.clamp(data.encode_utf16().count()))
+                .unwrap_or_default();
+            let detected_indentation = indentation::detect(&data);
+            let buffer = Mutable::new(Rc::new(data.clone()));
+            let language = state::Compartment::new();
+            let analysis = state::Compartment::new();
+            let compartments = Compartments::new();
+            let initial_language = FileType::from_name(&file.name.lock_ref()).language;
+            let state = state::EditorState::create(&object! {
+                "doc" => JsValue::from(data),
+                "selection" => object! {
+                    "anchor" => view_state.anchor as u32,
+                    "head" => view_state.head as u32,
+                },
+                "extensions" => [
+                    view::highlight_active_line_gutter(),
+                    view::highlight_special_chars(),
+                    commands::history(),
+                    language::fold_gutter(),
+                    view::draw_selection(),
+                    view::drop_cursor(),
+                    language::indent_on_input(),
+                    language::syntax_highlighting(&language::DEFAULT_HIGHLIGHT_STYLE, None),
+                    language::bracket_matching(),
+                    autocomplete::close_brackets(),
+                    autocomplete::autocompletion(),
+                    view::rectangular_selection(),
+                    view::crosshair_cursor(),
+                    view::highlight_active_line(),
+                    search::highlight_selection_matches(),
+                    view::KEYMAP.of(&js_sys::Array::of1(&save_binding)
+                        .concat(&autocomplete::CLOSE_BRACKETS_KEYMAP)
+                        .concat(&commands::DEFAULT_KEYMAP)
+                        .concat(&search::SEARCH_KEYMAP)
+                        .concat(&commands::HISTORY_KEYMAP)
+                        .concat(&language::FOLD_KEYMAP)
+                        .concat(&autocomplete::COMPLETION_KEYMAP)
+                        .concat(&js_sys::Array::of1(&commands::IDENT_WITH_TAB))),
+                    view::EditorView::update_listener()
+                        .of(&Closure::<dyn Fn(_)>::new(update_closure).into_js_value()),
+                    /* dynamic options */
+                    language.of(&initial_language.extension()),
+                    analysis.of(&js_sys::Array::new().into()),
+                    codemirror::READ_ONLY.of(&not_text.into()),
+                    codemirror::EDITABLE.of(&(!not_text).into()),
+                ].into_iter().chain(compartments.extensions()).collect::<js_sys::Array>(),
+            });
+
+            let view = view::EditorView::new(&object! {
+                "state" => state,
+            });
+            if !view_state.folds.is_empty() {
+                let effects = view_state.folds.iter()
+                    .map(|(from, to)| codemirror::FOLD_EFFECT.of(&object! {
+                        "from" => *from as u32,
+                        "to" => *to as u32,
+                    }.into()))
+                    .collect::<js_sys::Array>();
+                view.unchecked_ref::<codemirror::View>().dispatch(&object! {
+                    "effects" => effects,
+                }.into());
+            }
+            let scroll_listener = clone!(this => move || {
+                if let Some(this) = this.upgrade() {
+                    let view = this.view.unchecked_ref::<codemirror::View>();
+                    this.scroll_top.set(view.scroll_dom().scroll_top());
+                }
+            });
+            view.unchecked_ref::<codemirror::View>()
+                .scroll_dom()
+                .add_event_listener_with_callback(
+                    "scroll",
+                    Closure::<dyn Fn()>::new(scroll_listener).into_js_value().unchecked_ref()
+                )
+                .unwrap();
+
+            EDITORS.with(|editors| editors.borrow_mut().push(this.clone()));
+
+            let based_on = sibling.as_ref()
+                .map_or_else(|| Rc::new(Cell::new(file.modified.get())), |sibling| sibling.based_on.clone());
+            Editor {
+                file,
+                buffer,
+                not_text,
+                symbols: Mutable::new(Rc::new(Vec::new())),
+                cursor: Mutable::new(view_state.head),
+                dirty: sibling.as_ref().map_or_else(|| Mutable::new(false), |sibling| sibling.dirty.clone()),
+                edited: Mutable::new(false),
+                view,
+                scroll_top: Cell::new(view_state.scroll_top),
+                conflict: sibling.as_ref().map_or_else(|| Mutable::new(None), |sibling| sibling.conflict.clone()),
+                based_on,
+                following_file: Cell::new(false),
+                mirroring: Cell::new(false),
+                save_timeout: RefCell::new(None),
+                language,
+                analysis,
+                language_override: Mutable::new(None),
+                compartments,
+                applied_options: RefCell::new(None),
+                detected_indentation,
+                indentation: Mutable::new(None),
+            }
+        })
+    }
+
+    // the editors of the file that are still open
+    fn editors_of(file: &Rc<crate::vfs::File>) -> Vec<Rc<Editor>> {
+        EDITORS.with(|editors| {
+            let mut editors = editors.borrow_mut();
+            editors.retain(|editor| editor.strong_count() > 0);
+            editors.iter()
+                .filter_map(Weak::upgrade)
+                .filter(|editor| Rc::ptr_eq(&editor.file, file))
+                .collect()
+        })
+    }
+
+    fn siblings(self: &Rc<Self>) -> Vec<Rc<Editor>> {
+        let mut editors = Self::editors_of(&self.file);
+        editors.retain(|editor| !Rc::ptr_eq(editor, self));
+        editors
+    }
+
+    // applies the edits made in another editor of the file, both buffers hold the same text before
+    fn mirror(&self, changes: &JsValue) {
+        self.mirroring.set(true);
+        self.view.unchecked_ref::<codemirror::View>().dispatch(&object! {
+            "changes" => changes.clone(),
+        }.into());
+        self.mirroring.set(false);
+    }
+
+    // stored when the editor is hidden rather than on each change
+    fn store_view_state(&self) {
+        let state = self.view.unchecked_ref::<codemirror::View>().state();
+        let selection = state.selection().main();
+        let mut folds = Vec::new();
+        codemirror::folded_ranges(&state).between(0, state.doc().length(), &mut |from, to| {
+            folds.push((from as usize, to as usize));
+        });
+        view_state::store(&self.file, &ViewState {
+            anchor: selection.anchor() as usize,
+            head: selection.head() as usize,
+            scroll_top: self.scroll_top.get(),
+            folds,
+        });
+    }
+
+    // writes the buffer to the file, returns false if the buffer is left unsaved because the file
+    // was changed elsewhere
+    pub fn save(&self) -> bool {
+        self.save_timeout.replace(None);
+        self.write()
+    }
+
+    fn write(&self) -> bool {
+        if self.not_text || !self.dirty.get() {
+            return true;
+        }
+        // the file was changed elsewhere since, e.g., while the editor was hidden
+        if self.file.modified.get() != self.based_on.get() {
+            self.follow_file(&self.file.data.get_cloned());
+            if self.conflict.lock_ref().is_some() {
+                return false;
+            }
+        }
+        self.file.write(self.text().into_bytes());
+        self.based_on.set(self.file.modified.get());
+        self.dirty.set_neq(false);
+        true
+    }
+
+    // every edit restarts the delay
+    fn save_later(this: &Rc<Editor>) {
+        let this_weak = Rc::downgrade(this);
+        // the timeout must not be dropped while its callback runs, so the callback only writes
+        this.save_timeout.replace(Some(Timeout::new(AUTOSAVE_DELAY_MS, move || {
+            if let Some(this) = this_weak.upgrade() {
+                this.write();
+            }
+        })));
+    }
+
+    fn text(&self) -> String {
+        self.view.unchecked_ref::<codemirror::View>().state().doc().text()
+    }
+
+    // brings the buffer up to date with the file unless the buffer has unsaved edits, in which
+    // case the user decides which version to keep
+    fn follow_file(&self, data: &[u8]) {
+        if self.not_text {
+            return;
+        }
+        let incoming = String::from_utf8_lossy(data);
+        if incoming == self.text() {
+            self.conflict.set(None);
+        } else if self.dirty.get() {
+            self.conflict.set(Some(incoming.into_owned()));
+            return;
+        } else {
+            self.replace_text(&incoming);
         }
+        self.based_on.set(self.file.modified.get());
+    }
+
+    // CodeMirror maps the selection through the change, so the cursor stays where it was
+    // relative to the surrounding text
+    fn replace_text(&self, text: &str) {
+        let (from, to, insert) = minimal_change(&self.text(), text);
+        self.following_file.set(true);
+        self.view.unchecked_ref::<codemirror::View>().dispatch(&object! {
+            "changes" => object! {
+                "from" => from as u32,
+                "to" => to as u32,
+                "insert" => insert,
+            },
+        }.into());
+        self.following_file.set(false);
+    }
+
+    fn accept_incoming(&self) {
+        if let Some(incoming) = self.conflict.replace(None) {
+            self.replace_text(&incoming);
+            self.based_on.set(self.file.modified.get());
+            self.dirty.set_neq(false);
+        }
+    }
+
+    fn keep_local(&self) {
+        self.conflict.set(None);
+        self.based_on.set(self.file.modified.get());
+        self.save();
+    }
+
+    fn render_conflict(this: &Rc<Editor>) -> Dom {
+        html!("div", {
+            .apply(styles::editor::conflict)
+            .child(html!("span", {
+                .class("flex-1")
+                .text("The file was changed elsewhere while it has unsaved changes in this editor.")
+            }))
+            .child(html!("button", {
+                .apply(styles::editor::conflict_button)
+                .text("Accept Incoming")
+                .event(clone!(this => move |_: events::Click| this.accept_incoming()))
+            }))
+            .child(html!("button", {
+                .apply(styles::editor::conflict_button)
+                .text("Keep Local")
+                .event(clone!(this => move |_: events::Click| this.keep_local()))
+            }))
+        })
+    }
+
+    // the language chosen by the user or else the one of the file name, so renaming a file can
+    // change the language
+    pub fn language(&self) -> impl Signal<Item = Language> {
+        map_ref! {
+            let name = self.file.name.signal_cloned(),
+            let language_override = self.language_override.signal() => {
+                language_override.unwrap_or_else(|| FileType::from_name(name).language)
+            }
+        }
+    }
+
+    pub fn current_language(&self) -> Language {
+        self.language_override.get()
+            .unwrap_or_else(|| FileType::from_name(&self.file.name.lock_ref()).language)
+    }
+
+    // None goes back to the language of the file name
+    pub fn set_language(&self, language: Option<Language>) {
+        self.language_override.set_neq(language);
+    }
+
+    fn configure_language(&self, language: Language) {
+        let effects = self.language.unchecked_ref::<codemirror::Compartment>()
+            .reconfigure(&language.extension());
+        self.view.unchecked_ref::<codemirror::View>().dispatch(&object! {
+            "effects" => effects,
+        }.into());
+    }
+
+    // what the linter checks and what is completed, launch files are XML files that are named like
+    // one
+    fn analysis(&self) -> impl Signal<Item = Analysis> {
+        map_ref! {
+            let language = self.language(),
+            let name = self.file.name.signal_cloned() => match language {
+                Language::Xml if launch::is_launch_file(name) => Analysis::LaunchFile,
+                Language::Python => Analysis::Python,
+                _ => Analysis::None,
+            }
+        }
+    }
+
+    fn configure_analysis(&self, analysis: Analysis) {
+        let lint = |source: JsValue| [codemirror::linter(&source), codemirror::lint_gutter()];
+        let extensions: js_sys::Array = match analysis {
+            Analysis::LaunchFile => lint(lint_launch_file()).into_iter()
+                .chain([completion_extension(complete_launch_file())])
+                .collect(),
+            Analysis::Python => lint(lint_python()).into_iter()
+                .chain([
+                    completion_extension(complete_python()),
+                    tooltips::hover(worker::hover_python),
+                    tooltips::signature_help(worker::signature_help_python).into(),
+                ])
+                .collect(),
+            Analysis::None => js_sys::Array::new(),
+        };
+        let effects = self.analysis.unchecked_ref::<codemirror::Compartment>()
+            .reconfigure(&extensions);
+        self.view.unchecked_ref::<codemirror::View>().dispatch(&object! {
+            "effects" => effects,
+        }.into());
+    }
+
+    // the settings for the language of the editor with the indentation of the file unless the
+    // indentation was chosen in the status bar
+    pub fn options(&self) -> impl Signal<Item = EditorOptions> {
+        let detected_indentation = self.detected_indentation;
+        map_ref! {
+            let options = self.language().switch(|language| SETTINGS.with(|settings| settings.editor_options(language))),
+            let indentation = self.indentation.signal() => {
+                let mut options = options.clone();
+                let detected_indentation = detected_indentation.filter(|_| options.detect_indentation);
+                if let Some(indentation) = indentation.or(detected_indentation) {
+                    options.insert_spaces = indentation.insert_spaces;
+                    options.tab_size = indentation.tab_size.unwrap_or(options.tab_size);
+                }
+                options
+            }
+        }
+    }
+
+    // None goes back to the indentation of the settings or of the file
+    pub fn set_indentation(&self, indentation: Option<Indentation>) {
+        self.indentation.set_neq(indentation);
+    }
+
+    // only the compartments whose options changed are reconfigured
+    fn configure_options(&self, options: &EditorOptions) {
+        let previous = self.applied_options.replace(Some(options.clone()));
+        let changed = |same: fn(&EditorOptions, &EditorOptions) -> bool| {
+            previous.as_ref().is_none_or(|previous| !same(previous, options))
+        };
+        let effects = js_sys::Array::new();
+        let reconfigure = |compartment: &state::Compartment, changed: bool, extension: &dyn Fn() -> JsValue| {
+            if changed {
+                effects.push(&compartment.unchecked_ref::<codemirror::Compartment>().reconfigure(&extension()));
+            }
+        };
+        let compartments = &self.compartments;
+        reconfigure(
+            &compartments.indentation,
+            changed(|previous, options| previous.tab_size == options.tab_size && previous.insert_spaces == options.insert_spaces),
+            &|| indentation_extension(options)
+        );
+        reconfigure(
+            &compartments.wrapping,
+            changed(|previous, options| previous.word_wrap == options.word_wrap),
+            &|| match options.word_wrap {
+                true => codemirror::LINE_WRAPPING.clone(),
+                false => js_sys::Array::new().into(),
+            }
+        );
+        reconfigure(
+            &compartments.font,
+            changed(|previous, options| previous.font_family == options.font_family && previous.font_size == options.font_size),
+            &|| font_extension(options)
+        );
+        reconfigure(
+            &compartments.rulers,
+            changed(|previous, options| previous.rulers == options.rulers),
+            &|| rulers_extension(&options.rulers.0)
+        );
+        reconfigure(
+            &compartments.whitespace,
+            changed(|previous, options| previous.render_whitespace == options.render_whitespace),
+            &|| match options.render_whitespace {
+                RenderWhitespace::None => js_sys::Array::new().into(),
+                RenderWhitespace::Trailing => codemirror::highlight_trailing_whitespace(),
+                RenderWhitespace::All => codemirror::highlight_whitespace(),
+            }
+        );
+        reconfigure(
+            &compartments.line_numbers,
+            changed(|previous, options| previous.line_numbers == options.line_numbers),
+            &|| line_numbers_extension(options.line_numbers)
+        );
+        if effects.length() > 0 {
+            self.view.unchecked_ref::<codemirror::View>().dispatch(&object! {
+                "effects" => effects,
+            }.into());
+        }
+    }
+
+    // the gutter only formats the numbers again when its configuration changes
+    fn refresh_line_numbers(&self) {
+        let effects = self.compartments.line_numbers
+            .unchecked_ref::<codemirror::Compartment>()
+            .reconfigure(&line_numbers_extension(LineNumbers::Relative));
+        self.view.unchecked_ref::<codemirror::View>().dispatch(&object! {
+            "effects" => effects,
+        }.into());
+    }
+
+    // the line and the column of the position, both starting at 1
+    pub fn line_column(&self, position: usize) -> (u32, u32) {
+        let doc = self.view.unchecked_ref::<codemirror::View>().state().doc();
+        let position = (position as u32).min(doc.length());
+        let line = doc.line_at(position);
+        (line.number(), position - line.from() + 1)
+    }
+
+    // the symbols of the buffer, these are updated as the user types whether or not the buffer
+    // is saved, so their positions are in the buffer
+    pub fn symbols(&self) -> impl Signal<Item = Rc<Vec<Symbol>>> {
+        self.symbols.signal_cloned()
+    }
+
+    // a Python file is only sent to the worker once typing pauses, the texts that arrive while this
+    // waits are skipped by the signal
+    async fn update_symbols(this: Rc<Editor>, language: Language, text: Rc<String>) {
+        let symbols = match language {
+            Language::Python => {
+                TimeoutFuture::new(SYMBOLS_DELAY_MS).await;
+                if !Rc::ptr_eq(&text, &this.buffer.get_cloned()) {
+                    return;
+                }
+                symbols::from_js(&worker::symbols_python(String::clone(&text)).await)
+            }
+            Language::Xml => symbols::launch_symbols(&text),
+            _ => Vec::new(),
+        };
+        this.symbols.set(Rc::new(symbols));
+    }
+
+    // moves the cursor to the position and scrolls it into view
+    pub fn select(&self, position: usize) {
+        self.cursor.set_neq(position);
+        Self::dispatch_selection(&self.view, position);
+        self.view.unchecked_ref::<codemirror::View>().focus();
+    }
+
+    fn dispatch_selection(view: &view::EditorView, position: usize) {
+        view.unchecked_ref::<codemirror::View>().dispatch(&object! {
+            "selection" => object! { "anchor" => position as u32 },
+            "scrollIntoView" => true,
+        }.into());
     }
 
     pub fn render(
@@ -37,70 +657,69 @@ impl Editor {
         width: impl Signal<Item = u32> + 'static,
         height: impl Signal<Item = u32> + 'static
     ) -> impl Signal<Item = Option<dominator::Dom>> {
+        // the size is set on the container since the editors of several groups can be visible
         stylesheet!(".cm-editor", {
-            .style_signal("height", height.map(|height| format!("{height}px")))
-            .style_signal("width", width.map(|width| format!("{width}px")))
+            .style("height", "100%")
+            .style("width", "100%")
             .style_important("outline", "none")
         });
-        
-        let update_closure = clone!(this => move |update: view::ViewUpdate| {
-            if update.doc_changed() {
-                // autosave
-                let data = update.state().doc().to_string().as_bytes().to_vec();
-                this.file.data.replace(data);
-            }
-        });
 
-        // TODO: this is not necessary for the moment, but when opening the
-        // file, we are just taking a single snapshot and not updating it.
-        // This is ok since we only allow one editor per file.
-        let data = String::from_utf8(this.file.data.get_cloned()).unwrap();
-    
-        let language = state::Compartment::new();
-        let state = state::EditorState::create(&object! {
-            "doc" => JsValue::from(data),
-            "extensions" => [
-                view::line_numbers(),
-                view::highlight_active_line_gutter(),
-                view::highlight_special_chars(),
-                commands::history(),
-                language::fold_gutter(),
-                view::draw_selection(),
-                view::drop_cursor(),
-                language::indent_on_input(),
-                language::syntax_highlighting(&language::DEFAULT_HIGHLIGHT_STYLE, None),
-                language::bracket_matching(),
-                autocomplete::close_brackets(),
-                autocomplete::autocompletion(),
-                view::rectangular_selection(),
-                view::crosshair_cursor(),
-                view::highlight_active_line(),
-                search::highlight_selection_matches(),
-                view::KEYMAP.of(&js_sys::Array::new()
-                    .concat(&autocomplete::CLOSE_BRACKETS_KEYMAP)
-                    .concat(&commands::DEFAULT_KEYMAP)
-                    .concat(&search::SEARCH_KEYMAP)
-                    .concat(&commands::HISTORY_KEYMAP)
-                    .concat(&language::FOLD_KEYMAP)
-                    .concat(&autocomplete::COMPLETION_KEYMAP)
-                    .concat(&js_sys::Array::of1(&commands::IDENT_WITH_TAB))),
-                view::EditorView::update_listener()
-                    .of(&Closure::<dyn Fn(_)>::new(update_closure).into_js_value()),
-                /* dynamic options */
-                language.of(&lang_python::python()),
-            ].into_iter().collect::<js_sys::Array>(),
-        });
-        
-        let view = view::EditorView::new(&object! {
-            "state" => state,
-        });
+        // the view is moved into the new container, it keeps its state but has to be measured
+        // again and scrolled back
+        let view = this.view.clone();
+        let scroll_top = this.scroll_top.get();
 
         signal::always(Some(html!("div", {
             .class("block")
-            .class("h-full")
-            .after_inserted(move |parent| {
+            .class("relative")
+            .style_signal("height", height.map(|height| format!("{height}px")))
+            .style_signal("width", width.map(|width| format!("{width}px")))
+            .attr_signal("data-language", this.language().map(|language| language.as_ref().to_owned()))
+            .after_removed(clone!(this => move |_| this.store_view_state()))
+            // an editor that is not shown picks up a rename once it is shown again
+            .future(this.language().for_each(clone!(this => move |language| {
+                this.configure_language(language);
+                async {}
+            })))
+            .future(this.analysis().dedupe().for_each(clone!(this => move |analysis| {
+                this.configure_analysis(analysis);
+                async {}
+            })))
+            // launch files are checked against the packages and Python files against the messages
+            // of the project, an editor without a linter ignores this
+            .future(crate::PROJECT.with(|root| root.changes()).for_each(clone!(this => move |_| {
+                codemirror::force_linting(this.view.unchecked_ref());
+                async {}
+            })))
+            .future(map_ref! {
+                let language = this.language(),
+                let text = this.buffer.signal_cloned() => (*language, text.clone())
+            }.for_each(clone!(this => move |(language, text)| Self::update_symbols(this.clone(), language, text))))
+            .future(this.options().for_each(clone!(this => move |options| {
+                this.configure_options(&options);
+                async {}
+            })))
+            // an editor that is not shown catches up once it is shown again
+            .future(this.file.data.signal_cloned().for_each(clone!(this => move |data| {
+                this.follow_file(&data);
+                async {}
+            })))
+            .child_signal(this.conflict.signal_ref(clone!(this => move |conflict| {
+                conflict.is_some().then(|| Self::render_conflict(&this))
+            })))
+            .apply_if(this.not_text, |dom| dom.child(html!("div", {
+                .apply(styles::editor::not_text)
+                .text("The file is not displayed because it is either binary or uses an unsupported text encoding.")
+            })))
+            .after_inserted(clone!(this => move |parent| {
+                if this.not_text {
+                    return;
+                }
                 parent.append_child(&view.dom()).unwrap();
-            })
+                let view = view.unchecked_ref::<codemirror::View>();
+                view.scroll_dom().set_scroll_top(scroll_top);
+                view.request_measure();
+            }))
         })))
     }
 
@@ -111,15 +730,140 @@ impl Editor {
         })
     }
 
-    pub fn icon(&self) -> Dom {
-        const PATH: &str = "M14,2H6A2,2 0 0,0 4,4V20A2,2 0 0,0 6,22H18A2,2 0 0,0 20,20V8L14,2M18,20H6V4H13V9H18V20Z";
-        svg!("svg", {
-            .attr("height", "1.25em")
-            .attr("viewBox", "0 0 24 24")
-            .child(svg!("path", {
-                .attr("d", PATH)
-            }))
+    pub fn icon(&self) -> impl Signal<Item = Option<Dom>> {
+        self.file.name.signal_ref(|name| Some(FileType::from_name(name).icon.render("1.25em")))
+    }
+}
+
+// the view outlives its container, it is destroyed with the tab so that its listeners and pending
+// measurements are dropped
+impl Drop for Editor {
+    fn drop(&mut self) {
+        self.view.unchecked_ref::<codemirror::View>().destroy();
+    }
+}
+
+// the source of the linter, which checks the launch file against the current project
+fn lint_launch_file() -> JsValue {
+    let source = |view: JsValue| {
+        let text = view.unchecked_into::<codemirror::View>().state().doc().text();
+        let diagnostics = crate::PROJECT.with(|root| launch::lint::lint(&text, &launch::packages(root)));
+        common::diagnostics::to_js(&text, &diagnostics)
+    };
+    Closure::<dyn Fn(JsValue) -> js_sys::Array>::new(source).into_js_value()
+}
+
+// the source of the linter for Python files, which are parsed in a web worker
+fn lint_python() -> JsValue {
+    let source = |view: JsValue| {
+        let text = view.unchecked_into::<codemirror::View>().state().doc().text();
+        wasm_bindgen_futures::future_to_promise(async move {
+            Ok(worker::check_python(text).await.into())
         })
+    };
+    Closure::<dyn Fn(JsValue) -> js_sys::Promise>::new(source).into_js_value()
+}
+
+// the source of the completions in launch files, which come from the schema and the current project
+fn complete_launch_file() -> JsValue {
+    let source = |context: JsValue| {
+        let context = context.unchecked_into::<codemirror::CompletionContext>();
+        let text = context.state().doc().text();
+        let position = offsets::byte_offset(&text, context.pos());
+        let completions = crate::PROJECT.with(|root| {
+            launch::complete::complete(&text, position, context.explicit(), &launch::packages(root))
+        });
+        completions.map_or(JsValue::NULL, |completions| completions.to_js(&text, codemirror::snippet_completion))
+    };
+    Closure::<dyn Fn(JsValue) -> JsValue>::new(source).into_js_value()
+}
+
+// the source of the completions in Python files, which come from the API of rclpy and the message
+// types and are found in a web worker
+fn complete_python() -> JsValue {
+    let source = |context: JsValue| {
+        let context = context.unchecked_into::<codemirror::CompletionContext>();
+        let text = context.state().doc().text();
+        let completions = worker::complete_python(text, context.pos(), context.explicit());
+        wasm_bindgen_futures::future_to_promise(async move { Ok(completions.await) })
+    };
+    Closure::<dyn Fn(JsValue) -> js_sys::Promise>::new(source).into_js_value()
+}
+
+// adds a completion source to those of the language
+fn completion_extension(source: JsValue) -> JsValue {
+    let language_data = move || js_sys::Array::of1(&object! { "autocomplete" => source.clone() });
+    codemirror::LANGUAGE_DATA.of(&Closure::<dyn Fn() -> js_sys::Array>::new(language_data).into_js_value())
+}
+
+fn indentation_extension(options: &EditorOptions) -> JsValue {
+    let unit = match options.insert_spaces {
+        true => " ".repeat(options.tab_size as usize),
+        false => "\t".to_owned(),
+    };
+    js_sys::Array::of2(
+        &codemirror::TAB_SIZE.of(&options.tab_size.into()),
+        &codemirror::INDENT_UNIT.of(&unit.into()),
+    ).into()
+}
+
+// CodeMirror sets the font family on the scroller
+fn font_extension(options: &EditorOptions) -> JsValue {
+    codemirror::theme(&object! {
+        "&" => object! { "fontSize" => format!("{}px", options.font_size) },
+        ".cm-scroller" => object! { "fontFamily" => options.font_family.clone() },
+    }.into())
+}
+
+// each ruler is a one pixel line in the background of the lines, which are indented by 6px
+fn rulers_extension(columns: &[u32]) -> JsValue {
+    if columns.is_empty() {
+        return js_sys::Array::new().into();
+    }
+    let color = styles::RULER_COLOR;
+    let gradients = columns.iter()
+        .map(|column| format!("linear-gradient(to right, transparent calc(6px + {column}ch), \
+            {color} calc(6px + {column}ch), {color} calc(7px + {column}ch), transparent calc(7px + {column}ch))"))
+        .collect::<Vec<_>>();
+    codemirror::theme(&object! {
+        ".cm-line" => object! { "backgroundImage" => gradients.join(", ") },
+    }.into())
+}
+
+// relative numbers show the distance to the line of the cursor, which itself keeps its number
+fn line_numbers_extension(line_numbers: LineNumbers) -> JsValue {
+    match line_numbers {
+        LineNumbers::On => view::line_numbers(),
+        LineNumbers::Off => js_sys::Array::new().into(),
+        LineNumbers::Relative => {
+            let format_number = |number: u32, state: JsValue| {
+                let state = state.unchecked_into::<codemirror::State>();
+                let current = state.doc().line_at(codemirror::cursor(&state) as u32).number();
+                match number == current {
+                    true => number.to_string(),
+                    false => number.abs_diff(current).to_string(),
+                }
+            };
+            codemirror::line_numbers_with(&object! {
+                "formatNumber" => Closure::<dyn Fn(u32, JsValue) -> String>::new(format_number).into_js_value(),
+            }.into())
+        }
     }
 }
 
+// the smallest replacement that turns the old text into the new one as the range of the old text
+// in UTF-16 code units and the text to insert
+fn minimal_change(old: &str, new: &str) -> (usize, usize, String) {
+    let prefix = old.chars()
+        .zip(new.chars())
+        .take_while(|(old, new)| old == new)
+        .map(|(character, _)| character.len_utf8())
+        .sum::<usize>();
+    let suffix = old[prefix..].chars().rev()
+        .zip(new[prefix..].chars().rev())
+        .take_while(|(old, new)| old == new)
+        .map(|(character, _)| character.len_utf8())
+        .sum::<usize>();
+    let range = offsets::utf16_ranges(old, &[prefix..old.len() - suffix]).remove(0);
+    (range.start, range.end, new[prefix..new.len() - suffix].to_owned())
+}
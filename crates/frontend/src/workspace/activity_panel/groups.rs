@@ -0,0 +1,341 @@
+// the editor area is split into groups that each have their own tabs, the groups are laid out in a
+// single row or column and the dividers between them are dragged like the other resizers
+use std::rc::Rc;
+
+use dominator::{clone, events, html, with_node, Dom, EventOptions};
+use futures::StreamExt;
+use futures_signals::{map_ref, signal::{Mutable, Signal, SignalExt}, signal_vec::{MutableVec, SignalVecExt}};
+
+use crate::styles;
+
+use super::{Activity, ActivityPanel};
+
+const RESIZER_PX: u32 = 3;
+// no group can be made smaller than this share of the editor area
+const MIN_SHARE: f64 = 0.1;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    // the groups are side by side
+    Horizontal,
+    // the groups are on top of each other
+    Vertical,
+}
+
+pub struct EditorGroups {
+    groups: MutableVec<Rc<ActivityPanel>>,
+    // the group that receives the files opened from the explorer
+    active_group: Mutable<Rc<ActivityPanel>>,
+    orientation: Mutable<Orientation>,
+    // the tab being dragged and the group that it comes from
+    pub(super) dragged_tab: Mutable<Option<(Rc<ActivityPanel>, Rc<Activity>)>>,
+    // the index of the group after the divider that is being dragged
+    resizing: Mutable<Option<usize>>,
+}
+
+impl Default for EditorGroups {
+    fn default() -> Self {
+        let group: Rc<ActivityPanel> = Default::default();
+        Self {
+            groups: vec![group.clone()].into(),
+            active_group: Mutable::new(group),
+            orientation: Mutable::new(Orientation::Horizontal),
+            dragged_tab: Mutable::new(None),
+            resizing: Mutable::new(None),
+        }
+    }
+}
+
+impl EditorGroups {
+    pub(super) fn count(&self) -> usize {
+        self.groups.lock_ref().len()
+    }
+
+    pub(super) fn is_active(&self, group: &Rc<ActivityPanel>) -> bool {
+        Rc::ptr_eq(&self.active_group.lock_ref(), group)
+    }
+
+    pub(super) fn is_active_signal(&self, group: &Rc<ActivityPanel>) -> impl Signal<Item = bool> {
+        self.active_group.signal_ref(clone!(group => move |active_group| Rc::ptr_eq(active_group, &group)))
+    }
+
+    pub(super) fn activate(&self, group: &Rc<ActivityPanel>) {
+        if !self.is_active(group) {
+            self.active_group.set(group.clone());
+        }
+    }
+
+    fn position(&self, group: &Rc<ActivityPanel>) -> Option<usize> {
+        self.groups.lock_ref().iter().position(|candidate| Rc::ptr_eq(candidate, group))
+    }
+
+    fn group_of(&self, activity: &Rc<Activity>) -> Option<Rc<ActivityPanel>> {
+        self.groups.lock_ref()
+            .iter()
+            .find(|group| group.position(activity).is_some())
+            .cloned()
+    }
+
+    // a new group after the given one that takes half of its space
+    fn insert_after(&self, group: &Rc<ActivityPanel>) -> Rc<ActivityPanel> {
+        let share = group.share.get() / 2.0;
+        group.share.set(share);
+        let new_group = Rc::new(ActivityPanel::empty(share));
+        let index = self.position(group).map_or(0, |index| index + 1);
+        self.groups.lock_mut().insert_cloned(index, new_group.clone());
+        new_group
+    }
+
+    // the neighbouring group takes over the space of the removed one, the last group stays
+    fn remove(&self, group: &Rc<ActivityPanel>) {
+        let mut groups = self.groups.lock_mut();
+        let Some(index) = groups.iter().position(|candidate| Rc::ptr_eq(candidate, group)) else {
+            return;
+        };
+        if groups.len() < 2 {
+            return;
+        }
+        groups.remove(index);
+        let neighbour = groups[index.saturating_sub(1)].clone();
+        neighbour.share.set(neighbour.share.get() + group.share.get());
+        drop(groups);
+        if self.is_active(group) {
+            self.active_group.set(neighbour);
+        }
+    }
+
+    // opens the file in the group after the active one, which is created if there is none
+    fn open_to_side(&self, file: Rc<crate::vfs::File>) {
+        let active_group = self.active_group.get_cloned();
+        let next = self.position(&active_group)
+            .and_then(|index| self.groups.lock_ref().get(index + 1).cloned());
+        let group = next.unwrap_or_else(|| self.insert_after(&active_group));
+        group.open_file(file);
+        self.active_group.set(group);
+    }
+
+    // opens the file of the activity in a new group, splitting in the other direction turns the
+    // whole layout since the groups are in a single row or column
+    pub(super) fn split(&self, group: &Rc<ActivityPanel>, activity: &Rc<Activity>, orientation: Orientation) {
+        let Some(file) = activity.file() else {
+            return;
+        };
+        self.orientation.set_neq(orientation);
+        let new_group = self.insert_after(group);
+        new_group.open_file(file.clone());
+        self.active_group.set(new_group);
+    }
+
+    pub(super) fn flip_orientation(&self) {
+        self.orientation.set(match self.orientation.get() {
+            Orientation::Horizontal => Orientation::Vertical,
+            Orientation::Vertical => Orientation::Horizontal,
+        });
+    }
+
+    // drops the dragged tab in front of the target tab or at the end of the tab bar, a tab that
+    // comes from another group is moved over unless the group already has an editor for the file
+    pub(super) fn drop_tab(&self, group: &Rc<ActivityPanel>, target: Option<&Rc<Activity>>) {
+        let Some((source, activity)) = self.dragged_tab.replace(None) else {
+            return;
+        };
+        if Rc::ptr_eq(&source, group) {
+            match target {
+                Some(target) => group.move_before(&activity, target),
+                None => group.move_to_end(&activity),
+            }
+            return;
+        }
+        source.close(&activity);
+        group.insert_before(activity, target);
+        self.active_group.set(group.clone());
+    }
+
+    fn handle_command(&self, command: crate::WorkspaceCommand) {
+        let active_group = self.active_group.get_cloned();
+        match command {
+            crate::WorkspaceCommand::OpenFile(file) => active_group.open_file(file),
+            crate::WorkspaceCommand::KeepFileOpen(file) => {
+                active_group.open_file(file);
+                if let Some(activity) = active_group.active_activity.get_cloned() {
+                    active_group.keep_open(&activity);
+                }
+            }
+            crate::WorkspaceCommand::OpenFileAt(file, position) => {
+                active_group.open_file(file);
+                if let Some(Activity::Editor(editor)) = active_group.active_activity.get_cloned().as_deref() {
+                    editor.select(position);
+                }
+            }
+            crate::WorkspaceCommand::OpenFileToSide(file) => self.open_to_side(file),
+            crate::WorkspaceCommand::ActivateActivity(activity) => {
+                if let Some(group) = self.group_of(&activity) {
+                    group.active_activity.set(Some(activity));
+                    self.activate(&group);
+                }
+            }
+            crate::WorkspaceCommand::CloseActivity(activity) => {
+                if let Some(group) = self.group_of(&activity) {
+                    group.request_close(&activity);
+                }
+            }
+        }
+    }
+
+    // moves the divider in front of the group at the index to the position of the pointer
+    fn resize(&self, index: usize, container: &web_sys::HtmlElement, event: &events::PointerMove) {
+        let groups = self.groups.lock_ref();
+        if index == 0 || index >= groups.len() {
+            return;
+        }
+        let rect = container.get_bounding_client_rect();
+        let resizers = (RESIZER_PX * (groups.len() as u32 - 1)) as f64;
+        let (position, available) = match self.orientation.get() {
+            Orientation::Horizontal => (event.x() as f64 - rect.left(), rect.width() - resizers),
+            Orientation::Vertical => (event.y() as f64 - rect.top(), rect.height() - resizers),
+        };
+        if available <= 0.0 {
+            return;
+        }
+        let before = groups[..index - 1].iter().map(|group| group.share.get()).sum::<f64>();
+        let (first, second) = (&groups[index - 1], &groups[index]);
+        let pair = first.share.get() + second.share.get();
+        if pair <= 2.0 * MIN_SHARE {
+            return;
+        }
+        let share = (position / available - before).clamp(MIN_SHARE, pair - MIN_SHARE);
+        first.share.set_neq(share);
+        second.share.set_neq(pair - share);
+    }
+
+    pub fn render(
+        this: &Rc<EditorGroups>,
+        workspace_command_rx: crate::WorkspaceCommandReceiver,
+        sidebar_command_tx: &crate::SidebarCommandSender,
+        width: impl Signal<Item = u32> + 'static,
+        height: impl Signal<Item = u32> + 'static
+    ) -> Dom {
+        let width = width.broadcast();
+        let height = height.broadcast();
+        let count = this.groups.signal_vec_cloned().len().broadcast();
+
+        html!("div" => web_sys::HtmlElement, {
+            .class("flex")
+            .class("h-full")
+            .class_signal("flex-col", this.orientation.signal_ref(|orientation| *orientation == Orientation::Vertical))
+            .future(workspace_command_rx.for_each(clone!(this => move |command| {
+                this.handle_command(command);
+                async {}
+            })))
+            // the explorer lists the open activities of all groups
+            .future(this.groups.signal_vec_cloned()
+                .map_signal(|group| group.activities.signal_vec_cloned().to_signal_cloned())
+                .to_signal_cloned()
+                .for_each(clone!(sidebar_command_tx => move |activities| {
+                    sidebar_command_tx
+                        .unbounded_send(crate::SidebarCommand::OpenActivitiesChanged(activities.concat()))
+                        .unwrap();
+                    async {}
+                })))
+            // let the explorer follow the active editor of the active group
+            .future(this.active_group.signal_cloned()
+                .switch(|group| group.active_activity.signal_cloned())
+                .for_each(clone!(sidebar_command_tx => move |activity| {
+                    sidebar_command_tx
+                        .unbounded_send(crate::SidebarCommand::ActiveActivityChanged(activity))
+                        .unwrap();
+                    async {}
+                })))
+            .global_event(clone!(this => move |_: events::PointerUp| {
+                this.resizing.set_neq(None);
+            }))
+            .with_node!(element => {
+                .global_event(clone!(this => move |event: events::PointerMove| {
+                    if let Some(index) = this.resizing.get() {
+                        this.resize(index, &element, &event);
+                    }
+                }))
+            })
+            .children_signal_vec(this.groups.signal_vec_cloned().enumerate().map(clone!(this, sidebar_command_tx => move |(index, group)| {
+                html!("div", {
+                    .class("flex")
+                    .class_signal("flex-col", this.orientation.signal_ref(|orientation| *orientation == Orientation::Vertical))
+                    .child_signal(index.signal().map(clone!(this => move |index| {
+                        index.filter(|index| *index > 0).map(|index| Self::render_resizer(&this, index))
+                    })))
+                    .child(html!("div", {
+                        .event(clone!(this, group => move |_: events::PointerDown| {
+                            this.activate(&group);
+                        }))
+                        // an empty group closes unless it is the last one
+                        .future(group.activities.signal_vec_cloned().len().for_each(clone!(this, group => move |count| {
+                            if count == 0 {
+                                this.remove(&group);
+                            }
+                            async {}
+                        })))
+                        .child(ActivityPanel::render(
+                            &group,
+                            &this,
+                            &sidebar_command_tx,
+                            Self::size(&this, &group, width.signal(), count.signal(), Orientation::Horizontal),
+                            Self::size(&this, &group, height.signal(), count.signal(), Orientation::Vertical)))
+                    }))
+                })
+            })))
+        })
+    }
+
+    // the share of the group in the direction of the layout, the full extent across it
+    fn size(
+        this: &Rc<EditorGroups>,
+        group: &Rc<ActivityPanel>,
+        total: impl Signal<Item = u32> + 'static,
+        count: impl Signal<Item = usize> + 'static,
+        direction: Orientation
+    ) -> impl Signal<Item = u32> {
+        map_ref! {
+            let total = total,
+            let count = count,
+            let share = group.share.signal(),
+            let orientation = this.orientation.signal() => {
+                match *orientation == direction {
+                    true => {
+                        let resizers = RESIZER_PX * (*count as u32).saturating_sub(1);
+                        (total.saturating_sub(resizers) as f64 * share) as u32
+                    }
+                    false => *total,
+                }
+            }
+        }
+    }
+
+    fn render_resizer(this: &Rc<EditorGroups>, index: usize) -> Dom {
+        let hover = Mutable::new(false);
+        let active = this.resizing.signal_ref(move |resizing| *resizing == Some(index));
+        html!("div", {
+            .apply(|dom| styles::resizer(dom, active, hover.signal()))
+            .class_signal("cursor-ew-resize", this.orientation.signal_ref(|orientation| *orientation == Orientation::Horizontal))
+            .class_signal("cursor-ns-resize", this.orientation.signal_ref(|orientation| *orientation == Orientation::Vertical))
+            .style_signal("width", this.orientation.signal_ref(|orientation| match orientation {
+                Orientation::Horizontal => format!("{RESIZER_PX}px"),
+                Orientation::Vertical => "100%".to_owned(),
+            }))
+            .style_signal("height", this.orientation.signal_ref(|orientation| match orientation {
+                Orientation::Horizontal => "100%".to_owned(),
+                Orientation::Vertical => format!("{RESIZER_PX}px"),
+            }))
+            .event_with_options(&EventOptions::preventable(), clone!(this => move |event: events::PointerDown| {
+                this.resizing.set_neq(Some(index));
+                event.prevent_default();
+            }))
+            .event(clone!(hover => move |_: events::PointerEnter| {
+                hover.set_neq(true);
+            }))
+            .event(move |_: events::PointerLeave| {
+                hover.set_neq(false);
+            })
+        })
+    }
+}
+
@@ -0,0 +1,89 @@
+// guesses the indentation of a file from the leading whitespace of its lines, like VS Code this
+// looks at how much the indentation changes from one line to the next
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Indentation {
+    pub insert_spaces: bool,
+    // None keeps the tab size of the settings, e.g., for files indented with tabs
+    pub tab_size: Option<u32>,
+}
+
+const TAB_SIZES: std::ops::RangeInclusive<usize> = 2..=8;
+
+// None if no line is indented
+pub fn detect(text: &str) -> Option<Indentation> {
+    let mut tab_lines = 0;
+    let mut space_lines = 0;
+    // how often the indentation with spaces grows or shrinks by each number of spaces
+    let mut changes = [0usize; 9];
+    let mut previous = 0;
+    for line in text.lines() {
+        let content = line.trim_start_matches([' ', '\t']);
+        if content.is_empty() {
+            continue;
+        }
+        let indentation = &line[..line.len() - content.len()];
+        if indentation.starts_with('\t') {
+            tab_lines += 1;
+            continue;
+        }
+        let spaces = indentation.len();
+        if spaces > 0 {
+            space_lines += 1;
+        }
+        let change = spaces.abs_diff(previous);
+        if TAB_SIZES.contains(&change) {
+            changes[change] += 1;
+        }
+        previous = spaces;
+    }
+    match (tab_lines, space_lines) {
+        (0, 0) => None,
+        (tabs, spaces) if tabs > spaces => Some(Indentation { insert_spaces: false, tab_size: None }),
+        _ => {
+            // the most common change, the smaller size on a tie
+            let tab_size = TAB_SIZES
+                .filter(|size| changes[*size] > 0)
+                .max_by_key(|size| (changes[*size], std::cmp::Reverse(*size)))
+                .map(|size| size as u32);
+            Some(Indentation { insert_spaces: true, tab_size })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spaces(tab_size: u32) -> Option<Indentation> {
+        Some(Indentation { insert_spaces: true, tab_size: Some(tab_size) })
+    }
+
+    #[test]
+    fn no_indentation() {
+        assert_eq!(detect(""), None);
+        assert_eq!(detect("a = 1\nb = 2\n"), None);
+    }
+
+    #[test]
+    fn spaces_by_change() {
+        assert_eq!(detect("class A:\n    def f(self):\n        pass\n"), spaces(4));
+        assert_eq!(detect("<launch>\n  <node>\n    <param/>\n  </node>\n</launch>\n"), spaces(2));
+        // a continuation line does not outweigh the regular indentation
+        assert_eq!(detect("if a:\n    b(1,\n      2)\n    c()\nif d:\n    e()\n"), spaces(4));
+    }
+
+    #[test]
+    fn tie_prefers_smaller_size() {
+        assert_eq!(detect("a:\n  b:\n      c\n"), spaces(2));
+    }
+
+    #[test]
+    fn tabs() {
+        assert_eq!(detect("a:\n\tb\n\tc\n  d\n"), Some(Indentation { insert_spaces: false, tab_size: None }));
+    }
+
+    #[test]
+    fn blank_lines_are_skipped() {
+        assert_eq!(detect("a:\n   \n   b\n\n   c\n"), spaces(3));
+    }
+}
@@ -1,12 +1,22 @@
-use std::{pin::Pin, rc::Rc};
+use std::{cell::RefCell, pin::Pin, rc::Rc};
 
-use dominator::{clone, events, svg, Dom, EventOptions, html};
-use futures::StreamExt;
-use futures_signals::{signal::{Mutable, Signal, SignalExt}, signal_vec::{MutableVec, SignalVecExt}};
+use dominator::{clone, events, svg, with_node, Dom, EventOptions, html};
+use futures_signals::{map_ref, signal::{self, Mutable, Signal, SignalExt}, signal_vec::{MutableVec, SignalVecExt}};
 
-use crate::styles;
+use strum::IntoEnumIterator;
 
+use groups::{EditorGroups, Orientation};
+
+use crate::{contextmenu::{menu::{self, Entry, Item}, Target}, settings::{Autosave, SETTINGS}, sidebar::explorer::clipboard, styles};
+
+mod breadcrumbs;
 pub mod editor;
+pub mod groups;
+mod indentation;
+mod settings_menu;
+mod status_bar;
+mod switcher;
+pub mod view_state;
 pub mod welcome;
 
 const TAB_HEIGHT: u32 = 35;
@@ -35,33 +45,112 @@ impl Activity {
         }
     }
 
-    pub fn icon(&self) -> Dom {
+    pub fn file(&self) -> Option<&Rc<crate::vfs::File>> {
+        match self {
+            Activity::Editor(editor) => Some(&editor.file),
+            Activity::Welcome(_) => None,
+        }
+    }
+
+    pub fn dirty(&self) -> Pin<Box<dyn Signal<Item = bool>>> {
+        match self {
+            Activity::Editor(editor) => Box::pin(editor.dirty.signal()),
+            Activity::Welcome(_) => Box::pin(signal::always(false)),
+        }
+    }
+
+    // the name shown in lists of activities
+    pub fn title(&self) -> String {
         match self {
-            Activity::Editor(editor) => editor.icon(),
-            Activity::Welcome(welcome) => welcome.icon(),
+            Activity::Editor(editor) => editor.file.name.get_cloned(),
+            Activity::Welcome(_) => "Welcome".to_owned(),
+        }
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        match self {
+            Activity::Editor(editor) => editor.dirty.get(),
+            Activity::Welcome(_) => false,
+        }
+    }
+
+    pub fn icon(&self) -> Pin<Box<dyn Signal<Item = Option<Dom>>>> {
+        match self {
+            Activity::Editor(editor) => Box::pin(editor.icon()),
+            Activity::Welcome(welcome) => Box::pin(signal::always(Some(welcome.icon()))),
         }
     }
 
     fn render_tab(
         this: &Rc<Activity>,
-        panel: &Rc<ActivityPanel>
+        panel: &Rc<ActivityPanel>,
+        groups: &Rc<EditorGroups>
     ) -> dominator::Dom {
+        let mouse_over = Mutable::new(false);
+        let mouse_over_close = Mutable::new(false);
+        // a dirty tab shows a dot until the pointer is over the button
+        let close_icon_path = map_ref! {
+            let dirty = this.dirty(),
+            let mouse_over_close = mouse_over_close.signal() => {
+                match *dirty && !*mouse_over_close {
+                    true => CHANGED_ICON_PATH,
+                    false => CLOSE_ICON_PATH,
+                }
+            }
+        };
         let close_icon = svg!("svg", {
             .attr("height", "1em")
             .attr("viewBox", "0 0 24 24")
             .child(svg!("path", {
-                .attr("d", CLOSE_ICON_PATH)
+                .attr_signal("d", close_icon_path)
+            }))
+        });
+
+        let pin_icon = svg!("svg", {
+            .attr("height", "1em")
+            .attr("viewBox", "0 0 24 24")
+            .child(svg!("path", {
+                .attr("d", PIN_ICON_PATH)
             }))
         });
 
-        let mouse_over = Mutable::new(false);
-        let mouse_over_close = Mutable::new(false);
         let is_active = panel.active_activity.signal_ref(clone!(this => move |active_activity| {
             active_activity.as_ref().is_some_and(|active_activity| Rc::ptr_eq(active_activity, &this))
         }));
 
+        let is_active = is_active.broadcast();
+
         html!("div", {
-            .apply(|dom| styles::tab::body(dom, is_active, mouse_over.signal()))
+            .apply(|dom| styles::tab::body(dom, is_active.signal(), mouse_over.signal()))
+            .class_signal("italic", panel.is_preview_signal(this))
+            .attr("draggable", "true")
+            // keeps the active tab visible when the tab bar is scrolled
+            .with_node!(element => {
+                .future(is_active.signal().for_each(move |is_active| {
+                    if is_active {
+                        element.scroll_into_view_with_bool(false);
+                    }
+                    async {}
+                }))
+            })
+            .event(clone!(panel, groups, this => move |_: events::DragStart| {
+                groups.dragged_tab.set(Some((panel.clone(), this.clone())));
+            }))
+            .event_with_options(&EventOptions::preventable(), |event: events::DragOver| {
+                event.prevent_default(); // Allow drop
+            })
+            // the tab bar around the tab would handle the drop again
+            .event_with_options(&EventOptions::preventable(), clone!(panel, groups, this => move |event: events::Drop| {
+                event.prevent_default();
+                event.stop_propagation();
+                groups.drop_tab(&panel, Some(&this));
+            }))
+            .event(clone!(groups => move |_: events::DragEnd| {
+                groups.dragged_tab.set(None);
+            }))
+            .event(clone!(panel, this => move |_: events::DoubleClick| {
+                panel.keep_open(&this);
+            }))
             .event(clone!(mouse_over => move |_: events::PointerOver| {
                 mouse_over.set_neq(true);
             }))
@@ -71,18 +160,28 @@ impl Activity {
             .event(clone!(panel, this => move |_: events::PointerDown| {                
                 panel.active_activity.set(Some(this.clone()))
             }))
+            .event_with_options(&EventOptions::preventable(), clone!(panel, this => move |event: events::ContextMenu| {
+                event.prevent_default();
+                if this.file().is_some() {
+                    panel.tab_menu.set(Some(TabMenu {
+                        position: (event.x(), event.y()),
+                        activity: this.clone(),
+                    }));
+                }
+            }))
             .child(html!("div", {
                 .apply(styles::icon_text)
                 .class("inline-flex")
                 .child(html!("div", {
                     .apply(styles::icon)
-                    .child(this.icon())
+                    .child_signal(this.icon())
                 }))
                 .child(this.label())
                 // HACK DO NOT SHOW THE CLOSE ICON 
                 .apply_if(matches!(**this, Activity::Editor(_)), |dom| {
                     dom.child(html!("div", {
-                        .apply(|dom| styles::tab::icon(dom, mouse_over_close.signal(), mouse_over.signal()))
+                        .apply(|dom| styles::tab::icon(dom, mouse_over_close.signal(), signal::or(mouse_over.signal(), this.dirty())))
+                        .visible_signal(signal::not(panel.is_pinned_signal(this)))
                         .event(clone!(mouse_over_close => move |_: events::PointerOver| {
                             mouse_over_close.set_neq(true);
                         }))
@@ -91,15 +190,21 @@ impl Activity {
                         }))
                         .event_with_options(&EventOptions::preventable(), clone!(panel, this => move |ev: events::PointerDown| {
                             ev.stop_propagation();
-                            panel.activities.lock_mut().retain(|activity| !Rc::ptr_eq(activity, &this));
-                            let mut active_activity = panel.active_activity.lock_mut();
-                            if active_activity.as_ref().is_some_and(|active_activity| Rc::ptr_eq(active_activity, &this)) {
-                                // simple logic, VS Code is smart and keeps track of the last tab you looked at
-                                *active_activity = panel.activities.lock_ref().first().cloned();
-                            }
+                            panel.request_close(&this);
                         }))
                         .child(close_icon)
                     }))
+                    // pinned tabs cannot be closed from the tab, clicking the pin unpins them
+                    .child(html!("div", {
+                        .apply(styles::icon)
+                        .attr("title", "Unpin")
+                        .visible_signal(panel.is_pinned_signal(this))
+                        .event_with_options(&EventOptions::preventable(), clone!(panel, this => move |ev: events::PointerDown| {
+                            ev.stop_propagation();
+                            panel.set_pinned(&this, false);
+                        }))
+                        .child(pin_icon)
+                    }))
                 })
             }))
         })
@@ -107,19 +212,45 @@ impl Activity {
     }
 }
 
+#[derive(Clone)]
+struct TabMenu {
+    position: (i32, i32),
+    activity: Rc<Activity>,
+}
+
 pub struct ActivityPanel {
     activities: MutableVec<Rc<Activity>>,
-    active_activity: Mutable<Option<Rc<Activity>>>
+    active_activity: Mutable<Option<Rc<Activity>>>,
+    tab_menu: Mutable<Option<TabMenu>>,
+    // the pinned activities come first in the tab bar, in the order in which they were pinned
+    pinned: Mutable<Vec<Rc<Activity>>>,
+    // the activities in the order in which they were last viewed, most recent first
+    history: RefCell<Vec<Rc<Activity>>>,
+    switcher: Mutable<Option<Rc<switcher::Switcher>>>,
+    // the tab that the next opened file replaces until it is edited or double-clicked
+    preview: Mutable<Option<Rc<Activity>>>,
+    // the position of the list of all tabs
+    tab_list: Mutable<Option<(i32, i32)>>,
+    // the unsaved editors that wait for the user to decide whether to save them before closing
+    closing: Mutable<Vec<Rc<Activity>>>,
+    // the share of the editor area taken up by this group
+    share: Mutable<f64>,
+}
+
+#[derive(Clone, Copy)]
+enum CloseChoice {
+    Save,
+    Discard,
+    Cancel,
 }
 
 impl Default for ActivityPanel {
     fn default() -> Self {
         let welcome = Rc::new(Activity::Welcome(Rc::new(welcome::Welcome::new())));
-        
-        Self {
-            activities: vec![welcome.clone()].into(),
-            active_activity: Some(welcome).into()
-        }
+        let panel = Self::empty(1.0);
+        panel.activities.lock_mut().push_cloned(welcome.clone());
+        panel.active_activity.set(Some(welcome));
+        panel
     }
 }
 
@@ -128,14 +259,224 @@ impl Default for ActivityPanel {
 // how do I determine if a file is already open? Files should be uniquely identifiable from their
 // paths
 
-const CLOSE_ICON_PATH: &str = "M19,6.41L17.59,5L12,10.59L6.41,5L5,6.41L10.59,12L5,17.59L6.41,19L12,13.41L17.59,19L19,17.59L13.41,12L19,6.41Z";
-//const CHANGED_ICON_PATH: &str = "M12,2A10,10 0 0,0 2,12A10,10 0 0,0 12,22A10,10 0 0,0 22,12A10,10 0 0,0 12,2Z";
+pub const CLOSE_ICON_PATH: &str = "M19,6.41L17.59,5L12,10.59L6.41,5L5,6.41L10.59,12L5,17.59L6.41,19L12,13.41L17.59,19L19,17.59L13.41,12L19,6.41Z";
+pub const CHANGED_ICON_PATH: &str = "M12,2A10,10 0 0,0 2,12A10,10 0 0,0 12,22A10,10 0 0,0 22,12A10,10 0 0,0 12,2Z";
+const PIN_ICON_PATH: &str = "M16,12V4H17V2H7V4H8V12L6,14V16H11.2V22H12.8V16H18V14L16,12Z";
 
 impl ActivityPanel {
+    fn empty(share: f64) -> Self {
+        Self {
+            activities: MutableVec::new(),
+            active_activity: Mutable::new(None),
+            tab_menu: Mutable::new(None),
+            pinned: Mutable::new(Vec::new()),
+            history: RefCell::new(Vec::new()),
+            switcher: Mutable::new(None),
+            preview: Mutable::new(None),
+            tab_list: Mutable::new(None),
+            closing: Mutable::new(Vec::new()),
+            share: Mutable::new(share),
+        }
+    }
 
-    pub fn render(
+    // activates the editor of the file, opening one if there is none yet, new editors open in the
+    // preview tab
+    fn open_file(&self, file: Rc<crate::vfs::File>) {
+        let mut activities = self.activities.lock_mut();
+        let existing = activities.iter()
+            .find(|activity| match &***activity {
+                Activity::Editor(editor) => Rc::ptr_eq(&editor.file, &file),
+                _ => false,
+            })
+            .cloned();
+        let editor = existing.unwrap_or_else(|| {
+            let editor = Rc::new(Activity::Editor(editor::Editor::new(file)));
+            let preview = self.preview.replace(Some(editor.clone()));
+            match preview.and_then(|preview| activities.iter().position(|activity| Rc::ptr_eq(activity, &preview))) {
+                Some(index) => {
+                    let replaced = activities[index].clone();
+                    activities.set_cloned(index, editor.clone());
+                    self.history.borrow_mut().retain(|candidate| !Rc::ptr_eq(candidate, &replaced));
+                }
+                None => activities.push_cloned(editor.clone()),
+            }
+            editor
+        });
+        self.active_activity.set(Some(editor));
+    }
+
+    // turns the preview tab into a regular one
+    fn keep_open(&self, activity: &Rc<Activity>) {
+        let mut preview = self.preview.lock_mut();
+        if preview.as_ref().is_some_and(|preview| Rc::ptr_eq(preview, activity)) {
+            *preview = None;
+        }
+    }
+
+    fn is_preview_signal(&self, activity: &Rc<Activity>) -> impl Signal<Item = bool> {
+        self.preview.signal_ref(clone!(activity => move |preview| {
+            preview.as_ref().is_some_and(|preview| Rc::ptr_eq(preview, &activity))
+        }))
+    }
+
+    // moves the tab to the position of the target, tabs stay on their side of the pinned tabs
+    fn move_before(&self, activity: &Rc<Activity>, target: &Rc<Activity>) {
+        if self.is_pinned(activity) != self.is_pinned(target) {
+            return;
+        }
+        if let (Some(from), Some(to)) = (self.position(activity), self.position(target)) {
+            self.activities.lock_mut().move_from_to(from, to);
+        }
+    }
+
+    fn move_to_end(&self, activity: &Rc<Activity>) {
+        if self.is_pinned(activity) {
+            return;
+        }
+        if let Some(from) = self.position(activity) {
+            let mut activities = self.activities.lock_mut();
+            let to = activities.len() - 1;
+            activities.move_from_to(from, to);
+        }
+    }
+
+    // adds a tab that was dragged over from another group, an editor of the same file that is
+    // already in this group is activated instead
+    fn insert_before(&self, activity: Rc<Activity>, target: Option<&Rc<Activity>>) {
+        let existing = activity.file().and_then(|file| self.activities.lock_ref()
+            .iter()
+            .find(|candidate| candidate.file().is_some_and(|candidate| Rc::ptr_eq(candidate, file)))
+            .cloned());
+        if let Some(existing) = existing {
+            self.active_activity.set(Some(existing));
+            return;
+        }
+        // unpinned tabs cannot go in front of pinned ones
+        let pinned = self.pinned.lock_ref().len();
+        let index = target
+            .and_then(|target| self.position(target))
+            .map(|index| index.max(pinned));
+        let mut activities = self.activities.lock_mut();
+        match index {
+            Some(index) => activities.insert_cloned(index, activity.clone()),
+            None => activities.push_cloned(activity.clone()),
+        }
+        drop(activities);
+        self.active_activity.set(Some(activity));
+    }
+
+    fn close(&self, activity: &Rc<Activity>) {
+        self.keep_open(activity);
+        self.activities.lock_mut().retain(|candidate| !Rc::ptr_eq(candidate, activity));
+        self.pinned.lock_mut().retain(|candidate| !Rc::ptr_eq(candidate, activity));
+        self.history.borrow_mut().retain(|candidate| !Rc::ptr_eq(candidate, activity));
+        let mut active_activity = self.active_activity.lock_mut();
+        if active_activity.as_ref().is_some_and(|active_activity| Rc::ptr_eq(active_activity, activity)) {
+            // like VS Code, go back to the tab that was looked at before
+            *active_activity = self.history.borrow()
+                .first()
+                .cloned()
+                .or_else(|| self.activities.lock_ref().first().cloned());
+        }
+    }
+
+    // unsaved editors ask whether to save them first
+    fn request_close(&self, activity: &Rc<Activity>) {
+        if !activity.is_dirty() {
+            self.close(activity);
+            return;
+        }
+        let mut closing = self.closing.lock_mut();
+        if !closing.iter().any(|candidate| Rc::ptr_eq(candidate, activity)) {
+            closing.push(activity.clone());
+        }
+    }
+
+    // cancelling also keeps the other editors that were about to be closed
+    fn resolve_close(&self, choice: CloseChoice) {
+        let activity = {
+            let mut closing = self.closing.lock_mut();
+            if closing.is_empty() {
+                return;
+            }
+            if let CloseChoice::Cancel = choice {
+                closing.clear();
+                return;
+            }
+            closing.remove(0)
+        };
+        if let (CloseChoice::Save, Activity::Editor(editor)) = (choice, &*activity) {
+            // the file was changed elsewhere, the editor stays open with the choice between the
+            // versions and the others are kept as well
+            if !editor.save() {
+                self.closing.lock_mut().clear();
+                self.active_activity.set(Some(activity));
+                return;
+            }
+        }
+        self.close(&activity);
+    }
+
+    // closes the editors for which the condition holds given their index in the tab bar, pinned
+    // editors are kept like the welcome page
+    fn close_unpinned(&self, should_close: impl Fn(usize, &Rc<Activity>) -> bool) {
+        let closing = self.activities.lock_ref()
+            .iter()
+            .enumerate()
+            .filter(|(index, activity)| {
+                activity.file().is_some() && !self.is_pinned(activity) && should_close(*index, activity)
+            })
+            .map(|(_, activity)| activity.clone())
+            .collect::<Vec<_>>();
+        for activity in &closing {
+            self.request_close(activity);
+        }
+    }
+
+    fn position(&self, activity: &Rc<Activity>) -> Option<usize> {
+        self.activities.lock_ref().iter().position(|candidate| Rc::ptr_eq(candidate, activity))
+    }
+
+    fn is_pinned(&self, activity: &Rc<Activity>) -> bool {
+        self.pinned.lock_ref().iter().any(|pinned| Rc::ptr_eq(pinned, activity))
+    }
+
+    fn is_pinned_signal(&self, activity: &Rc<Activity>) -> impl Signal<Item = bool> {
+        self.pinned.signal_ref(clone!(activity => move |pinned| {
+            pinned.iter().any(|pinned| Rc::ptr_eq(pinned, &activity))
+        }))
+    }
+
+    // moves the activity to the end of the pinned tabs or to the first tab after them
+    fn set_pinned(&self, activity: &Rc<Activity>, pinned: bool) {
+        if self.is_pinned(activity) == pinned {
+            return;
+        }
+        self.keep_open(activity);
+        let Some(index) = self.position(activity) else {
+            return;
+        };
+        let mut pinned_activities = self.pinned.lock_mut();
+        let mut activities = self.activities.lock_mut();
+        activities.remove(index);
+        match pinned {
+            true => pinned_activities.push(activity.clone()),
+            false => pinned_activities.retain(|candidate| !Rc::ptr_eq(candidate, activity)),
+        }
+        let index = pinned_activities.len() - usize::from(pinned);
+        activities.insert_cloned(index, activity.clone());
+    }
+
+    fn visit(&self, activity: &Rc<Activity>) {
+        let mut history = self.history.borrow_mut();
+        history.retain(|candidate| !Rc::ptr_eq(candidate, activity));
+        history.insert(0, activity.clone());
+    }
+
+    fn render(
         this: &Rc<ActivityPanel>,
-        workspace_command_rx: crate::WorkspaceCommandReceiver,
+        groups: &Rc<EditorGroups>,
+        sidebar_command_tx: &crate::SidebarCommandSender,
         width: impl Signal<Item = u32> + 'static,
         height: impl Signal<Item = u32> + 'static
     ) -> dominator::Dom {
@@ -147,26 +488,53 @@ impl ActivityPanel {
         html!("div", {
             .apply(styles::default_layout)
             .class("grid-rows-[auto_1fr]")
+            .class("relative")
 
-            .future(workspace_command_rx.for_each(clone!(this => move |command| clone!(this => async move {
-                match command {
-                    crate::WorkspaceCommand::OpenFile(file) => {
-                        let mut activities = this.activities.lock_mut();
-                        let editor = activities.iter()
-                            .find(|activity| match &***activity {
-                                Activity::Editor(editor) => Rc::ptr_eq(&editor.file, &file),
-                                _ => false,
-                            })
-                            .cloned()
-                            .unwrap_or_else(move || {
-                                let editor = Rc::new(Activity::Editor(Rc::new(editor::Editor::new(file))));
-                                activities.push_cloned(editor.clone());
-                                editor
-                            });
-                        this.active_activity.set(Some(editor));
-                    },
+            // editing the file in the preview tab keeps it open
+            .future(this.preview.signal_cloned()
+                .switch(|preview| -> Pin<Box<dyn Signal<Item = bool>>> {
+                    match preview.as_deref() {
+                        Some(Activity::Editor(editor)) => Box::pin(editor.edited.signal()),
+                        _ => Box::pin(signal::always(false)),
+                    }
+                })
+                .for_each(clone!(this => move |edited| {
+                    if edited {
+                        this.preview.set(None);
+                    }
+                    async {}
+                })))
+            .future(this.active_activity.signal_cloned().for_each(clone!(this => move |activity| {
+                if let Some(activity) = activity {
+                    this.visit(&activity);
                 }
-            }))))
+                async {}
+            })))
+            .child_signal(this.switcher.signal_cloned().map(clone!(this => move |switcher| {
+                switcher.map(|switcher| switcher::render(&this, &switcher))
+            })))
+            // only the active group switches between its activities
+            .global_event_with_options(&EventOptions::preventable(), clone!(this, groups => move |event: events::KeyDown| {
+                if !groups.is_active(&this) {
+                    return;
+                }
+                match event.key().as_str() {
+                    "Tab" if event.ctrl_key() => {
+                        event.prevent_default();
+                        switcher::step(&this, !event.shift_key());
+                    }
+                    "Escape" => this.switcher.set(None),
+                    _ => {}
+                }
+            }))
+            .global_event(clone!(this, groups => move |event: events::KeyUp| {
+                if event.key() == "Control" && groups.is_active(&this) {
+                    switcher::commit(&this);
+                }
+            }))
+            .child_signal(this.tab_menu.signal_cloned().map(clone!(this, groups, sidebar_command_tx => move |tab_menu| {
+                tab_menu.map(|tab_menu| Self::render_tab_menu(&this, &groups, &tab_menu, &sidebar_command_tx))
+            })))
             // this takes up the full height but should only display when there are no activities
             // and hence no tab bar
             .child_signal(activity_count.signal().map(clone!(height => move |count| {
@@ -175,28 +543,225 @@ impl ActivityPanel {
             // tabs take up one full line
             .child(html!("div", {
                 .apply(styles::tab::bar)
-                .children_signal_vec(this.activities.signal_vec_cloned().map(clone!(this => move |activity| {
-                    html!("div", {
-                        .class("h-full")
-                        .child(Activity::render_tab(&activity, &this))
+                // the explorer opens files in the active group, the others are dimmed
+                .class_signal("opacity-60", signal::not(groups.is_active_signal(&this)))
+                .style_signal("width", width.signal_ref(|width| format!("{width}px")))
+                // dropping a tab behind the last one moves it to the end
+                .event_with_options(&EventOptions::preventable(), |event: events::DragOver| {
+                    event.prevent_default(); // Allow drop
+                })
+                .event_with_options(&EventOptions::preventable(), clone!(this, groups => move |event: events::Drop| {
+                    event.prevent_default();
+                    groups.drop_tab(&this, None);
+                }))
+                .child(html!("div" => web_sys::HtmlElement, {
+                    .apply(styles::tab::tabs)
+                    // the wheel scrolls the tabs sideways
+                    .with_node!(element => {
+                        .event_with_options(&EventOptions::preventable(), move |event: events::Wheel| {
+                            if event.delta_x() == 0.0 {
+                                event.prevent_default();
+                                element.set_scroll_left(element.scroll_left() + event.delta_y() as i32);
+                            }
+                        })
                     })
-                })))
+                    .children_signal_vec(this.activities.signal_vec_cloned().map(clone!(this, groups => move |activity| {
+                        html!("div", {
+                            .class("h-full")
+                            .child(Activity::render_tab(&activity, &this, &groups))
+                        })
+                    })))
+                }))
+                .child(Self::render_tab_list_button(&this))
             }))
+            .child_signal(this.closing.signal_ref(clone!(this => move |closing| {
+                closing.first().map(|activity| Self::render_close_prompt(&this, activity))
+            })))
+            .child_signal(this.tab_list.signal().map(clone!(this, groups => move |position| {
+                position.map(|position| Self::render_tab_list(&this, &groups, position))
+            })))
             .child_signal(this.active_activity
                 .signal_cloned()
-                .map(move |activity: Option<Rc<Activity>>| activity
-                    .map(clone!(width, height => move |activity| html!("div", {
-                        .class("h-full")
-                        .child_signal(Activity::render(
-                            &activity,
-                            width.signal(),
-                            height.signal_ref(|height| height.saturating_sub(TAB_HEIGHT))))
-                    })))
-                )
+                .map(clone!(this => move |activity: Option<Rc<Activity>>| activity
+                    .map(clone!(this, width, height => move |activity| {
+                        // editors show the path of the file and the symbols at the cursor above and
+                        // the position of the cursor and the language below
+                        let (breadcrumbs, status_bar) = match &*activity {
+                            Activity::Editor(editor) => (
+                                Some(breadcrumbs::render(&this, editor)),
+                                Some(status_bar::render(editor)),
+                            ),
+                            Activity::Welcome(_) => (None, None),
+                        };
+                        let offset = TAB_HEIGHT + match breadcrumbs {
+                            Some(_) => breadcrumbs::BREADCRUMBS_HEIGHT + status_bar::STATUS_BAR_HEIGHT,
+                            None => 0,
+                        };
+                        html!("div", {
+                            .class("h-full")
+                            .children(breadcrumbs)
+                            .child_signal(Activity::render(
+                                &activity,
+                                width.signal(),
+                                height.signal_ref(move |height| height.saturating_sub(offset))))
+                            .children(status_bar)
+                        })
+                    }))
+                ))
             )
         })
     }
 
+    fn render_tab_menu(
+        this: &Rc<Self>,
+        groups: &Rc<EditorGroups>,
+        tab_menu: &TabMenu,
+        sidebar_command_tx: &crate::SidebarCommandSender
+    ) -> Dom {
+        let activity = &tab_menu.activity;
+        let is_last = this.position(activity).is_some_and(|index| index + 1 == this.activities.lock_ref().len());
+        let is_pinned = this.is_pinned(activity);
+        let entries = vec![
+            Entry::Item(Item::new("Close", clone!(this, activity => move || this.request_close(&activity)))),
+            Entry::Item(Item::new("Close Others", clone!(this, activity => move || {
+                this.close_unpinned(|_, other| !Rc::ptr_eq(other, &activity));
+            }))),
+            Entry::Item(Item::new("Close to the Right", clone!(this, activity => move || {
+                let position = this.position(&activity);
+                this.close_unpinned(|index, _| position.is_some_and(|position| index > position));
+            })).enabled(!is_last)),
+            Entry::Item(Item::new("Close Saved", clone!(this => move || {
+                this.close_unpinned(|_, other| !other.is_dirty());
+            }))),
+            Entry::Item(Item::new("Close All", clone!(this => move || this.close_unpinned(|_, _| true)))),
+            Entry::Separator,
+            Entry::Item(Item::new("Split Right", clone!(this, groups, activity => move || {
+                groups.split(&this, &activity, Orientation::Horizontal);
+            }))),
+            Entry::Item(Item::new("Split Down", clone!(this, groups, activity => move || {
+                groups.split(&this, &activity, Orientation::Vertical);
+            }))),
+            Entry::Separator,
+            Entry::Item(Item::new("Copy Path", clone!(activity => move || {
+                if let Some(file) = activity.file() {
+                    crate::PROJECT.with(|root| clipboard::copy_path(&Target::File(file.clone()), root, false));
+                }
+            }))),
+            Entry::Item(Item::new("Reveal in Explorer", clone!(activity, sidebar_command_tx => move || {
+                if let Some(file) = activity.file() {
+                    sidebar_command_tx
+                        .unbounded_send(crate::SidebarCommand::RevealFile(file.clone()))
+                        .unwrap();
+                }
+            }))),
+            Entry::Separator,
+            Entry::Item(Item::new(if is_pinned { "Unpin Tab" } else { "Pin Tab" }, clone!(this, activity => move || {
+                this.set_pinned(&activity, !is_pinned);
+            }))),
+        ];
+        menu::render(entries, tab_menu.position, Rc::new(clone!(this => move || this.tab_menu.set(None))))
+    }
+
+    fn render_tab_list_button(this: &Rc<Self>) -> Dom {
+        const ICON_PATH: &str = "M16,12A2,2 0 0,1 18,10A2,2 0 0,1 20,12A2,2 0 0,1 18,14A2,2 0 0,1 16,12M10,\
+            12A2,2 0 0,1 12,10A2,2 0 0,1 14,12A2,2 0 0,1 12,14A2,2 0 0,1 10,12M4,12A2,2 0 0,1 6,10A2,2 0 0,\
+            1 8,12A2,2 0 0,1 6,14A2,2 0 0,1 4,12Z";
+        html!("div" => web_sys::HtmlElement, {
+            .apply(styles::icon)
+            .class("cursor-pointer")
+            .attr("title", "Open Editors and Settings")
+            .with_node!(element => {
+                .event(clone!(this => move |event: events::PointerDown| {
+                    event.stop_propagation();
+                    let rect = element.get_bounding_client_rect();
+                    this.tab_list.set(Some((rect.left() as i32, rect.bottom() as i32)));
+                }))
+            })
+            .child(svg!("svg", {
+                .attr("height", "1em")
+                .attr("viewBox", "0 0 24 24")
+                .child(svg!("path", {
+                    .attr("d", ICON_PATH)
+                }))
+            }))
+        })
+    }
+
+    // every tab, including those scrolled out of view, and the editor settings
+    fn render_tab_list(this: &Rc<Self>, groups: &Rc<EditorGroups>, position: (i32, i32)) -> Dom {
+        let mut entries = this.activities.lock_ref()
+            .iter()
+            .map(|activity| Entry::Item(Item::new(activity.title(), clone!(this, activity => move || {
+                this.active_activity.set(Some(activity.clone()));
+            }))))
+            .collect::<Vec<_>>();
+        if let Some(activity) = this.active_activity.get_cloned().filter(|activity| activity.file().is_some()) {
+            entries.push(Entry::Separator);
+            entries.push(Entry::Item(Item::new("Split Right", clone!(this, groups, activity => move || {
+                groups.split(&this, &activity, Orientation::Horizontal);
+            }))));
+            entries.push(Entry::Item(Item::new("Split Down", clone!(this, groups, activity => move || {
+                groups.split(&this, &activity, Orientation::Vertical);
+            }))));
+        }
+        if groups.count() > 1 {
+            entries.push(Entry::Item(Item::new("Flip Layout", clone!(groups => move || groups.flip_orientation()))));
+        }
+        let current = SETTINGS.with(|settings| settings.editor_autosave.get());
+        entries.push(Entry::Separator);
+        entries.push(Entry::Item(Item::submenu("Auto Save", Autosave::iter()
+            .map(|autosave| Entry::Item(Item::new(autosave.label(), move || {
+                SETTINGS.with(|settings| settings.set_editor_autosave(autosave));
+            }).checked(autosave == current)))
+            .collect())));
+        entries.push(Entry::Item(Item::submenu("Editor Settings", settings_menu::entries(None))));
+        if let Some(Activity::Editor(editor)) = this.active_activity.get_cloned().as_deref() {
+            let language = editor.current_language();
+            entries.push(Entry::Item(Item::submenu(
+                format!("{} Editor Settings", language.label()),
+                settings_menu::entries(Some(language))
+            )));
+        }
+        menu::render(entries, position, Rc::new(clone!(this => move || this.tab_list.set(None))))
+    }
+
+    fn render_close_prompt(this: &Rc<Self>, activity: &Rc<Activity>) -> Dom {
+        let button = |label: &str, primary: bool, choice: CloseChoice| html!("button", {
+            .apply(|dom| styles::dialog::button(dom, primary))
+            .text(label)
+            .event(clone!(this => move |_: events::Click| this.resolve_close(choice)))
+        });
+        html!("div", {
+            .apply(styles::dialog::overlay)
+            .child(html!("div", {
+                .apply(styles::dialog::body)
+                .attr("tabindex", "-1")
+                .focused(true)
+                .event(clone!(this => move |event: events::KeyDown| {
+                    match event.key().as_str() {
+                        "Enter" => this.resolve_close(CloseChoice::Save),
+                        "Escape" => this.resolve_close(CloseChoice::Cancel),
+                        _ => {}
+                    }
+                }))
+                .child(html!("div", {
+                    .class("font-semibold")
+                    .text(&format!("Do you want to save the changes you made to {}?", activity.title()))
+                }))
+                .child(html!("div", {
+                    .class("text-[0.85rem]")
+                    .text("Your changes will be lost if you don't save them.")
+                }))
+                .child(html!("div", {
+                    .apply(styles::dialog::buttons)
+                    .child(button("Save", true, CloseChoice::Save))
+                    .child(button("Don't Save", false, CloseChoice::Discard))
+                    .child(button("Cancel", false, CloseChoice::Cancel))
+                }))
+            }))
+        })
+    }
+
     fn render_background(
         height: impl Signal<Item = u32> + 'static
     ) -> Dom {
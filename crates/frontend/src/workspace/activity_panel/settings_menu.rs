@@ -0,0 +1,112 @@
+// the editor settings in the menu of the tab bar, either for all languages or as overrides for the
+// language of the active editor
+use std::{fmt, str::FromStr};
+
+use dominator::clone;
+use strum::IntoEnumIterator;
+
+use crate::{contextmenu::menu::{Entry, Item}, filetype::Language, settings::{LineNumbers, RenderWhitespace, Rulers, Setting, Settings, SETTINGS}};
+
+const FONT_FAMILIES: [(&str, &str); 5] = [
+    ("Monospace", "monospace"),
+    ("Consolas", "Consolas, monospace"),
+    ("Courier New", "'Courier New', monospace"),
+    ("Fira Code", "'Fira Code', monospace"),
+    ("Source Code Pro", "'Source Code Pro', monospace"),
+];
+
+const FONT_SIZES: [u32; 7] = [10, 12, 13, 14, 16, 18, 20];
+
+// the choices for a setting with the current value checked, an override can also be removed again
+fn setting_entries<T: Clone + PartialEq + FromStr + fmt::Display + 'static>(
+    select: fn(&Settings) -> &Setting<T>,
+    language: Option<Language>,
+    choices: Vec<(String, T)>
+) -> Vec<Entry> {
+    // without an override, none of the choices for the language is checked
+    let (current, explicit) = SETTINGS.with(|settings| {
+        let setting = select(settings);
+        match language {
+            Some(language) => (setting.get(language), setting.get_override(language).is_some()),
+            None => (setting.get_default(), true),
+        }
+    });
+    let mut entries = Vec::new();
+    if let Some(language) = language {
+        entries.push(Entry::Item(Item::new("Same as All Languages", move || {
+            SETTINGS.with(|settings| select(settings).set_override(language, None));
+        }).checked(!explicit)));
+        entries.push(Entry::Separator);
+    }
+    entries.extend(choices.into_iter().map(|(label, value)| {
+        let checked = explicit && value == current;
+        Entry::Item(Item::new(label, clone!(value => move || SETTINGS.with(|settings| match language {
+            Some(language) => select(settings).set_override(language, Some(value.clone())),
+            None => select(settings).set(value.clone()),
+        }))).checked(checked))
+    }));
+    entries
+}
+
+fn on_off() -> Vec<(String, bool)> {
+    vec![("On".to_owned(), true), ("Off".to_owned(), false)]
+}
+
+pub fn entries(language: Option<Language>) -> Vec<Entry> {
+    let submenu = |label: &str, entries: Vec<Entry>| Entry::Item(Item::submenu(label, entries));
+    vec![
+        submenu("Tab Size", setting_entries(
+            |settings| &settings.editor_tab_size,
+            language,
+            [2, 4, 8].into_iter().map(|size| (size.to_string(), size)).collect()
+        )),
+        submenu("Indent Using", setting_entries(
+            |settings| &settings.editor_insert_spaces,
+            language,
+            vec![("Spaces".to_owned(), true), ("Tabs".to_owned(), false)]
+        )),
+        submenu("Detect Indentation", setting_entries(
+            |settings| &settings.editor_detect_indentation,
+            language,
+            on_off()
+        )),
+        Entry::Separator,
+        submenu("Word Wrap", setting_entries(
+            |settings| &settings.editor_word_wrap,
+            language,
+            on_off()
+        )),
+        submenu("Rulers", setting_entries(
+            |settings| &settings.editor_rulers,
+            language,
+            vec![
+                ("None".to_owned(), Rulers(Vec::new())),
+                ("80".to_owned(), Rulers(vec![80])),
+                ("100".to_owned(), Rulers(vec![100])),
+                ("120".to_owned(), Rulers(vec![120])),
+                ("80 and 120".to_owned(), Rulers(vec![80, 120])),
+            ]
+        )),
+        submenu("Render Whitespace", setting_entries(
+            |settings| &settings.editor_render_whitespace,
+            language,
+            RenderWhitespace::iter().map(|choice| (choice.label().to_owned(), choice)).collect()
+        )),
+        submenu("Line Numbers", setting_entries(
+            |settings| &settings.editor_line_numbers,
+            language,
+            LineNumbers::iter().map(|choice| (choice.label().to_owned(), choice)).collect()
+        )),
+        Entry::Separator,
+        submenu("Font Family", setting_entries(
+            |settings| &settings.editor_font_family,
+            language,
+            FONT_FAMILIES.iter().map(|(label, family)| (label.to_string(), family.to_string())).collect()
+        )),
+        submenu("Font Size", setting_entries(
+            |settings| &settings.editor_font_size,
+            language,
+            FONT_SIZES.into_iter().map(|size| (size.to_string(), size)).collect()
+        )),
+    ]
+}
@@ -0,0 +1,119 @@
+use std::rc::Rc;
+
+use dominator::{clone, events, html, with_node, Dom};
+use futures_signals::signal::{Mutable, SignalExt};
+use strum::IntoEnumIterator;
+
+use crate::{contextmenu::menu::{self, Entry, Item}, filetype::Language, settings::EditorOptions, styles};
+
+use super::{editor::Editor, indentation::Indentation};
+
+pub const STATUS_BAR_HEIGHT: u32 = 22;
+
+// the languages to choose from, the current one is checked
+fn language_entries(editor: &Rc<Editor>, current: Language) -> Vec<Entry> {
+    let mut entries = vec![
+        Entry::Item(Item::new("Auto Detect", clone!(editor => move || editor.set_language(None)))),
+        Entry::Separator,
+    ];
+    entries.extend(Language::iter().map(|language| Entry::Item(Item::new(language.label(), clone!(editor => move || {
+        editor.set_language(Some(language));
+    })).checked(language == current))));
+    entries
+}
+
+const TAB_SIZES: [u32; 3] = [2, 4, 8];
+
+// the indentation of this editor, the current choice is checked
+fn indentation_entries(editor: &Rc<Editor>, options: &EditorOptions) -> Vec<Entry> {
+    let tab_size = options.tab_size;
+    let mut entries = [(true, "Indent Using Spaces"), (false, "Indent Using Tabs")]
+        .into_iter()
+        .map(|(insert_spaces, label)| Entry::Item(Item::new(label, clone!(editor => move || {
+            editor.set_indentation(Some(Indentation { insert_spaces, tab_size: Some(tab_size) }));
+        })).checked(insert_spaces == options.insert_spaces)))
+        .collect::<Vec<_>>();
+    entries.push(Entry::Separator);
+    let insert_spaces = options.insert_spaces;
+    entries.extend(TAB_SIZES.into_iter().map(|size| Entry::Item(Item::new(format!("Tab Size: {size}"), clone!(editor => move || {
+        editor.set_indentation(Some(Indentation { insert_spaces, tab_size: Some(size) }));
+    })).checked(size == tab_size))));
+    entries.push(Entry::Separator);
+    entries.push(Entry::Item(Item::new("Reset to Settings", clone!(editor => move || {
+        editor.set_indentation(None);
+    }))));
+    entries
+}
+
+fn indentation_label(options: &EditorOptions) -> String {
+    match options.insert_spaces {
+        true => format!("Spaces: {}", options.tab_size),
+        false => format!("Tab Size: {}", options.tab_size),
+    }
+}
+
+// opens the menu above the item
+fn open_at(element: &web_sys::HtmlElement, picker: &Mutable<Option<(i32, i32)>>) {
+    let rect = element.get_bounding_client_rect();
+    picker.set(Some((rect.left() as i32, rect.top() as i32)));
+}
+
+fn render_language(language: &Mutable<Language>, picker: &Mutable<Option<(i32, i32)>>) -> Dom {
+    html!("div", {
+        .apply(styles::status_bar::item)
+        .attr("title", "Select Language Mode")
+        .text_signal(language.signal_ref(|language| language.label()))
+        .with_node!(element => {
+            .event(clone!(picker => move |_: events::Click| open_at(&element, &picker)))
+        })
+    })
+}
+
+pub fn render(editor: &Rc<Editor>) -> Dom {
+    let language = Mutable::new(Language::PlainText);
+    let options = Mutable::new(None);
+    let picker = Mutable::new(None);
+    let indentation_picker = Mutable::new(None);
+    html!("div", {
+        .apply(styles::status_bar::bar)
+        .future(editor.language().for_each(clone!(language => move |current| {
+            language.set_neq(current);
+            async {}
+        })))
+        .future(editor.options().for_each(clone!(options => move |current| {
+            options.set(Some(current));
+            async {}
+        })))
+        .child(html!("div", {
+            .apply(styles::status_bar::item)
+            .text_signal(editor.cursor.signal().map(clone!(editor => move |cursor| {
+                let (line, column) = editor.line_column(cursor);
+                format!("Ln {line}, Col {column}")
+            })))
+        }))
+        .child(html!("div", {
+            .apply(styles::status_bar::item)
+            .attr("title", "Select Indentation")
+            .text_signal(options.signal_ref(|options| options.as_ref().map(indentation_label).unwrap_or_default()))
+            .with_node!(element => {
+                .event(clone!(indentation_picker => move |_: events::Click| open_at(&element, &indentation_picker)))
+            })
+        }))
+        .child(render_language(&language, &picker))
+        .child_signal(indentation_picker.signal().map(clone!(editor, options, indentation_picker => move |position| {
+            let options = options.get_cloned()?;
+            position.map(|position| menu::render(
+                indentation_entries(&editor, &options),
+                position,
+                Rc::new(clone!(indentation_picker => move || indentation_picker.set(None)))
+            ))
+        })))
+        .child_signal(picker.signal().map(clone!(editor, picker => move |position| {
+            position.map(|position| menu::render(
+                language_entries(&editor, language.get()),
+                position,
+                Rc::new(clone!(picker => move || picker.set(None)))
+            ))
+        })))
+    })
+}
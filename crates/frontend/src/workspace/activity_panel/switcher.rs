@@ -0,0 +1,101 @@
+// Ctrl+Tab lists the open activities in the order in which they were last viewed, pressing Tab
+// again while holding Ctrl moves through the list and releasing Ctrl activates the selection
+use std::rc::Rc;
+
+use dominator::{clone, events, html, Dom};
+use futures_signals::signal::Mutable;
+
+use crate::styles;
+
+use super::{Activity, ActivityPanel};
+
+pub struct Switcher {
+    activities: Vec<Rc<Activity>>,
+    selected: Mutable<usize>,
+}
+
+// the open activities, most recently viewed first, activities that were never viewed come last
+fn recently_viewed(panel: &ActivityPanel) -> Vec<Rc<Activity>> {
+    let activities = panel.activities.lock_ref();
+    let history = panel.history.borrow();
+    let mut ordered = history.iter()
+        .filter(|viewed| activities.iter().any(|activity| Rc::ptr_eq(activity, viewed)))
+        .cloned()
+        .collect::<Vec<_>>();
+    for activity in activities.iter() {
+        if !ordered.iter().any(|viewed| Rc::ptr_eq(viewed, activity)) {
+            ordered.push(activity.clone());
+        }
+    }
+    ordered
+}
+
+// opens the switcher on the activity viewed before the active one, or moves the selection
+pub fn step(panel: &ActivityPanel, forward: bool) {
+    let mut switcher = panel.switcher.lock_mut();
+    match &*switcher {
+        Some(switcher) => {
+            let count = switcher.activities.len();
+            let selected = switcher.selected.get();
+            switcher.selected.set(match forward {
+                true => (selected + 1) % count,
+                false => (selected + count - 1) % count,
+            });
+        }
+        None => {
+            let activities = recently_viewed(panel);
+            if activities.len() < 2 {
+                return;
+            }
+            let selected = match forward {
+                true => 1,
+                false => activities.len() - 1,
+            };
+            *switcher = Some(Rc::new(Switcher {
+                activities,
+                selected: Mutable::new(selected),
+            }));
+        }
+    }
+}
+
+// activates the selection and closes the switcher
+pub fn commit(panel: &ActivityPanel) {
+    if let Some(switcher) = panel.switcher.replace(None) {
+        let activity = switcher.activities[switcher.selected.get()].clone();
+        panel.active_activity.set(Some(activity));
+    }
+}
+
+pub fn render(panel: &Rc<ActivityPanel>, switcher: &Rc<Switcher>) -> Dom {
+    html!("div", {
+        .apply(styles::switcher::body)
+        .child(html!("div", {
+            .apply(styles::switcher::title)
+            .text("Recently Viewed")
+        }))
+        .children(switcher.activities.iter().enumerate().map(|(index, activity)| html!("div", {
+            .apply(|dom| styles::contextmenu::item(
+                dom,
+                switcher.selected.signal_ref(move |selected| *selected == index),
+                true
+            ))
+            .child(html!("div", {
+                .apply(styles::icon_text)
+                .class("inline-flex")
+                .child(html!("div", {
+                    .apply(styles::icon)
+                    .child_signal(activity.icon())
+                }))
+                .child(activity.label())
+            }))
+            .event(clone!(switcher => move |_: events::PointerEnter| {
+                switcher.selected.set_neq(index);
+            }))
+            .event(clone!(panel => move |event: events::Click| {
+                event.stop_propagation();
+                commit(&panel);
+            }))
+        })))
+    })
+}
@@ -0,0 +1,64 @@
+// the selection, scroll offset and folded ranges of an editor, kept per file so that they come
+// back when the file is opened again after its tabs were closed
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{contextmenu::Target, vfs::File};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ViewState {
+    // positions are in UTF-16 code units like in CodeMirror
+    pub anchor: usize,
+    pub head: usize,
+    pub scroll_top: i32,
+    pub folds: Vec<(usize, usize)>,
+}
+
+impl ViewState {
+    // drops what no longer fits, e.g., after the file was shortened elsewhere
+    pub fn clamp(mut self, length: usize) -> ViewState {
+        self.anchor = self.anchor.min(length);
+        self.head = self.head.min(length);
+        self.folds.retain(|(from, to)| from < to && *to <= length);
+        self
+    }
+}
+
+thread_local! {
+    // keyed by the path of the file, so moving or renaming a file forgets its view state
+    static VIEW_STATES: RefCell<HashMap<String, ViewState>> = RefCell::new(HashMap::new());
+}
+
+fn path(file: &Rc<File>) -> Option<String> {
+    crate::PROJECT.with(|root| Target::File(file.clone()).path(root))
+}
+
+pub fn load(file: &Rc<File>) -> Option<ViewState> {
+    let path = path(file)?;
+    VIEW_STATES.with(|view_states| view_states.borrow().get(&path).cloned())
+}
+
+pub fn store(file: &Rc<File>, view_state: &ViewState) {
+    if let Some(path) = path(file) {
+        VIEW_STATES.with(|view_states| view_states.borrow_mut().insert(path, view_state.clone()));
+    }
+}
+
+// removes the view states of the file or of the files in the folder before it is moved or renamed,
+// afterwards they could not be found anymore
+pub fn forget(target: &Target) {
+    let Some(path) = crate::PROJECT.with(|root| target.path(root)) else {
+        return;
+    };
+    VIEW_STATES.with(|view_states| {
+        let mut view_states = view_states.borrow_mut();
+        match target {
+            Target::File(_) => {
+                view_states.remove(&path);
+            }
+            Target::Directory(_) => {
+                let prefix = format!("{path}/");
+                view_states.retain(|key, _| !key.starts_with(&prefix));
+            }
+        }
+    });
+}
@@ -12,7 +12,7 @@ const DEFAULT_CONSOLE_HEIGHT: u32 = 200;
 const RESIZER_PX: u32 = 3;
 
 pub struct Workspace {
-    activity_panel: Rc<activity_panel::ActivityPanel>,
+    editor_groups: Rc<activity_panel::groups::EditorGroups>,
     console: Rc<console::Console>,
     console_height: Mutable<u32>,
     resize_active: Mutable<bool>,
@@ -22,7 +22,7 @@ pub struct Workspace {
 impl Default for Workspace {
     fn default() -> Self {
         Self {
-            activity_panel: Default::default(),
+            editor_groups: Default::default(),
             console: Default::default(),
             console_height: Mutable::new(DEFAULT_CONSOLE_HEIGHT),
             resize_active: Mutable::new(false),
@@ -36,10 +36,11 @@ impl Workspace {
     pub fn render(
         this: &Rc<Workspace>,
         workspace_command_rx: crate::WorkspaceCommandReceiver,
+        sidebar_command_tx: &crate::SidebarCommandSender,
         width: impl Signal<Item = u32> + 'static,
         height: impl Signal<Item = u32> + 'static
     ) -> Dom {
-        use activity_panel::ActivityPanel;
+        use activity_panel::groups::EditorGroups;
 
         let console_height = this.console_height.signal();
         let activity_panel_height = 
@@ -50,7 +51,7 @@ impl Workspace {
             .class("grid-rows-[1fr_auto_auto]")
 
             // activity area
-            .child(ActivityPanel::render(&this.activity_panel, workspace_command_rx, width, activity_panel_height))
+            .child(EditorGroups::render(&this.editor_groups, workspace_command_rx, sidebar_command_tx, width, activity_panel_height))
 
             // resizer
             .child(html!("div", {
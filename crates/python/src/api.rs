@@ -0,0 +1,516 @@
+// the parts of rclpy that the simulator supports and the message types that it provides, which the
+// editor uses for completions, signature help and hover documentation
+pub struct Module {
+    // dotted, e.g., `rclpy.node`
+    pub name: &'static str,
+    pub description: &'static str,
+    pub functions: &'static [Function],
+    pub classes: &'static [Class],
+}
+
+pub struct Class {
+    pub name: &'static str,
+    pub description: &'static str,
+    // the parameters of the constructor
+    pub parameters: &'static [Parameter],
+    pub methods: &'static [Function],
+    pub attributes: &'static [Attribute],
+}
+
+pub struct Function {
+    pub name: &'static str,
+    pub description: &'static str,
+    // without `self` for methods
+    pub parameters: &'static [Parameter],
+    // a class of the API with its module, e.g., `rclpy.timer.Timer`, or a builtin type
+    pub returns: &'static str,
+}
+
+pub struct Parameter {
+    pub name: &'static str,
+    pub annotation: &'static str,
+    pub default: Option<&'static str>,
+    pub description: &'static str,
+}
+
+pub struct Attribute {
+    pub name: &'static str,
+    // like the return type of a function
+    pub annotation: &'static str,
+    pub description: &'static str,
+}
+
+const NODE: Class = Class {
+    name: "Node",
+    description: "A participant in the communication, which publishes and subscribes to topics and runs timers. Nodes are usually classes that derive from it.",
+    parameters: &[
+        Parameter {
+            name: "node_name",
+            annotation: "str",
+            default: None,
+            description: "The name of the node, which the `name` attribute of the node in the launch file replaces",
+        },
+    ],
+    methods: &[
+        Function {
+            name: "get_name",
+            description: "The name of the node",
+            parameters: &[],
+            returns: "str",
+        },
+        Function {
+            name: "get_logger",
+            description: "The logger of the node, whose messages are shown in the console",
+            parameters: &[],
+            returns: "rclpy.impl.rcutils_logger.RcutilsLogger",
+        },
+        Function {
+            name: "create_publisher",
+            description: "Creates a publisher, which sends messages on a topic",
+            parameters: &[
+                Parameter {
+                    name: "msg_type",
+                    annotation: "type",
+                    default: None,
+                    description: "The message type, e.g., `Velocity`",
+                },
+                Parameter {
+                    name: "topic",
+                    annotation: "str",
+                    default: None,
+                    description: "The name of the topic",
+                },
+                Parameter {
+                    name: "qos_profile",
+                    annotation: "int",
+                    default: None,
+                    description: "The number of messages that are kept until they are delivered",
+                },
+            ],
+            returns: "rclpy.publisher.Publisher",
+        },
+        Function {
+            name: "create_subscription",
+            description: "Creates a subscription, which calls the callback with each message that is received on a topic",
+            parameters: &[
+                Parameter {
+                    name: "msg_type",
+                    annotation: "type",
+                    default: None,
+                    description: "The message type, e.g., `Velocity`",
+                },
+                Parameter {
+                    name: "topic",
+                    annotation: "str",
+                    default: None,
+                    description: "The name of the topic",
+                },
+                Parameter {
+                    name: "callback",
+                    annotation: "Callable",
+                    default: None,
+                    description: "The function that is called with each message",
+                },
+                Parameter {
+                    name: "qos_profile",
+                    annotation: "int",
+                    default: None,
+                    description: "The number of messages that are kept until the callback is called",
+                },
+            ],
+            returns: "rclpy.subscription.Subscription",
+        },
+        Function {
+            name: "create_timer",
+            description: "Creates a timer, which calls the callback periodically while the node spins",
+            parameters: &[
+                Parameter {
+                    name: "timer_period_sec",
+                    annotation: "float",
+                    default: None,
+                    description: "The period in seconds",
+                },
+                Parameter {
+                    name: "callback",
+                    annotation: "Callable",
+                    default: None,
+                    description: "The function that is called without arguments",
+                },
+            ],
+            returns: "rclpy.timer.Timer",
+        },
+        Function {
+            name: "destroy_publisher",
+            description: "Destroys a publisher of the node, returns whether it was found",
+            parameters: &[
+                Parameter {
+                    name: "publisher",
+                    annotation: "Publisher",
+                    default: None,
+                    description: "The publisher",
+                },
+            ],
+            returns: "bool",
+        },
+        Function {
+            name: "destroy_subscription",
+            description: "Destroys a subscription of the node, returns whether it was found",
+            parameters: &[
+                Parameter {
+                    name: "subscription",
+                    annotation: "Subscription",
+                    default: None,
+                    description: "The subscription",
+                },
+            ],
+            returns: "bool",
+        },
+        Function {
+            name: "destroy_timer",
+            description: "Destroys a timer of the node, returns whether it was found",
+            parameters: &[
+                Parameter {
+                    name: "timer",
+                    annotation: "Timer",
+                    default: None,
+                    description: "The timer",
+                },
+            ],
+            returns: "bool",
+        },
+        Function {
+            name: "destroy_node",
+            description: "Destroys the node together with its publishers, subscriptions and timers",
+            parameters: &[],
+            returns: "None",
+        },
+    ],
+    attributes: &[],
+};
+
+const PUBLISHER: Class = Class {
+    name: "Publisher",
+    description: "Sends messages on a topic, created with `Node.create_publisher`",
+    parameters: &[],
+    methods: &[
+        Function {
+            name: "publish",
+            description: "Sends a message to the subscriptions of the topic",
+            parameters: &[
+                Parameter {
+                    name: "msg",
+                    annotation: "object",
+                    default: None,
+                    description: "The message, whose type is the message type of the publisher",
+                },
+            ],
+            returns: "None",
+        },
+    ],
+    attributes: &[
+        Attribute {
+            name: "topic_name",
+            annotation: "str",
+            description: "The name of the topic",
+        },
+    ],
+};
+
+const SUBSCRIPTION: Class = Class {
+    name: "Subscription",
+    description: "Receives the messages on a topic, created with `Node.create_subscription`",
+    parameters: &[],
+    methods: &[],
+    attributes: &[
+        Attribute {
+            name: "topic_name",
+            annotation: "str",
+            description: "The name of the topic",
+        },
+    ],
+};
+
+const TIMER: Class = Class {
+    name: "Timer",
+    description: "Calls a callback periodically, created with `Node.create_timer`",
+    parameters: &[],
+    methods: &[
+        Function {
+            name: "cancel",
+            description: "Stops calling the callback",
+            parameters: &[],
+            returns: "None",
+        },
+        Function {
+            name: "reset",
+            description: "Starts the period again, also after the timer was canceled",
+            parameters: &[],
+            returns: "None",
+        },
+        Function {
+            name: "is_canceled",
+            description: "Whether the timer was canceled",
+            parameters: &[],
+            returns: "bool",
+        },
+    ],
+    attributes: &[
+        Attribute {
+            name: "timer_period_ns",
+            annotation: "int",
+            description: "The period in nanoseconds",
+        },
+    ],
+};
+
+const MESSAGE_PARAMETER: [Parameter; 1] = [
+    Parameter {
+        name: "message",
+        annotation: "str",
+        default: None,
+        description: "The message",
+    },
+];
+
+const LOGGER: Class = Class {
+    name: "RcutilsLogger",
+    description: "Writes messages with a severity to the console, returned by `Node.get_logger`",
+    parameters: &[],
+    methods: &[
+        Function {
+            name: "debug",
+            description: "Logs a message with the severity DEBUG, returns whether it was logged",
+            parameters: &MESSAGE_PARAMETER,
+            returns: "bool",
+        },
+        Function {
+            name: "info",
+            description: "Logs a message with the severity INFO, returns whether it was logged",
+            parameters: &MESSAGE_PARAMETER,
+            returns: "bool",
+        },
+        Function {
+            name: "warning",
+            description: "Logs a message with the severity WARN, returns whether it was logged",
+            parameters: &MESSAGE_PARAMETER,
+            returns: "bool",
+        },
+        Function {
+            name: "error",
+            description: "Logs a message with the severity ERROR, returns whether it was logged",
+            parameters: &MESSAGE_PARAMETER,
+            returns: "bool",
+        },
+        Function {
+            name: "fatal",
+            description: "Logs a message with the severity FATAL, returns whether it was logged",
+            parameters: &MESSAGE_PARAMETER,
+            returns: "bool",
+        },
+    ],
+    attributes: &[],
+};
+
+pub static MODULES: [Module; 7] = [
+    Module {
+        name: "rclpy",
+        description: "The ROS 2 client library for Python",
+        functions: &[
+            Function {
+                name: "init",
+                description: "Initializes the communication, which has to be done before nodes are created",
+                parameters: &[
+                    Parameter {
+                        name: "args",
+                        annotation: "list[str] | None",
+                        default: Some("None"),
+                        description: "The command line arguments",
+                    },
+                ],
+                returns: "None",
+            },
+            Function {
+                name: "shutdown",
+                description: "Shuts down the communication",
+                parameters: &[],
+                returns: "None",
+            },
+            Function {
+                name: "spin",
+                description: "Calls the callbacks of the node, e.g., of its timers and subscriptions, until the communication is shut down",
+                parameters: &[
+                    Parameter {
+                        name: "node",
+                        annotation: "Node",
+                        default: None,
+                        description: "The node",
+                    },
+                ],
+                returns: "None",
+            },
+            Function {
+                name: "spin_once",
+                description: "Calls the next callback of the node that is ready, waiting for one until the timeout",
+                parameters: &[
+                    Parameter {
+                        name: "node",
+                        annotation: "Node",
+                        default: None,
+                        description: "The node",
+                    },
+                    Parameter {
+                        name: "timeout_sec",
+                        annotation: "float | None",
+                        default: Some("None"),
+                        description: "The time to wait in seconds, forever if it is `None`",
+                    },
+                ],
+                returns: "None",
+            },
+            Function {
+                name: "ok",
+                description: "Whether the communication was initialized and has not been shut down",
+                parameters: &[],
+                returns: "bool",
+            },
+            Function {
+                name: "create_node",
+                description: "Creates a node without deriving a class from `Node`",
+                parameters: &[
+                    Parameter {
+                        name: "node_name",
+                        annotation: "str",
+                        default: None,
+                        description: "The name of the node",
+                    },
+                ],
+                returns: "rclpy.node.Node",
+            },
+        ],
+        classes: &[],
+    },
+    Module {
+        name: "rclpy.node",
+        description: "Nodes, which publish and subscribe to topics",
+        functions: &[],
+        classes: &[NODE],
+    },
+    Module {
+        name: "rclpy.publisher",
+        description: "Publishers, which send messages on topics",
+        functions: &[],
+        classes: &[PUBLISHER],
+    },
+    Module {
+        name: "rclpy.subscription",
+        description: "Subscriptions, which receive the messages on topics",
+        functions: &[],
+        classes: &[SUBSCRIPTION],
+    },
+    Module {
+        name: "rclpy.timer",
+        description: "Timers, which call callbacks periodically",
+        functions: &[],
+        classes: &[TIMER],
+    },
+    Module {
+        name: "rclpy.logging",
+        description: "Logging to the console",
+        functions: &[
+            Function {
+                name: "get_logger",
+                description: "The logger with the name, which is shown with its messages",
+                parameters: &[
+                    Parameter {
+                        name: "name",
+                        annotation: "str",
+                        default: None,
+                        description: "The name of the logger",
+                    },
+                ],
+                returns: "rclpy.impl.rcutils_logger.RcutilsLogger",
+            },
+        ],
+        classes: &[],
+    },
+    Module {
+        name: "rclpy.impl.rcutils_logger",
+        description: "The logger of nodes",
+        functions: &[],
+        classes: &[LOGGER],
+    },
+];
+
+pub fn module(name: &str) -> Option<&'static Module> {
+    MODULES.iter().find(|module| module.name == name)
+}
+
+// e.g., `rclpy.timer.Timer`
+pub fn class(path: &str) -> Option<&'static Class> {
+    let (module_name, class_name) = path.rsplit_once('.')?;
+    module(module_name)?.classes.iter().find(|class| class.name == class_name)
+}
+
+// a message type in the format of `.msg` files, where the comments before the first field describe
+// the message and those before a field or after it on the same line describe the field
+pub struct MessageDefinition<'a> {
+    pub package: &'a str,
+    pub name: &'a str,
+    pub text: &'a str,
+}
+
+const VELOCITY: &str = "\
+# The velocities of the wheels of a differential drive robot, e.g., the TurtleBot, positive
+# velocities drive the robot forwards
+float64 left  # The velocity of the left wheel
+float64 right  # The velocity of the right wheel
+";
+
+const STRING: &str = "\
+# A string
+string data
+";
+
+const BOOL: &str = "\
+# A boolean
+bool data
+";
+
+const INT32: &str = "\
+# A 32-bit integer
+int32 data
+";
+
+const INT64: &str = "\
+# A 64-bit integer
+int64 data
+";
+
+const FLOAT32: &str = "\
+# A 32-bit floating point number
+float32 data
+";
+
+const FLOAT64: &str = "\
+# A 64-bit floating point number
+float64 data
+";
+
+const EMPTY: &str = "\
+# A message without data, e.g., for events
+";
+
+pub static MESSAGES: [MessageDefinition<'static>; 12] = [
+    MessageDefinition { package: "example_interfaces", name: "Velocity", text: VELOCITY },
+    MessageDefinition { package: "example_interfaces", name: "String", text: STRING },
+    MessageDefinition { package: "example_interfaces", name: "Bool", text: BOOL },
+    MessageDefinition { package: "example_interfaces", name: "Int64", text: INT64 },
+    MessageDefinition { package: "example_interfaces", name: "Float64", text: FLOAT64 },
+    MessageDefinition { package: "std_msgs", name: "String", text: STRING },
+    MessageDefinition { package: "std_msgs", name: "Bool", text: BOOL },
+    MessageDefinition { package: "std_msgs", name: "Int32", text: INT32 },
+    MessageDefinition { package: "std_msgs", name: "Int64", text: INT64 },
+    MessageDefinition { package: "std_msgs", name: "Float32", text: FLOAT32 },
+    MessageDefinition { package: "std_msgs", name: "Float64", text: FLOAT64 },
+    MessageDefinition { package: "std_msgs", name: "Empty", text: EMPTY },
+];
@@ -0,0 +1,559 @@
+// completions, signature help and hover documentation in Python files, found by reading the tokens
+// around the cursor and inferring what the expression before it is, offsets are in bytes
+use std::ops::Range;
+
+use crate::{
+    ast::{ExprKind, Stmt, StmtKind},
+    infer::{Analysis, Library, Member, MemberKind, Module, Signature, SignatureParameterKind, Value},
+    parse,
+    tokens::{self, Token, TokenKind},
+};
+
+pub struct Completion {
+    pub label: String,
+    pub kind: MemberKind,
+    // the parameters of functions and classes, the type of attributes
+    pub detail: Option<String>,
+    pub info: String,
+}
+
+pub struct Completions {
+    // where the typed word starts
+    pub from: usize,
+    pub options: Vec<Completion>,
+}
+
+pub struct Hover {
+    pub range: Range<usize>,
+    // e.g., `def spin(node: Node) -> None`
+    pub signature: String,
+    pub description: String,
+}
+
+pub struct SignatureHelp {
+    pub signature: Signature,
+    // the parameter of the argument at the cursor
+    pub active: Option<usize>,
+    // the `(` of the call, where the help is shown
+    pub position: usize,
+}
+
+// the call whose arguments the cursor is in
+struct Call<'t> {
+    // the index of the `(` among the tokens
+    opening: usize,
+    // the index of the argument at the cursor
+    argument: usize,
+    // the keyword of the argument at the cursor
+    keyword: Option<&'t str>,
+    // the keywords of the arguments before it
+    keywords: Vec<&'t str>,
+}
+
+fn is_operator(token: &Token, text: &str) -> bool {
+    token.kind == TokenKind::Operator && token.text == text
+}
+
+fn is_logical_line_end(token: &Token) -> bool {
+    matches!(token.kind, TokenKind::Newline | TokenKind::Indent | TokenKind::Dedent)
+}
+
+// the last token of an atom, after which calls, subscripts and attributes may follow
+fn ends_atom(token: &Token) -> bool {
+    match token.kind {
+        TokenKind::Name => !tokens::is_keyword(token.text) || matches!(token.text, "None" | "True" | "False"),
+        TokenKind::Number | TokenKind::String => true,
+        TokenKind::Operator => matches!(token.text, ")" | "]"),
+        _ => false,
+    }
+}
+
+// the index of the bracket that the closing bracket at the index closes
+fn opening_bracket(tokens: &[Token], closing: usize) -> Option<usize> {
+    let mut depth = 0;
+    for index in (0..=closing).rev() {
+        if tokens[index].kind != TokenKind::Operator {
+            continue;
+        }
+        match tokens[index].text {
+            ")" | "]" | "}" => depth += 1,
+            "(" | "[" | "{" => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+// the index of the first token of the primary expression that ends with the token at the index,
+// e.g., of `self.get_logger().info`
+fn primary_start(tokens: &[Token], end: usize) -> Option<usize> {
+    let mut index = end;
+    loop {
+        // the calls and subscripts after the atom
+        while is_operator(&tokens[index], ")") || is_operator(&tokens[index], "]") {
+            let opening = opening_bracket(tokens, index)?;
+            match opening.checked_sub(1) {
+                Some(before) if ends_atom(&tokens[before]) => index = before,
+                // a parenthesized expression or a list
+                _ => {
+                    index = opening;
+                    break;
+                }
+            }
+        }
+        if !(ends_atom(&tokens[index]) || is_operator(&tokens[index], "(") || is_operator(&tokens[index], "[")) {
+            return None;
+        }
+        match index.checked_sub(2) {
+            Some(before) if is_operator(&tokens[index - 1], ".") && ends_atom(&tokens[before]) => index = before,
+            _ => return Some(index),
+        }
+    }
+}
+
+// the index of the first token of the logical line that the token at the index is in
+fn line_start(tokens: &[Token], index: usize) -> usize {
+    tokens[..index].iter().rposition(is_logical_line_end).map_or(0, |end| end + 1)
+}
+
+// e.g., `rclpy.node`
+fn dotted_name(tokens: &[Token]) -> Option<String> {
+    let dotted = !tokens.is_empty() && tokens.iter().enumerate().all(|(index, token)| match index % 2 {
+        0 => token.kind == TokenKind::Name,
+        _ => is_operator(token, "."),
+    });
+    (dotted && tokens.len() % 2 == 1).then(|| tokens.iter().map(|token| token.text).collect())
+}
+
+// the tokens before the position, without those that the tokenizer adds at the end of the text,
+// None if the position is in a string or in a comment
+fn tokens_before(text: &str, position: usize) -> Option<Vec<Token<'_>>> {
+    let (mut tokens, errors) = tokens::tokenize(&text[..position]);
+    while tokens.last().is_some_and(|token| token.range.is_empty()) {
+        tokens.pop();
+    }
+    let in_string = tokens.last().is_some_and(|token| {
+        token.kind == TokenKind::String
+            && token.range.end == position
+            && errors.iter().any(|error| error.range.start == token.range.start)
+    });
+    let end = tokens.last().map_or(0, |token| token.range.end);
+    let line_start = text[..position].rfind('\n').map_or(0, |newline| newline + 1);
+    let in_comment = text[end.max(line_start)..position].contains('#');
+    (!in_string && !in_comment).then_some(tokens)
+}
+
+// finds the `(` that is not closed before the end of the tokens
+fn open_call<'t>(tokens: &[Token<'t>]) -> Option<Call<'t>> {
+    let mut depth = 0;
+    let mut commas = Vec::new();
+    let mut index = tokens.len();
+    let opening = loop {
+        index = index.checked_sub(1)?;
+        let token = &tokens[index];
+        if is_logical_line_end(token) {
+            return None;
+        }
+        if token.kind != TokenKind::Operator {
+            continue;
+        }
+        match token.text {
+            ")" | "]" | "}" => depth += 1,
+            "(" if depth == 0 => break index,
+            // in a list, a subscript, a set or a dict
+            "[" | "{" if depth == 0 => return None,
+            "(" | "[" | "{" => depth -= 1,
+            "," if depth == 0 => commas.push(index),
+            _ => {}
+        }
+    };
+    // not a parenthesized expression or a tuple
+    if !opening.checked_sub(1).is_some_and(|before| ends_atom(&tokens[before])) {
+        return None;
+    }
+    let keyword = |start: usize| match tokens.get(start..start + 2) {
+        Some([name, equals]) if name.kind == TokenKind::Name && is_operator(equals, "=") => Some(name.text),
+        _ => None,
+    };
+    // the arguments start after the `(` and after the commas
+    let starts = std::iter::once(opening)
+        .chain(commas.iter().rev().copied())
+        .map(|index| index + 1)
+        .collect::<Vec<_>>();
+    let (current, before) = starts.split_last().unwrap();
+    Some(Call {
+        opening,
+        argument: commas.len(),
+        keyword: keyword(*current),
+        keywords: before.iter().filter_map(|start| keyword(*start)).collect(),
+    })
+}
+
+// the value of the primary expression that ends with the token at the index, e.g., of
+// `self.publisher_`
+fn evaluate<'a>(analysis: &Analysis<'a>, text: &str, tokens: &[Token], end: usize, position: usize) -> Option<Value<'a>> {
+    let start = primary_start(tokens, end)?;
+    let module = parse::parse(&text[tokens[start].range.start..tokens[end].range.end]);
+    match module.body.as_slice() {
+        [Stmt { kind: StmtKind::Expr(expr), .. }] if module.errors.is_empty() => analysis.value_of(expr, &analysis.scope(position)),
+        _ => None,
+    }
+}
+
+// the modules that can be imported after the tokens of an import, e.g., the submodules after
+// `import rclpy.`
+fn importable_modules<'a>(analysis: &Analysis<'a>, tokens: &[Token]) -> Option<Vec<Member<'a>>> {
+    let Some((dot, parent)) = tokens.split_last() else {
+        return Some(analysis.top_level_modules());
+    };
+    if !is_operator(dot, ".") {
+        return None;
+    }
+    let module = analysis.module(&dotted_name(parent)?)?;
+    let members = analysis.members(Value::Module(module)).into_iter()
+        .filter(|member| member.kind == MemberKind::Module)
+        .collect();
+    Some(members)
+}
+
+// what an import statement can import at the end of the tokens of its logical line
+fn importable<'a>(analysis: &Analysis<'a>, line: &[Token]) -> Option<Vec<Member<'a>>> {
+    let import = line.iter().position(|token| token.kind == TokenKind::Name && token.text == "import");
+    match (line[0].text, import) {
+        // `import rclpy.node, rclpy.`
+        ("import", _) => {
+            let start = line.iter().rposition(|token| is_operator(token, ",")).unwrap_or(0) + 1;
+            importable_modules(analysis, &line[start..])
+        }
+        // `from rclpy.`
+        (_, None) => importable_modules(analysis, &line[1..]),
+        // `from rclpy.node import `
+        (_, Some(import)) => {
+            let last = line.last().unwrap();
+            if !(last.text == "import" || is_operator(last, ",") || is_operator(last, "(")) {
+                return None;
+            }
+            let module = analysis.module(&dotted_name(&line[1..import])?)?;
+            Some(analysis.members(Value::Module(module)))
+        }
+    }
+}
+
+fn completion<'a>(analysis: &Analysis<'a>, member: Member<'a>) -> Completion {
+    let detail = analysis.resolve(&member).and_then(|value| match value {
+        Value::Class(_) | Value::Function(_) => analysis.signature(value).map(|signature| signature.parameters_label().to_owned()),
+        Value::Instance(_) | Value::Builtin(_) => Some(analysis.type_name(value)),
+        Value::Module(_) => None,
+    });
+    Completion {
+        label: member.name.to_owned(),
+        kind: member.kind,
+        detail,
+        info: member.description,
+    }
+}
+
+// the keyword arguments of the call at the start of an argument
+fn keyword_arguments(analysis: &Analysis, text: &str, tokens: &[Token], position: usize) -> Option<Vec<Completion>> {
+    let last = tokens.last()?;
+    if !(is_operator(last, "(") || is_operator(last, ",")) {
+        return None;
+    }
+    let call = open_call(tokens)?;
+    let signature = analysis.signature(evaluate(analysis, text, tokens, call.opening - 1, position)?)?;
+    let completions = signature.parameters.into_iter()
+        .filter(|parameter| matches!(parameter.kind, SignatureParameterKind::Positional | SignatureParameterKind::KeywordOnly))
+        .filter(|parameter| !call.keywords.contains(&parameter.name.as_str()))
+        .map(|parameter| Completion {
+            label: format!("{}=", parameter.name),
+            kind: MemberKind::Variable,
+            detail: parameter.annotation,
+            info: parameter.description,
+        })
+        .collect();
+    Some(completions)
+}
+
+// the attributes after a `.`, the modules and names in imports and the keyword arguments of calls,
+// other names are completed by the language support of the editor
+pub fn complete(text: &str, position: usize, explicit: bool, library: &Library) -> Option<Completions> {
+    let tokens = tokens_before(text, position)?;
+    let (from, before) = match tokens.split_last() {
+        Some((last, before)) if last.kind == TokenKind::Name && last.range.end == position => (last.range.start, before),
+        _ => (position, &tokens[..]),
+    };
+    let module = parse::parse(text);
+    let analysis = Analysis::new(text, library, &module.body);
+    let line = &before[line_start(before, before.len())..];
+    let options = if line.first().is_some_and(|first| first.kind == TokenKind::Name && matches!(first.text, "import" | "from")) {
+        importable(&analysis, line)?.into_iter().map(|member| completion(&analysis, member)).collect()
+    } else if before.last().is_some_and(|last| is_operator(last, ".")) {
+        let value = evaluate(&analysis, text, before, before.len().checked_sub(2)?, position)?;
+        analysis.members(value).into_iter().map(|member| completion(&analysis, member)).collect()
+    } else if from < position || explicit {
+        keyword_arguments(&analysis, text, before, position)?
+    } else {
+        return None;
+    };
+    Some(Completions { from, options })
+}
+
+pub fn signature_help(text: &str, position: usize, library: &Library) -> Option<SignatureHelp> {
+    let tokens = tokens_before(text, position)?;
+    let call = open_call(&tokens)?;
+    let module = parse::parse(text);
+    let analysis = Analysis::new(text, library, &module.body);
+    let signature = analysis.signature(evaluate(&analysis, text, &tokens, call.opening - 1, position)?)?;
+    let parameters = || signature.parameters.iter().enumerate();
+    let active = match call.keyword {
+        Some(keyword) => parameters()
+            .find(|(_, parameter)| parameter.name == keyword && parameter.kind != SignatureParameterKind::VariadicPositional)
+            .or_else(|| parameters().find(|(_, parameter)| parameter.kind == SignatureParameterKind::VariadicKeyword)),
+        None => parameters()
+            .filter(|(_, parameter)| parameter.kind == SignatureParameterKind::Positional)
+            .nth(call.argument)
+            .or_else(|| parameters().find(|(_, parameter)| parameter.kind == SignatureParameterKind::VariadicPositional))
+            // the next keyword argument, e.g., of a message
+            .or_else(|| parameters().find(|(_, parameter)| {
+                parameter.kind == SignatureParameterKind::KeywordOnly && !call.keywords.contains(&parameter.name.as_str())
+            })),
+    };
+    Some(SignatureHelp {
+        active: active.map(|(index, _)| index),
+        position: tokens[call.opening].range.start,
+        signature,
+    })
+}
+
+// what is shown for a name and what it refers to
+fn documentation(analysis: &Analysis, name: &str, value: Value, description: String) -> Option<(String, String)> {
+    let signature = match value {
+        Value::Module(module) => match module {
+            Module::Api(path) => format!("module {path}"),
+            Module::Package(package) => format!("module {package}"),
+            Module::Messages(package) => format!("module {package}.msg"),
+        },
+        Value::Class(_) => format!("class {}", analysis.signature(value).map_or_else(|| name.to_owned(), |signature| signature.label)),
+        Value::Function(_) => format!("def {}", analysis.signature(value)?.label),
+        Value::Instance(_) | Value::Builtin(_) => format!("{name}: {}", analysis.type_name(value)),
+    };
+    let description = match description.is_empty() {
+        true => analysis.description(value),
+        false => description,
+    };
+    Some((signature, description))
+}
+
+// the modules and the names of an import statement, None for an alias, which is looked up like
+// other names
+fn imported<'a>(analysis: &Analysis<'a>, line: &[Token], index: usize) -> Option<Option<(Value<'a>, String)>> {
+    if index > 0 && line[index - 1].text == "as" {
+        return Some(None);
+    }
+    let import = line.iter().position(|token| token.kind == TokenKind::Name && token.text == "import");
+    let module = |tokens: &[Token]| Some(Value::Module(analysis.module(&dotted_name(tokens)?)?));
+    let imported = match (line[0].text, import) {
+        // `rclpy.node` in `from rclpy.node import Node`
+        ("from", Some(import)) if index < import => module(&line[1..=index]).map(|value| (value, String::new())),
+        ("from", Some(import)) => {
+            let member = analysis.member(module(&line[1..import])?, line[index].text)?;
+            analysis.resolve(&member).map(|value| (value, member.description))
+        }
+        // `node` in `import rclpy.node`
+        _ => {
+            let start = line[..index].iter()
+                .rposition(|token| !(token.kind == TokenKind::Name && token.text != "import" || is_operator(token, ".")))
+                .map_or(0, |separator| separator + 1);
+            module(&line[start..=index]).map(|value| (value, String::new()))
+        }
+    };
+    Some(imported)
+}
+
+pub fn hover(text: &str, position: usize, library: &Library) -> Option<Hover> {
+    let (tokens, _) = tokens::tokenize(text);
+    let index = tokens.iter().position(|token| token.kind == TokenKind::Name && token.range.contains(&position))?;
+    let token = &tokens[index];
+    if tokens::is_keyword(token.text) {
+        return None;
+    }
+    let module = parse::parse(text);
+    let analysis = Analysis::new(text, library, &module.body);
+    let scope = analysis.scope(position);
+    let start = line_start(&tokens, index);
+    let line = &tokens[start..];
+    let hover = |signature: String, description: String| Hover { range: token.range.clone(), signature, description };
+    if line[0].kind == TokenKind::Name && matches!(line[0].text, "import" | "from") {
+        if let Some(imported) = imported(&analysis, line, index - start)? {
+            let (value, description) = imported;
+            let (signature, description) = documentation(&analysis, token.text, value, description)?;
+            return Some(hover(signature, description));
+        }
+    }
+    // a keyword argument, e.g., `left` in `Velocity(left=1.0)`
+    let keyword = index > 0
+        && (is_operator(&tokens[index - 1], "(") || is_operator(&tokens[index - 1], ","))
+        && tokens.get(index + 1).is_some_and(|equals| is_operator(equals, "="));
+    if keyword {
+        let call = open_call(&tokens[..index])?;
+        let signature = analysis.signature(evaluate(&analysis, text, &tokens, call.opening - 1, position)?)?;
+        let parameter = signature.parameters.iter().find(|parameter| parameter.name == token.text)?;
+        return Some(hover(signature.label[parameter.range.clone()].to_owned(), parameter.description.clone()));
+    }
+    let expression_start = primary_start(&tokens, index)?;
+    let expression = parse::parse(&text[tokens[expression_start].range.start..token.range.end]);
+    let [Stmt { kind: StmtKind::Expr(expr), .. }] = expression.body.as_slice() else {
+        return None;
+    };
+    let (value, description) = match &expr.kind {
+        ExprKind::Name(name) => (analysis.lookup(name, &scope)?, String::new()),
+        ExprKind::Attribute { value, attribute } => {
+            let member = analysis.member(analysis.value_of(value, &scope)?, attribute)?;
+            (analysis.resolve(&member)?, member.description)
+        }
+        _ => return None,
+    };
+    let (signature, description) = documentation(&analysis, token.text, value, description)?;
+    Some(hover(signature, description))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::MessageDefinition;
+
+    // the file that new projects start with
+    const SAMPLE: &str = "\
+import rclpy
+
+from rclpy.node import Node
+from example_interfaces.msg import Velocity
+
+class VelocityPublisher(Node):
+    def __init__(self):
+        super().__init__('velocity_publisher')
+        self.publisher_ = \\
+            self.create_publisher(Velocity, 'velocity', 10)
+        timer_period = 5.0  # seconds
+        self.timer = \\
+            self.create_timer(timer_period, self.timer_callback)
+        self.drive_forwards = True
+    def timer_callback(self):
+        if self.drive_forwards:
+            # drive forwards
+            self.get_logger().info('Driving forwards')
+            self.publisher_.publish(Velocity(left=5.0, right=-5.0))
+        else:
+            # turn on the spot
+            self.get_logger().info('Turning')
+            self.publisher_.publish(Velocity(left=2.5, right=2.5))
+        # toggle mode
+        self.drive_forwards = not self.drive_forwards
+        
+rclpy.init()
+velocity_publisher = VelocityPublisher()
+rclpy.spin(velocity_publisher)
+velocity_publisher.destroy_node()
+rclpy.shutdown()
+";
+
+    // the position right after the first occurrence of the text
+    fn after(text: &str) -> usize {
+        SAMPLE.find(text).unwrap() + text.len()
+    }
+
+    fn labels(position: usize, explicit: bool) -> Vec<String> {
+        let library = Library::new(&[]);
+        let completions = complete(SAMPLE, position, explicit, &library).unwrap();
+        completions.options.into_iter().map(|completion| completion.label).collect()
+    }
+
+    fn hover_at(text: &str) -> (String, String) {
+        let library = Library::new(&[]);
+        let hover = hover(SAMPLE, after(text) - 1, &library).unwrap();
+        assert_eq!(&SAMPLE[hover.range], text.rsplit(|character: char| !character.is_alphanumeric() && character != '_').next().unwrap());
+        (hover.signature, hover.description)
+    }
+
+    #[test]
+    fn complete_attributes() {
+        let position = after("self.publisher_.pub");
+        let library = Library::new(&[]);
+        assert_eq!(complete(SAMPLE, position, false, &library).unwrap().from, position - 3);
+        assert_eq!(labels(position, false), ["publish", "topic_name"]);
+        let members = labels(after("rclpy.sp"), false);
+        assert!(["init", "spin", "shutdown", "node"].iter().all(|label| members.contains(&label.to_string())));
+        assert!(labels(after("self.get_logger()."), false).contains(&"info".to_owned()));
+    }
+
+    #[test]
+    fn complete_imports() {
+        assert_eq!(labels(after("from rclpy.node import No"), false), ["Node"]);
+        assert!(labels(after("from example_interfaces.msg import Vel"), false).contains(&"Velocity".to_owned()));
+    }
+
+    #[test]
+    fn complete_keyword_arguments() {
+        // the arguments that are already given are left out
+        assert_eq!(labels(after("Velocity(left=5.0, "), true), ["right="]);
+        let library = Library::new(&[]);
+        assert!(complete(SAMPLE, after("Velocity(left=5.0, "), false, &library).is_none());
+    }
+
+    #[test]
+    fn complete_project_messages() {
+        let text = "from control.msg import Pose\nPose(";
+        let definitions = [MessageDefinition { package: "control", name: "Pose", text: "float64 x\nfloat64 y\n" }];
+        let library = Library::new(&definitions);
+        let completions = complete(text, text.len(), true, &library).unwrap();
+        let labels = completions.options.iter().map(|completion| completion.label.as_str()).collect::<Vec<_>>();
+        assert_eq!(labels, ["x=", "y="]);
+    }
+
+    #[test]
+    fn signature_help_of_calls() {
+        let library = Library::new(&[]);
+        let help = signature_help(SAMPLE, after("create_publisher(Velocity, "), &library).unwrap();
+        assert_eq!(help.signature.label, "create_publisher(msg_type: type, topic: str, qos_profile: int) -> Publisher");
+        assert_eq!(help.active, Some(1));
+        assert_eq!(help.position, after("create_publisher"));
+        let topic = &help.signature.parameters[1];
+        assert_eq!(&help.signature.label[topic.range.clone()], "topic: str");
+
+        let help = signature_help(SAMPLE, after("rclpy.spin("), &library).unwrap();
+        assert_eq!(help.signature.label, "spin(node: Node) -> None");
+        assert_eq!(help.active, Some(0));
+    }
+
+    #[test]
+    fn signature_help_of_messages() {
+        let library = Library::new(&[]);
+        let help = signature_help(SAMPLE, after("Velocity(left=5.0, right="), &library).unwrap();
+        assert_eq!(help.signature.label, "Velocity(*, left: float = 0.0, right: float = 0.0)");
+        assert_eq!(help.signature.parameters[help.active.unwrap()].name, "right");
+        // the next keyword that is not given yet
+        let help = signature_help(SAMPLE, after("Velocity(left=5.0, "), &library).unwrap();
+        assert_eq!(help.signature.parameters[help.active.unwrap()].name, "right");
+        assert!(signature_help(SAMPLE, after("rclpy.init()"), &library).is_none());
+    }
+
+    #[test]
+    fn hover_documentation() {
+        assert_eq!(hover_at("rclpy.spin").0, "def spin(node: Node) -> None");
+        assert_eq!(hover_at("(Node").0, "class Node(node_name: str)");
+        assert_eq!(hover_at("import rclpy").0, "module rclpy");
+        assert_eq!(hover_at("velocity_publisher.destroy_node").0, "def destroy_node() -> None");
+        let (signature, description) = hover_at("Velocity(left");
+        assert_eq!(signature, "left: float = 0.0");
+        assert_eq!(description, "The velocity of the left wheel");
+        // the type of a variable is inferred from its value
+        assert_eq!(hover_at("timer_period").0, "timer_period: float");
+        let library = Library::new(&[]);
+        assert!(hover(SAMPLE, after("class") - 1, &library).is_none());
+    }
+}
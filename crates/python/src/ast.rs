@@ -0,0 +1,224 @@
+// the syntax tree of a Python file, which only keeps what is needed to know where names are bound
+// and used, offsets are in bytes
+use std::ops::Range;
+
+#[derive(Debug)]
+pub struct Expr<'a> {
+    pub kind: ExprKind<'a>,
+    pub range: Range<usize>,
+}
+
+#[derive(Debug)]
+pub enum ExprKind<'a> {
+    Name(&'a str),
+    // numbers, strings, `None`, `True`, `False` and `...`
+    Constant(&'a str),
+    Attribute {
+        value: Box<Expr<'a>>,
+        attribute: &'a str,
+    },
+    Subscript {
+        value: Box<Expr<'a>>,
+        slice: Box<Expr<'a>>,
+    },
+    Call {
+        function: Box<Expr<'a>>,
+        arguments: Vec<Argument<'a>>,
+    },
+    Tuple(Vec<Expr<'a>>),
+    List(Vec<Expr<'a>>),
+    Set(Vec<Expr<'a>>),
+    // a missing key is a `**` entry
+    Dict(Vec<(Option<Expr<'a>>, Expr<'a>)>),
+    Starred(Box<Expr<'a>>),
+    // `name := value`, in patterns `value as name`
+    Named {
+        target: Name<'a>,
+        value: Box<Expr<'a>>,
+    },
+    Lambda {
+        parameters: Vec<Parameter<'a>>,
+        body: Box<Expr<'a>>,
+    },
+    Comprehension {
+        // e.g., "list comprehension"
+        description: &'static str,
+        // the element, or the key and the value of a dict comprehension
+        elements: Vec<Expr<'a>>,
+        generators: Vec<Generator<'a>>,
+    },
+    // the operators, slices, `await` and `yield`, of which only the operands matter, the
+    // description names it in errors, e.g., "comparison"
+    Operation(&'static str, Vec<Expr<'a>>),
+}
+
+impl Expr<'_> {
+    // what the expression is called in errors, e.g., "cannot assign to function call"
+    pub fn description(&self) -> &'static str {
+        match &self.kind {
+            ExprKind::Name(_) => "name",
+            ExprKind::Constant("None") => "None",
+            ExprKind::Constant("True") => "True",
+            ExprKind::Constant("False") => "False",
+            ExprKind::Constant("...") => "ellipsis",
+            ExprKind::Constant(_) => "literal",
+            ExprKind::Attribute { .. } => "attribute",
+            ExprKind::Subscript { .. } => "subscript",
+            ExprKind::Call { .. } => "function call",
+            ExprKind::Tuple(_) => "tuple",
+            ExprKind::List(_) => "list",
+            ExprKind::Set(_) => "set display",
+            ExprKind::Dict(_) => "dict literal",
+            ExprKind::Starred(_) => "starred",
+            ExprKind::Named { .. } => "named expression",
+            ExprKind::Lambda { .. } => "lambda",
+            ExprKind::Comprehension { description, .. } => description,
+            ExprKind::Operation(description, _) => description,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Name<'a> {
+    pub name: &'a str,
+    pub range: Range<usize>,
+}
+
+#[derive(Debug)]
+pub struct Argument<'a> {
+    pub keyword: Option<Name<'a>>,
+    // `*args` is starred and `**kwargs` is an operation
+    pub value: Expr<'a>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParameterKind {
+    Positional,
+    // `*args`
+    VariadicPositional,
+    // `**kwargs`
+    VariadicKeyword,
+}
+
+#[derive(Debug)]
+pub struct Parameter<'a> {
+    pub name: Name<'a>,
+    pub kind: ParameterKind,
+    pub annotation: Option<Expr<'a>>,
+    pub default: Option<Expr<'a>>,
+}
+
+#[derive(Debug)]
+pub struct Generator<'a> {
+    pub target: Expr<'a>,
+    pub iterable: Expr<'a>,
+    pub conditions: Vec<Expr<'a>>,
+}
+
+#[derive(Debug)]
+pub struct Stmt<'a> {
+    pub kind: StmtKind<'a>,
+    pub range: Range<usize>,
+}
+
+#[derive(Debug)]
+pub enum StmtKind<'a> {
+    Expr(Expr<'a>),
+    Assign {
+        targets: Vec<Expr<'a>>,
+        value: Expr<'a>,
+    },
+    AugmentedAssign {
+        target: Expr<'a>,
+        value: Expr<'a>,
+    },
+    AnnotatedAssign {
+        target: Expr<'a>,
+        annotation: Expr<'a>,
+        value: Option<Expr<'a>>,
+    },
+    Delete(Vec<Expr<'a>>),
+    Pass,
+    Break,
+    Continue,
+    Return(Option<Expr<'a>>),
+    // the exception and its cause
+    Raise(Vec<Expr<'a>>),
+    Assert(Vec<Expr<'a>>),
+    Global(Vec<Name<'a>>),
+    Nonlocal(Vec<Name<'a>>),
+    Import(Vec<Alias<'a>>),
+    ImportFrom {
+        // with the leading dots of a relative import
+        module: &'a str,
+        names: Vec<Alias<'a>>,
+        // `from module import *`
+        star: bool,
+    },
+    If {
+        test: Expr<'a>,
+        body: Vec<Stmt<'a>>,
+        orelse: Vec<Stmt<'a>>,
+    },
+    While {
+        test: Expr<'a>,
+        body: Vec<Stmt<'a>>,
+        orelse: Vec<Stmt<'a>>,
+    },
+    For {
+        target: Expr<'a>,
+        iterable: Expr<'a>,
+        body: Vec<Stmt<'a>>,
+        orelse: Vec<Stmt<'a>>,
+    },
+    Try {
+        body: Vec<Stmt<'a>>,
+        handlers: Vec<Handler<'a>>,
+        orelse: Vec<Stmt<'a>>,
+        finally: Vec<Stmt<'a>>,
+    },
+    With {
+        items: Vec<(Expr<'a>, Option<Expr<'a>>)>,
+        body: Vec<Stmt<'a>>,
+    },
+    FunctionDef {
+        name: Name<'a>,
+        decorators: Vec<Expr<'a>>,
+        parameters: Vec<Parameter<'a>>,
+        returns: Option<Expr<'a>>,
+        body: Vec<Stmt<'a>>,
+    },
+    ClassDef {
+        name: Name<'a>,
+        decorators: Vec<Expr<'a>>,
+        bases: Vec<Argument<'a>>,
+        body: Vec<Stmt<'a>>,
+    },
+    Match {
+        subject: Expr<'a>,
+        cases: Vec<Case<'a>>,
+    },
+}
+
+#[derive(Debug)]
+pub struct Alias<'a> {
+    // dotted for modules, e.g., `rclpy.node`
+    pub name: Name<'a>,
+    pub alias: Option<Name<'a>>,
+}
+
+#[derive(Debug)]
+pub struct Handler<'a> {
+    pub exception: Option<Expr<'a>>,
+    pub name: Option<Name<'a>>,
+    pub body: Vec<Stmt<'a>>,
+}
+
+// the pattern is written like an expression: names capture, attributes are values, calls are
+// class patterns, `as` is a named expression and alternatives are an operation
+#[derive(Debug)]
+pub struct Case<'a> {
+    pub pattern: Expr<'a>,
+    pub guard: Option<Expr<'a>>,
+    pub body: Vec<Stmt<'a>>,
+}
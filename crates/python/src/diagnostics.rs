@@ -0,0 +1,9 @@
+// the syntax errors that the tokenizer and the parser find, which become diagnostics of the file,
+// offsets are in bytes
+use std::ops::Range;
+
+#[derive(Clone, Debug)]
+pub struct SyntaxError {
+    pub range: Range<usize>,
+    pub message: String,
+}
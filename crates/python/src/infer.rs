@@ -0,0 +1,947 @@
+// what the names and expressions of a Python file refer to, as far as they come from the API of
+// rclpy, the message types and the classes of the file, offsets are in bytes
+use std::{cell::Cell, ops::Range};
+
+use common::completions::CompletionKind;
+
+use crate::{
+    api::{self, MessageDefinition},
+    ast::{Argument, Expr, ExprKind, ParameterKind, Stmt, StmtKind},
+    messages::{self, Message},
+};
+
+// the message types that can be imported, the bundled ones and those of the project
+pub struct Library<'a> {
+    messages: Vec<Message<'a>>,
+}
+
+impl<'a> Library<'a> {
+    pub fn new(project: &[MessageDefinition<'a>]) -> Library<'a> {
+        Library {
+            messages: api::MESSAGES.iter().chain(project).map(messages::parse).collect(),
+        }
+    }
+
+    fn message(&self, package: &str, name: &str) -> Option<&Message<'a>> {
+        self.messages.iter().find(|message| message.package == package && message.name == name)
+    }
+
+    fn packages(&self) -> Vec<&'a str> {
+        let mut packages = Vec::new();
+        for message in &self.messages {
+            if !packages.contains(&message.package) {
+                packages.push(message.package);
+            }
+        }
+        packages
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Module<'a> {
+    // `rclpy` or one of its submodules, e.g., `rclpy.node`
+    Api(&'static str),
+    // a package with message types
+    Package(&'a str),
+    // the `msg` module of a package
+    Messages(&'a str),
+}
+
+#[derive(Clone, Copy)]
+pub enum Class<'a> {
+    Api(&'static api::Class),
+    Message(&'a Message<'a>),
+    // a class of the file
+    Source(&'a Stmt<'a>),
+}
+
+#[derive(Clone, Copy)]
+pub enum Function<'a> {
+    Api(&'static api::Function),
+    // a function of the file, a method is bound if it is an attribute of an instance, which
+    // passes `self`
+    Source { definition: &'a Stmt<'a>, bound: bool },
+}
+
+#[derive(Clone, Copy)]
+pub enum Value<'a> {
+    Module(Module<'a>),
+    Class(Class<'a>),
+    Instance(Class<'a>),
+    Function(Function<'a>),
+    // a value of a type that is only known by its name, e.g., `float`
+    Builtin(&'a str),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemberKind {
+    Module,
+    Class,
+    Function,
+    Method,
+    Property,
+    Constant,
+    Variable,
+}
+
+impl MemberKind {
+    // the icon of the completion in CodeMirror
+    pub fn completion_kind(&self) -> CompletionKind {
+        match self {
+            MemberKind::Module => CompletionKind::Module,
+            MemberKind::Class => CompletionKind::Class,
+            MemberKind::Function => CompletionKind::Function,
+            MemberKind::Method => CompletionKind::Method,
+            MemberKind::Property => CompletionKind::Property,
+            MemberKind::Constant => CompletionKind::Constant,
+            MemberKind::Variable => CompletionKind::Variable,
+        }
+    }
+}
+
+// where an expression is evaluated
+#[derive(Clone)]
+pub struct Scope<'a> {
+    // the functions and classes around the position, the innermost last
+    definitions: Vec<&'a Stmt<'a>>,
+    position: usize,
+}
+
+#[derive(Clone)]
+pub enum Target<'a> {
+    Value(Value<'a>),
+    // an attribute that a class of the file assigns, which is only inferred when it is needed
+    Assignment {
+        value: &'a Expr<'a>,
+        // the value is the annotation of the attribute
+        annotated: bool,
+        scope: Scope<'a>,
+    },
+}
+
+// an attribute of a module, a class or an instance
+pub struct Member<'a> {
+    pub name: &'a str,
+    pub kind: MemberKind,
+    pub target: Target<'a>,
+    // empty for the attributes of the classes of the file
+    pub description: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignatureParameterKind {
+    // given by position or by keyword
+    Positional,
+    KeywordOnly,
+    VariadicPositional,
+    VariadicKeyword,
+}
+
+pub struct SignatureParameter {
+    pub name: String,
+    pub kind: SignatureParameterKind,
+    pub annotation: Option<String>,
+    // the range of the parameter in the label, in bytes
+    pub range: Range<usize>,
+    pub description: String,
+}
+
+// how a function or a class is called, e.g., `create_timer(timer_period_sec: float, callback: Callable) -> Timer`
+pub struct Signature {
+    pub name: String,
+    pub label: String,
+    pub parameters: Vec<SignatureParameter>,
+    pub description: String,
+}
+
+// a parameter before it is written into a label
+struct ParameterText {
+    name: String,
+    kind: SignatureParameterKind,
+    annotation: Option<String>,
+    default: Option<String>,
+    description: String,
+}
+
+impl Signature {
+    fn new(name: &str, parameters: Vec<ParameterText>, returns: Option<&str>, description: String) -> Signature {
+        let mut label = format!("{name}(");
+        let mut keyword_only = false;
+        let mut signature_parameters = Vec::new();
+        for (index, parameter) in parameters.into_iter().enumerate() {
+            if index > 0 {
+                label.push_str(", ");
+            }
+            if parameter.kind == SignatureParameterKind::KeywordOnly && !keyword_only {
+                label.push_str("*, ");
+            }
+            keyword_only |= matches!(parameter.kind, SignatureParameterKind::KeywordOnly | SignatureParameterKind::VariadicPositional);
+            let start = label.len();
+            label.push_str(match parameter.kind {
+                SignatureParameterKind::VariadicPositional => "*",
+                SignatureParameterKind::VariadicKeyword => "**",
+                _ => "",
+            });
+            label.push_str(&parameter.name);
+            if let Some(annotation) = &parameter.annotation {
+                label.push_str(": ");
+                label.push_str(annotation);
+            }
+            if let Some(default) = &parameter.default {
+                label.push_str(" = ");
+                label.push_str(default);
+            }
+            signature_parameters.push(SignatureParameter {
+                name: parameter.name,
+                kind: parameter.kind,
+                annotation: parameter.annotation,
+                range: start..label.len(),
+                description: parameter.description,
+            });
+        }
+        label.push(')');
+        if let Some(returns) = returns {
+            label.push_str(" -> ");
+            label.push_str(returns);
+        }
+        Signature {
+            name: name.to_owned(),
+            label,
+            parameters: signature_parameters,
+            description,
+        }
+    }
+
+    // the constructor of a base class for a class that derives from it
+    fn rename(mut self, name: &str) -> Signature {
+        let shift = |offset: usize| offset + name.len() - self.name.len();
+        for parameter in &mut self.parameters {
+            parameter.range = shift(parameter.range.start)..shift(parameter.range.end);
+        }
+        self.label = format!("{name}{}", &self.label[self.name.len()..]);
+        self.name = name.to_owned();
+        self
+    }
+
+    // the parameters and the return type, e.g., for the detail of a completion
+    pub fn parameters_label(&self) -> &str {
+        &self.label[self.name.len()..]
+    }
+}
+
+// how a name is bound in a body
+#[derive(Clone, Copy)]
+enum Binding<'a> {
+    Value(&'a Expr<'a>),
+    Annotation(&'a Expr<'a>),
+    // `import rclpy.node` binds `rclpy`, `import rclpy.node as node` binds the module itself
+    Module(&'a str),
+    // `from module import name`
+    Import { module: &'a str, name: &'a str },
+    // a function or a class
+    Definition(&'a Stmt<'a>),
+}
+
+// the statements nested in a statement, e.g., the body and the `else` block of an `if` statement
+fn blocks<'a>(statement: &'a Stmt<'a>) -> Vec<&'a [Stmt<'a>]> {
+    match &statement.kind {
+        StmtKind::If { body, orelse, .. } | StmtKind::While { body, orelse, .. } | StmtKind::For { body, orelse, .. } => {
+            vec![body, orelse]
+        }
+        StmtKind::Try { body, handlers, orelse, finally } => std::iter::once(&body[..])
+            .chain(handlers.iter().map(|handler| &handler.body[..]))
+            .chain([&orelse[..], &finally[..]])
+            .collect(),
+        StmtKind::With { body, .. } | StmtKind::FunctionDef { body, .. } | StmtKind::ClassDef { body, .. } => vec![body],
+        StmtKind::Match { cases, .. } => cases.iter().map(|case| &case.body[..]).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn is_definition(statement: &Stmt) -> bool {
+    matches!(statement.kind, StmtKind::FunctionDef { .. } | StmtKind::ClassDef { .. })
+}
+
+// the bindings of a name in a body with the positions of their statements, without those in the
+// functions and classes of the body
+fn bindings<'a>(body: &'a [Stmt<'a>], name: &str, found: &mut Vec<(usize, Binding<'a>)>) {
+    let is_name = |expr: &Expr| matches!(expr.kind, ExprKind::Name(target) if target == name);
+    for statement in body {
+        let start = statement.range.start;
+        match &statement.kind {
+            StmtKind::Assign { targets, value } if targets.iter().any(is_name) => found.push((start, Binding::Value(value))),
+            StmtKind::AnnotatedAssign { target, annotation, .. } if is_name(target) => {
+                found.push((start, Binding::Annotation(annotation)));
+            }
+            StmtKind::Import(aliases) => {
+                for alias in aliases {
+                    match &alias.alias {
+                        Some(bound) if bound.name == name => found.push((start, Binding::Module(alias.name.name))),
+                        None if alias.name.name.split('.').next() == Some(name) => {
+                            found.push((start, Binding::Module(&alias.name.name[..name.len()])));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            StmtKind::ImportFrom { module, names, .. } => {
+                for alias in names {
+                    if alias.alias.as_ref().unwrap_or(&alias.name).name == name {
+                        found.push((start, Binding::Import { module, name: alias.name.name }));
+                    }
+                }
+            }
+            StmtKind::FunctionDef { name: defined, .. } | StmtKind::ClassDef { name: defined, .. } if defined.name == name => {
+                found.push((start, Binding::Definition(statement)));
+            }
+            _ => {}
+        }
+        if !is_definition(statement) {
+            for block in blocks(statement) {
+                bindings(block, name, found);
+            }
+        }
+    }
+}
+
+// the last binding before the position, or the first one if the name is only bound later, e.g.,
+// in a function that is called later
+fn binding<'a>(body: &'a [Stmt<'a>], name: &str, position: usize) -> Option<(usize, Binding<'a>)> {
+    let mut found = Vec::new();
+    bindings(body, name, &mut found);
+    found.iter().rev().find(|(start, _)| *start < position).or(found.first()).copied()
+}
+
+// the argument for a parameter, given by keyword or by position
+fn argument<'e, 'a>(arguments: &'e [Argument<'a>], index: usize, name: &str) -> Option<&'e Expr<'a>> {
+    let by_keyword = arguments.iter().find(|argument| argument.keyword.as_ref().is_some_and(|keyword| keyword.name == name));
+    by_keyword
+        .or_else(|| arguments.iter().filter(|argument| argument.keyword.is_none()).nth(index))
+        .map(|argument| &argument.value)
+}
+
+// the text of a string literal without its prefix and its quotes
+fn string_contents(literal: &str) -> Option<&str> {
+    let literal = literal.trim_start_matches(['r', 'R', 'u', 'U']);
+    ["\"\"\"", "'''", "\"", "'"].iter().find_map(|quote| literal.strip_prefix(quote)?.strip_suffix(quote))
+}
+
+// the builtin type of a literal, None for `None` and `...`
+fn literal_type(literal: &str) -> Option<&'static str> {
+    let literal_type = match literal {
+        "True" | "False" => "bool",
+        "None" | "..." => return None,
+        _ if literal.starts_with(|character: char| character.is_ascii_digit() || character == '.') => {
+            let lowercase = literal.to_ascii_lowercase();
+            if lowercase.ends_with('j') {
+                "complex"
+            } else if !lowercase.starts_with("0x") && lowercase.contains(['.', 'e']) {
+                "float"
+            } else {
+                "int"
+            }
+        }
+        _ if literal.trim_start_matches(['r', 'R']).starts_with(['b', 'B']) => "bytes",
+        _ => "str",
+    };
+    Some(literal_type)
+}
+
+// the string at the start of the body of a function or a class
+fn docstring(body: &[Stmt]) -> String {
+    let Some(StmtKind::Expr(Expr { kind: ExprKind::Constant(literal), .. })) = body.first().map(|statement| &statement.kind) else {
+        return String::new();
+    };
+    let Some(contents) = string_contents(literal) else {
+        return String::new();
+    };
+    contents.lines().map(str::trim).collect::<Vec<_>>().join("\n").trim().to_owned()
+}
+
+pub struct Analysis<'a> {
+    text: &'a str,
+    library: &'a Library<'a>,
+    body: &'a [Stmt<'a>],
+    // how deeply values are inferred from other values, which stops assignments that refer to
+    // themselves, e.g., `x = x.y`
+    depth: Cell<usize>,
+}
+
+const MAXIMUM_DEPTH: usize = 24;
+
+impl<'a> Analysis<'a> {
+    pub fn new(text: &'a str, library: &'a Library<'a>, body: &'a [Stmt<'a>]) -> Analysis<'a> {
+        Analysis {
+            text,
+            library,
+            body,
+            depth: Cell::new(0),
+        }
+    }
+
+    fn guarded<T>(&self, infer: impl FnOnce() -> Option<T>) -> Option<T> {
+        if self.depth.get() >= MAXIMUM_DEPTH {
+            return None;
+        }
+        self.depth.set(self.depth.get() + 1);
+        let inferred = infer();
+        self.depth.set(self.depth.get() - 1);
+        inferred
+    }
+
+    pub fn scope(&self, position: usize) -> Scope<'a> {
+        let mut definitions = Vec::new();
+        let mut body = self.body;
+        'nested: loop {
+            for statement in body {
+                if !(statement.range.start < position && position <= statement.range.end) {
+                    continue;
+                }
+                if is_definition(statement) {
+                    definitions.push(statement);
+                }
+                // the block of a compound statement that contains the position
+                let block = blocks(statement).into_iter().find(|block| {
+                    block.first().is_some_and(|first| first.range.start < position)
+                        && block.last().is_some_and(|last| position <= last.range.end)
+                });
+                if let Some(block) = block {
+                    body = block;
+                    continue 'nested;
+                }
+                break 'nested;
+            }
+            break;
+        }
+        Scope { definitions, position }
+    }
+
+    fn scope_of(&self, statement: &'a Stmt<'a>) -> Scope<'a> {
+        self.scope(statement.range.start)
+    }
+
+    // the modules that can be imported without a package
+    pub fn top_level_modules(&self) -> Vec<Member<'a>> {
+        let rclpy = Module::Api("rclpy");
+        std::iter::once(rclpy)
+            .chain(self.library.packages().into_iter().map(Module::Package))
+            .map(|module| self.module_member(module))
+            .collect()
+    }
+
+    pub fn module(&self, path: &str) -> Option<Module<'a>> {
+        let api = api::MODULES.iter().find(|module| {
+            module.name.strip_prefix(path).is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        });
+        if let Some(module) = api {
+            return Some(Module::Api(&module.name[..path.len()]));
+        }
+        let (package, submodule) = match path.split_once('.') {
+            Some((package, submodule)) => (package, Some(submodule)),
+            None => (path, None),
+        };
+        let package = self.library.packages().into_iter().find(|known| *known == package)?;
+        match submodule {
+            None => Some(Module::Package(package)),
+            Some("msg") => Some(Module::Messages(package)),
+            Some(_) => None,
+        }
+    }
+
+    pub fn value_of(&self, expr: &Expr, scope: &Scope<'a>) -> Option<Value<'a>> {
+        self.guarded(|| match &expr.kind {
+            ExprKind::Name(name) => self.lookup(name, scope),
+            ExprKind::Attribute { value, attribute } => self.member_value(self.value_of(value, scope)?, attribute),
+            ExprKind::Call { function, .. } => {
+                // `super()` in a method
+                if matches!(function.kind, ExprKind::Name("super")) && self.lookup("super", scope).is_none() {
+                    let class = scope.definitions.iter().rev().copied().find(|definition| matches!(definition.kind, StmtKind::ClassDef { .. }))?;
+                    return self.bases(class).into_iter().next().map(Value::Instance);
+                }
+                match self.value_of(function, scope)? {
+                    Value::Class(class) => Some(Value::Instance(class)),
+                    Value::Function(function) => self.returns(function),
+                    _ => None,
+                }
+            }
+            ExprKind::Constant(constant) => literal_type(constant).map(Value::Builtin),
+            ExprKind::Tuple(_) => Some(Value::Builtin("tuple")),
+            ExprKind::List(_) => Some(Value::Builtin("list")),
+            ExprKind::Set(_) => Some(Value::Builtin("set")),
+            ExprKind::Dict(_) => Some(Value::Builtin("dict")),
+            _ => None,
+        })
+    }
+
+    // the instance of the class that an annotation names, or the name of a builtin type
+    fn annotation(&self, annotation: &'a Expr<'a>, scope: &Scope<'a>) -> Option<Value<'a>> {
+        match (self.value_of(annotation, scope), &annotation.kind) {
+            (Some(Value::Class(class)), _) => Some(Value::Instance(class)),
+            (None, ExprKind::Name(name)) => Some(Value::Builtin(name)),
+            _ => None,
+        }
+    }
+
+    // the instance of a class of the API or the name of a builtin type
+    fn type_value(&self, annotation: &'static str) -> Value<'a> {
+        api::class(annotation).map_or(Value::Builtin(annotation), |class| Value::Instance(Class::Api(class)))
+    }
+
+    pub fn lookup(&self, name: &str, scope: &Scope<'a>) -> Option<Value<'a>> {
+        let definitions = &scope.definitions;
+        for (index, definition) in definitions.iter().enumerate().rev() {
+            let body = match &definition.kind {
+                StmtKind::FunctionDef { parameters, body, .. } => {
+                    if let Some(parameter) = parameters.iter().position(|parameter| parameter.name.name == name) {
+                        return self.parameter(&definitions[..=index], parameter);
+                    }
+                    body
+                }
+                // the names of a class are not seen by its methods
+                StmtKind::ClassDef { body, .. } if index + 1 == definitions.len() => body,
+                _ => continue,
+            };
+            if let Some((start, binding)) = binding(body, name, scope.position) {
+                let scope = Scope { definitions: definitions[..=index].to_vec(), position: start };
+                return self.binding_value(binding, &scope);
+            }
+        }
+        let (start, binding) = binding(self.body, name, scope.position)?;
+        self.binding_value(binding, &Scope { definitions: Vec::new(), position: start })
+    }
+
+    fn binding_value(&self, binding: Binding<'a>, scope: &Scope<'a>) -> Option<Value<'a>> {
+        match binding {
+            Binding::Value(value) => self.value_of(value, scope),
+            Binding::Annotation(annotation) => self.annotation(annotation, scope),
+            Binding::Module(path) => self.module(path).map(Value::Module),
+            Binding::Import { module, name } => self.member_value(Value::Module(self.module(module)?), name),
+            Binding::Definition(definition) => match definition.kind {
+                StmtKind::ClassDef { .. } => Some(Value::Class(Class::Source(definition))),
+                _ => Some(Value::Function(Function::Source { definition, bound: false })),
+            },
+        }
+    }
+
+    // a parameter of the innermost function, which is the instance for the first parameter of a
+    // method and the message for the parameter of a subscription callback
+    fn parameter(&self, definitions: &[&'a Stmt<'a>], index: usize) -> Option<Value<'a>> {
+        let (&function, outer) = definitions.split_last()?;
+        let StmtKind::FunctionDef { parameters, decorators, .. } = &function.kind else {
+            return None;
+        };
+        let outer = Scope { definitions: outer.to_vec(), position: function.range.start };
+        if let Some(annotation) = &parameters[index].annotation {
+            return self.annotation(annotation, &outer);
+        }
+        let decorated = |name: &str| decorators.iter().any(|decorator| matches!(decorator.kind, ExprKind::Name(decorator) if decorator == name));
+        let class = outer.definitions.last().copied().filter(|definition| matches!(definition.kind, StmtKind::ClassDef { .. }));
+        let method = class.is_some() && !decorated("staticmethod");
+        if let (Some(class), true, 0) = (class, method, index) {
+            return Some(match decorated("classmethod") {
+                true => Value::Class(Class::Source(class)),
+                false => Value::Instance(Class::Source(class)),
+            });
+        }
+        match index - usize::from(method) {
+            0 => self.callback_message(function),
+            _ => None,
+        }
+    }
+
+    // the message that a function receives as the callback of a subscription, found by looking
+    // for the call that creates the subscription
+    fn callback_message(&self, function: &'a Stmt<'a>) -> Option<Value<'a>> {
+        let StmtKind::FunctionDef { name, .. } = &function.kind else {
+            return None;
+        };
+        let mut calls = Vec::new();
+        statement_calls(self.body, &mut calls);
+        calls.into_iter().find_map(|(statement, call)| {
+            let ExprKind::Call { function: callee, arguments } = &call.kind else {
+                return None;
+            };
+            // the callback is the function, e.g., `self.listener_callback`
+            let refers = |expr: &Expr| match &expr.kind {
+                ExprKind::Name(callback) | ExprKind::Attribute { attribute: callback, .. } => *callback == name.name,
+                _ => false,
+            };
+            if !arguments.iter().any(|argument| refers(&argument.value)) {
+                return None;
+            }
+            let scope = self.scope_of(statement);
+            let Some(Value::Function(Function::Api(api_function))) = self.value_of(callee, &scope) else {
+                return None;
+            };
+            let position = |parameter: &str| api_function.parameters.iter().position(|candidate| candidate.name == parameter);
+            let callback = argument(arguments, position("callback")?, "callback")?;
+            if !refers(callback) {
+                return None;
+            }
+            let message_type = argument(arguments, position("msg_type")?, "msg_type")?;
+            match self.value_of(message_type, &scope)? {
+                Value::Class(class) => Some(Value::Instance(class)),
+                _ => None,
+            }
+        })
+    }
+
+    fn returns(&self, function: Function<'a>) -> Option<Value<'a>> {
+        match function {
+            Function::Api(function) => Some(self.type_value(function.returns)),
+            Function::Source { definition, .. } => match &definition.kind {
+                StmtKind::FunctionDef { returns: Some(returns), .. } => self.annotation(returns, &self.scope_of(definition)),
+                _ => None,
+            },
+        }
+    }
+
+    // the classes that a class of the file derives from, as far as they are known
+    fn bases(&self, class: &'a Stmt<'a>) -> Vec<Class<'a>> {
+        let StmtKind::ClassDef { bases, .. } = &class.kind else {
+            return Vec::new();
+        };
+        let scope = self.scope_of(class);
+        bases.iter()
+            .filter(|base| base.keyword.is_none())
+            .filter_map(|base| match self.value_of(&base.value, &scope) {
+                Some(Value::Class(base)) => Some(base),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn resolve(&self, member: &Member<'a>) -> Option<Value<'a>> {
+        match &member.target {
+            Target::Value(value) => Some(*value),
+            Target::Assignment { value, annotated: false, scope } => self.value_of(value, scope),
+            Target::Assignment { value, annotated: true, scope } => self.annotation(value, scope),
+        }
+    }
+
+    pub fn member(&self, value: Value<'a>, name: &str) -> Option<Member<'a>> {
+        self.members(value).into_iter().find(|member| member.name == name)
+    }
+
+    fn member_value(&self, value: Value<'a>, name: &str) -> Option<Value<'a>> {
+        self.resolve(&self.member(value, name)?)
+    }
+
+    pub fn members(&self, value: Value<'a>) -> Vec<Member<'a>> {
+        match value {
+            Value::Module(module) => self.module_members(module),
+            Value::Class(class) => self.class_members(class, false).unwrap_or_default(),
+            Value::Instance(class) => self.class_members(class, true).unwrap_or_default(),
+            Value::Function(_) | Value::Builtin(_) => Vec::new(),
+        }
+    }
+
+    fn member_of(&self, name: &'a str, kind: MemberKind, value: Value<'a>) -> Member<'a> {
+        Member {
+            name,
+            kind,
+            target: Target::Value(value),
+            description: self.description(value),
+        }
+    }
+
+    fn module_member(&self, module: Module<'a>) -> Member<'a> {
+        let name = match module {
+            Module::Api(path) => path.rsplit('.').next().unwrap(),
+            Module::Package(package) => package,
+            Module::Messages(_) => "msg",
+        };
+        self.member_of(name, MemberKind::Module, Value::Module(module))
+    }
+
+    fn module_members(&self, module: Module<'a>) -> Vec<Member<'a>> {
+        match module {
+            Module::Api(path) => {
+                let mut members = Vec::new();
+                if let Some(module) = api::module(path) {
+                    members.extend(module.functions.iter().map(|function| {
+                        self.member_of(function.name, MemberKind::Function, Value::Function(Function::Api(function)))
+                    }));
+                    members.extend(module.classes.iter().map(|class| {
+                        self.member_of(class.name, MemberKind::Class, Value::Class(Class::Api(class)))
+                    }));
+                }
+                for submodule in &api::MODULES {
+                    let Some(rest) = submodule.name.strip_prefix(path).and_then(|rest| rest.strip_prefix('.')) else {
+                        continue;
+                    };
+                    let name = rest.split('.').next().unwrap();
+                    if !members.iter().any(|member| member.name == name) {
+                        let length = path.len() + 1 + name.len();
+                        members.push(self.module_member(Module::Api(&submodule.name[..length])));
+                    }
+                }
+                members
+            }
+            Module::Package(package) => vec![self.module_member(Module::Messages(package))],
+            Module::Messages(package) => self.library.messages.iter()
+                .filter(|message| message.package == package)
+                .map(|message| self.member_of(message.name, MemberKind::Class, Value::Class(Class::Message(message))))
+                .collect(),
+        }
+    }
+
+    fn class_members(&self, class: Class<'a>, instance: bool) -> Option<Vec<Member<'a>>> {
+        self.guarded(|| Some(match class {
+            Class::Api(class) => class.methods.iter()
+                .map(|method| self.member_of(method.name, MemberKind::Method, Value::Function(Function::Api(method))))
+                .chain(class.attributes.iter().map(|attribute| Member {
+                    name: attribute.name,
+                    kind: MemberKind::Property,
+                    target: Target::Value(self.type_value(attribute.annotation)),
+                    description: attribute.description.to_owned(),
+                }))
+                .collect(),
+            Class::Message(message) => message.fields.iter()
+                .map(|field| {
+                    let value = field.message_type()
+                        .and_then(|(package, name)| self.library.message(package.unwrap_or(message.package), name))
+                        .map_or(Value::Builtin(&field.annotation), |message| Value::Instance(Class::Message(message)));
+                    Member {
+                        name: field.name,
+                        kind: MemberKind::Property,
+                        target: Target::Value(value),
+                        description: field.description.clone(),
+                    }
+                })
+                .chain(message.constants.iter().map(|constant| Member {
+                    name: constant.name,
+                    kind: MemberKind::Constant,
+                    target: Target::Value(Value::Builtin(&constant.annotation)),
+                    description: match constant.description.is_empty() {
+                        true => format!("The value is `{}`", constant.value),
+                        false => format!("{}\n\nThe value is `{}`", constant.description, constant.value),
+                    },
+                }))
+                .collect(),
+            Class::Source(definition) => {
+                let StmtKind::ClassDef { body, .. } = &definition.kind else {
+                    return None;
+                };
+                let mut members = Vec::new();
+                let mut add = |member: Member<'a>| {
+                    if !members.iter().any(|existing: &Member| existing.name == member.name) {
+                        members.push(member);
+                    }
+                };
+                for statement in body.iter() {
+                    match &statement.kind {
+                        StmtKind::FunctionDef { name, .. } => {
+                            add(self.member_of(name.name, MemberKind::Method, Value::Function(Function::Source { definition: statement, bound: instance })));
+                        }
+                        StmtKind::ClassDef { name, .. } => {
+                            add(self.member_of(name.name, MemberKind::Class, Value::Class(Class::Source(statement))));
+                        }
+                        StmtKind::Assign { targets, value } => {
+                            for target in targets {
+                                if let ExprKind::Name(name) = target.kind {
+                                    add(Member {
+                                        name,
+                                        kind: MemberKind::Variable,
+                                        target: Target::Assignment { value, annotated: false, scope: self.scope_of(statement) },
+                                        description: String::new(),
+                                    });
+                                }
+                            }
+                        }
+                        StmtKind::AnnotatedAssign { target: Expr { kind: ExprKind::Name(name), .. }, annotation, .. } => {
+                            add(Member {
+                                name,
+                                kind: MemberKind::Variable,
+                                target: Target::Assignment { value: annotation, annotated: true, scope: self.scope_of(statement) },
+                                description: String::new(),
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+                if instance {
+                    for (value, annotated, statement, name) in self.instance_attributes(body) {
+                        add(Member {
+                            name,
+                            kind: MemberKind::Property,
+                            target: Target::Assignment { value, annotated, scope: self.scope(statement.range.start) },
+                            description: String::new(),
+                        });
+                    }
+                }
+                for base in self.bases(definition) {
+                    for member in self.class_members(base, instance).unwrap_or_default() {
+                        add(member);
+                    }
+                }
+                members
+            }
+        }))
+    }
+
+    // the attributes that the methods of a class assign to `self`, with their values or
+    // annotations and the statements that assign them
+    #[allow(clippy::type_complexity)]
+    fn instance_attributes(&self, body: &'a [Stmt<'a>]) -> Vec<(&'a Expr<'a>, bool, &'a Stmt<'a>, &'a str)> {
+        let mut attributes = Vec::new();
+        for method in body {
+            let StmtKind::FunctionDef { parameters, body, decorators, .. } = &method.kind else {
+                continue;
+            };
+            let Some(first) = parameters.first().filter(|_| decorators.is_empty()) else {
+                continue;
+            };
+            let attribute = |target: &'a Expr<'a>| match &target.kind {
+                ExprKind::Attribute { value, attribute } if matches!(value.kind, ExprKind::Name(name) if name == first.name.name) => Some(*attribute),
+                _ => None,
+            };
+            let mut pending = vec![&body[..]];
+            while let Some(block) = pending.pop() {
+                for statement in block {
+                    match &statement.kind {
+                        StmtKind::Assign { targets, value } => {
+                            attributes.extend(targets.iter().filter_map(attribute).map(|name| (value, false, statement, name)));
+                        }
+                        StmtKind::AnnotatedAssign { target, annotation, .. } => {
+                            attributes.extend(attribute(target).map(|name| (annotation, true, statement, name)));
+                        }
+                        _ => {}
+                    }
+                    if !is_definition(statement) {
+                        pending.extend(blocks(statement));
+                    }
+                }
+            }
+        }
+        attributes
+    }
+
+    fn class_name(&self, class: Class<'a>) -> &'a str {
+        match class {
+            Class::Api(class) => class.name,
+            Class::Message(message) => message.name,
+            Class::Source(definition) => match &definition.kind {
+                StmtKind::ClassDef { name, .. } => name.name,
+                _ => "",
+            },
+        }
+    }
+
+    // e.g., `float` or `Publisher`
+    pub fn type_name(&self, value: Value<'a>) -> String {
+        match value {
+            Value::Module(_) => "module".to_owned(),
+            Value::Class(class) => format!("type[{}]", self.class_name(class)),
+            Value::Instance(class) => self.class_name(class).to_owned(),
+            Value::Function(_) => "function".to_owned(),
+            Value::Builtin(name) => name.to_owned(),
+        }
+    }
+
+    pub fn description(&self, value: Value<'a>) -> String {
+        match value {
+            Value::Module(Module::Api(path)) => api::module(path).map_or("", |module| module.description).to_owned(),
+            Value::Module(Module::Package(package)) => format!("The package `{package}`"),
+            Value::Module(Module::Messages(package)) => format!("The message types of the package `{package}`"),
+            Value::Class(class) | Value::Instance(class) => match class {
+                Class::Api(class) => class.description.to_owned(),
+                Class::Message(message) => message.description.clone(),
+                Class::Source(definition) => match &definition.kind {
+                    StmtKind::ClassDef { body, .. } => docstring(body),
+                    _ => String::new(),
+                },
+            },
+            Value::Function(Function::Api(function)) => function.description.to_owned(),
+            Value::Function(Function::Source { definition, .. }) => match &definition.kind {
+                StmtKind::FunctionDef { body, .. } => docstring(body),
+                _ => String::new(),
+            },
+            Value::Builtin(_) => String::new(),
+        }
+    }
+
+    // how a function is called or a class is constructed
+    pub fn signature(&self, value: Value<'a>) -> Option<Signature> {
+        let api_parameters = |parameters: &[api::Parameter]| -> Vec<ParameterText> {
+            parameters.iter()
+                .map(|parameter| ParameterText {
+                    name: parameter.name.to_owned(),
+                    kind: SignatureParameterKind::Positional,
+                    annotation: Some(parameter.annotation.to_owned()),
+                    default: parameter.default.map(str::to_owned),
+                    description: parameter.description.to_owned(),
+                })
+                .collect()
+        };
+        let description = self.description(value);
+        match value {
+            Value::Function(Function::Api(function)) => {
+                let returns = function.returns.rsplit('.').next();
+                Some(Signature::new(function.name, api_parameters(function.parameters), returns, description))
+            }
+            Value::Function(Function::Source { definition, bound }) => {
+                let StmtKind::FunctionDef { name, parameters, returns, .. } = &definition.kind else {
+                    return None;
+                };
+                let text = |expr: &Expr| self.text[expr.range.clone()].to_owned();
+                let parameters = parameters.iter()
+                    .skip(usize::from(bound))
+                    .map(|parameter| ParameterText {
+                        name: parameter.name.name.to_owned(),
+                        kind: match parameter.kind {
+                            ParameterKind::Positional => SignatureParameterKind::Positional,
+                            ParameterKind::VariadicPositional => SignatureParameterKind::VariadicPositional,
+                            ParameterKind::VariadicKeyword => SignatureParameterKind::VariadicKeyword,
+                        },
+                        annotation: parameter.annotation.as_ref().map(text),
+                        default: parameter.default.as_ref().map(text),
+                        description: String::new(),
+                    })
+                    .collect();
+                let returns = returns.as_ref().map(text);
+                Some(Signature::new(name.name, parameters, returns.as_deref(), description))
+            }
+            Value::Class(Class::Api(class)) => Some(Signature::new(class.name, api_parameters(class.parameters), None, description)),
+            Value::Class(Class::Message(message)) => {
+                let parameters = message.fields.iter()
+                    .map(|field| ParameterText {
+                        name: field.name.to_owned(),
+                        kind: SignatureParameterKind::KeywordOnly,
+                        annotation: Some(field.annotation.clone()),
+                        default: Some(field.default.clone()),
+                        description: field.description.clone(),
+                    })
+                    .collect();
+                Some(Signature::new(message.name, parameters, None, description))
+            }
+            // the constructor of the class or of the class that it derives from
+            Value::Class(class @ Class::Source(definition)) => {
+                let name = self.class_name(class);
+                let signature = match self.member_value(Value::Instance(class), "__init__") {
+                    Some(Value::Function(function @ Function::Source { .. })) => self.signature(Value::Function(function))?,
+                    _ => self.bases(definition).into_iter().find_map(|base| self.signature(Value::Class(base)))?,
+                };
+                Some(Signature { description, ..signature.rename(name) })
+            }
+            _ => None,
+        }
+    }
+}
+
+// the statements of a body, also the nested ones, that are calls or assign the result of a call,
+// e.g., `self.subscription = self.create_subscription(...)`, with their calls
+fn statement_calls<'a>(body: &'a [Stmt<'a>], found: &mut Vec<(&'a Stmt<'a>, &'a Expr<'a>)>) {
+    for statement in body {
+        let value = match &statement.kind {
+            StmtKind::Expr(value) | StmtKind::Assign { value, .. } | StmtKind::AnnotatedAssign { value: Some(value), .. } => Some(value),
+            _ => None,
+        };
+        if let Some(value @ Expr { kind: ExprKind::Call { .. }, .. }) = value {
+            found.push((statement, value));
+        }
+        for block in blocks(statement) {
+            statement_calls(block, found);
+        }
+    }
+}
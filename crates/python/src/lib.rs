@@ -0,0 +1,71 @@
+// checks Python files for syntax errors, indentation errors and names that are not defined, finds
+// their classes and functions and answers completions, signature help and hover documentation from
+// the API of rclpy and the message types, this crate is built into its own WebAssembly module, which the editor runs in a web worker
+pub mod api;
+mod assist;
+pub mod ast;
+pub mod diagnostics;
+mod infer;
+mod messages;
+mod names;
+pub mod parse;
+pub mod symbols;
+pub mod tokens;
+mod worker;
+
+use common::diagnostics::Diagnostic;
+
+pub fn diagnostics(text: &str) -> Vec<Diagnostic> {
+    let module = parse::parse(text);
+    let mut diagnostics = module.errors.into_iter()
+        .map(|error| Diagnostic::error(error.range, error.message))
+        .collect::<Vec<_>>();
+    // a broken statement can hide where a name is bound, so names are only checked once the file
+    // parses
+    if diagnostics.is_empty() {
+        diagnostics.extend(names::undefined(&module.body).into_iter()
+            .map(|name| Diagnostic::warning(name.range, format!("`{}` is not defined", name.name))));
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use common::diagnostics::Severity;
+
+    use super::*;
+
+    fn messages(text: &str) -> Vec<(Severity, String)> {
+        diagnostics(text).into_iter()
+            .map(|diagnostic| (diagnostic.severity, diagnostic.message))
+            .collect()
+    }
+
+    #[test]
+    fn valid_file() {
+        assert!(messages("import rclpy\n\ndef f(x):\n    return x + 1\n\nprint(f(1))\n").is_empty());
+    }
+
+    #[test]
+    fn syntax_errors() {
+        assert_eq!(messages("x = (1,\n"), [(Severity::Error, "`(` was never closed".to_owned())]);
+        assert_eq!(messages("x = 1)\n"), [(Severity::Error, "unmatched `)`".to_owned())]);
+        assert_eq!(messages("def f(:\n    pass\n")[0].0, Severity::Error);
+        assert_eq!(messages("break\n"), [(Severity::Error, "`break` outside of a loop".to_owned())]);
+    }
+
+    #[test]
+    fn indentation_errors() {
+        assert_eq!(messages("if True:\npass\n"), [
+            (Severity::Error, "expected an indented block after `if` statement on line 1".to_owned()),
+        ]);
+        assert_eq!(messages("if True:\n    x = 1\n  y = 2\n")[0].1, "unindent does not match any outer indentation level");
+        assert_eq!(messages("if True:\n\tx = 1\n        y = 2\n")[0].1, "inconsistent use of tabs and spaces in indentation");
+    }
+
+    #[test]
+    fn undefined_names_only_without_syntax_errors() {
+        assert_eq!(messages("print(x)\n"), [(Severity::Warning, "`x` is not defined".to_owned())]);
+        assert!(messages("print(x\n").iter().all(|(severity, _)| *severity == Severity::Error));
+    }
+}
@@ -0,0 +1,160 @@
+// the message types of `.msg` files, whose fields become the keyword arguments of the constructor
+// and the attributes of the message classes in Python
+use crate::api::MessageDefinition;
+
+pub struct Message<'a> {
+    pub package: &'a str,
+    pub name: &'a str,
+    pub description: String,
+    pub fields: Vec<Field<'a>>,
+    pub constants: Vec<Constant<'a>>,
+}
+
+pub struct Field<'a> {
+    pub name: &'a str,
+    // as written, e.g., `float64[]` or `geometry_msgs/Point`
+    pub type_name: &'a str,
+    // the type in Python, e.g., `list[float]`
+    pub annotation: String,
+    pub default: String,
+    pub description: String,
+}
+
+pub struct Constant<'a> {
+    pub name: &'a str,
+    pub annotation: String,
+    pub value: &'a str,
+    pub description: String,
+}
+
+// the type in Python and the default value of a primitive type
+fn primitive(type_name: &str) -> Option<(&'static str, &'static str)> {
+    // e.g., `string<=10`
+    let type_name = type_name.split("<=").next().unwrap();
+    let primitive = match type_name {
+        "bool" => ("bool", "False"),
+        "byte" => ("bytes", "b'\\x00'"),
+        "float32" | "float64" => ("float", "0.0"),
+        "char" | "int8" | "uint8" | "int16" | "uint16" | "int32" | "uint32" | "int64" | "uint64" => ("int", "0"),
+        "string" | "wstring" => ("str", "''"),
+        _ => return None,
+    };
+    Some(primitive)
+}
+
+impl<'a> Field<'a> {
+    // the element type and the size of arrays, which is None if it is not fixed
+    fn array(&self) -> Option<(&'a str, Option<&'a str>)> {
+        let (element, size) = self.type_name.strip_suffix(']')?.split_once('[')?;
+        let size = size.trim_start_matches("<=");
+        Some((element, (!size.is_empty() && !self.type_name.contains("[<=")).then_some(size)))
+    }
+
+    fn element_type(&self) -> &'a str {
+        self.array().map_or(self.type_name, |(element, _)| element)
+    }
+
+    // the package, which is None for the package of the field, and the name of a message type
+    pub fn message_type(&self) -> Option<(Option<&'a str>, &'a str)> {
+        let element = self.element_type();
+        if self.array().is_some() || primitive(element).is_some() {
+            return None;
+        }
+        Some(match element.split_once('/') {
+            // also `package/msg/Name`
+            Some((package, name)) => (Some(package), name.rsplit('/').next().unwrap()),
+            None => (None, element),
+        })
+    }
+}
+
+// the comment lines joined into paragraphs
+fn join(lines: &[&str]) -> String {
+    let mut text = String::new();
+    for line in lines {
+        match (line.is_empty(), text.is_empty() || text.ends_with('\n')) {
+            (true, _) => text.push_str("\n\n"),
+            (false, true) => text.push_str(line),
+            (false, false) => {
+                text.push(' ');
+                text.push_str(line);
+            }
+        }
+    }
+    text.trim().to_owned()
+}
+
+pub fn parse<'a>(definition: &MessageDefinition<'a>) -> Message<'a> {
+    let mut message = Message {
+        package: definition.package,
+        name: definition.name,
+        description: String::new(),
+        fields: Vec::new(),
+        constants: Vec::new(),
+    };
+    // the comments since the last field
+    let mut comments = Vec::new();
+    let mut described = false;
+    for line in definition.text.lines() {
+        let (code, comment) = match line.split_once('#') {
+            Some((code, comment)) => (code.trim(), Some(comment.strip_prefix(' ').unwrap_or(comment).trim_end())),
+            None => (line.trim(), None),
+        };
+        if code.is_empty() {
+            comments.extend(comment);
+            continue;
+        }
+        if !described {
+            message.description = join(&comments);
+            comments.clear();
+            described = true;
+        }
+        comments.extend(comment);
+        let description = join(&comments);
+        comments.clear();
+        let Some((type_name, rest)) = code.split_once(char::is_whitespace) else {
+            continue;
+        };
+        let rest = rest.trim();
+        let annotation = |element: &str| primitive(element).map_or_else(
+            || element.rsplit('/').next().unwrap().to_owned(),
+            |(annotation, _)| annotation.to_owned(),
+        );
+        // e.g., `int32 MAXIMUM=10`
+        if let Some((name, value)) = rest.split_once('=') {
+            message.constants.push(Constant {
+                name: name.trim(),
+                annotation: annotation(type_name),
+                value: value.trim(),
+                description,
+            });
+            continue;
+        }
+        let (name, default) = match rest.split_once(char::is_whitespace) {
+            Some((name, default)) => (name, Some(default.trim())),
+            None => (rest, None),
+        };
+        let mut field = Field {
+            name,
+            type_name,
+            annotation: String::new(),
+            default: String::new(),
+            description,
+        };
+        let element = field.element_type();
+        let element_default = primitive(element).map_or_else(|| format!("{}()", annotation(element)), |(_, default)| default.to_owned());
+        (field.annotation, field.default) = match field.array() {
+            Some((_, Some(size))) => (format!("list[{}]", annotation(element)), format!("[{element_default}] * {size}")),
+            Some((_, None)) => (format!("list[{}]", annotation(element)), "[]".to_owned()),
+            None => (annotation(element), element_default),
+        };
+        if let Some(default) = default {
+            field.default = default.to_owned();
+        }
+        message.fields.push(field);
+    }
+    if !described {
+        message.description = join(&comments);
+    }
+    message
+}
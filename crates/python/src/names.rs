@@ -0,0 +1,436 @@
+// finds the names that are used but bound nowhere they could be looked up from, i.e., neither in
+// their scope, nor in the enclosing functions, the module or the builtins, where a name is bound
+// does not matter since functions are only called later
+use std::collections::HashSet;
+
+use crate::ast::{Expr, ExprKind, Name, Parameter, Stmt, StmtKind};
+
+const BUILTINS: [&str; 162] = [
+    "ArithmeticError", "AssertionError", "AttributeError", "BaseException", "BaseExceptionGroup",
+    "BlockingIOError", "BrokenPipeError", "BufferError", "BytesWarning", "ChildProcessError",
+    "ConnectionAbortedError", "ConnectionError", "ConnectionRefusedError", "ConnectionResetError",
+    "DeprecationWarning", "EOFError", "Ellipsis", "EncodingWarning", "EnvironmentError", "Exception",
+    "ExceptionGroup", "FileExistsError", "FileNotFoundError", "FloatingPointError", "FutureWarning",
+    "GeneratorExit", "IOError", "ImportError", "ImportWarning", "IndentationError", "IndexError",
+    "InterruptedError", "IsADirectoryError", "KeyError", "KeyboardInterrupt", "LookupError",
+    "MemoryError", "ModuleNotFoundError", "NameError", "NotADirectoryError", "NotImplemented",
+    "NotImplementedError", "OSError", "OverflowError", "PendingDeprecationWarning", "PermissionError",
+    "ProcessLookupError", "RecursionError", "ReferenceError", "ResourceWarning", "RuntimeError",
+    "RuntimeWarning", "StopAsyncIteration", "StopIteration", "SyntaxError", "SyntaxWarning",
+    "SystemError", "SystemExit", "TabError", "TimeoutError", "TypeError", "UnboundLocalError",
+    "UnicodeDecodeError", "UnicodeEncodeError", "UnicodeError", "UnicodeTranslateError",
+    "UnicodeWarning", "UserWarning", "ValueError", "Warning", "ZeroDivisionError", "abs", "aiter",
+    "all", "anext", "any", "ascii", "bin", "bool", "breakpoint", "bytearray", "bytes", "callable",
+    "chr", "classmethod", "compile", "complex", "copyright", "credits", "delattr", "dict", "dir",
+    "divmod", "enumerate", "eval", "exec", "exit", "filter", "float", "format", "frozenset",
+    "getattr", "globals", "hasattr", "hash", "help", "hex", "id", "input", "int", "isinstance",
+    "issubclass", "iter", "len", "license", "list", "locals", "map", "max", "memoryview", "min",
+    "next", "object", "oct", "open", "ord", "pow", "print", "property", "quit", "range", "repr",
+    "reversed", "round", "set", "setattr", "slice", "sorted", "staticmethod", "str", "sum", "super",
+    "tuple", "type", "vars", "zip", "__import__", "__build_class__", "__debug__", "__doc__",
+    "__name__", "__file__", "__package__", "__spec__", "__loader__", "__builtins__", "__path__",
+    "__annotations__", "__class__", "__module__", "__qualname__", "__dict__",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ScopeKind {
+    Module,
+    // names bound in a class are not seen by the functions in it
+    Class,
+    Function,
+    Comprehension,
+}
+
+struct Scope<'a> {
+    kind: ScopeKind,
+    parent: Option<usize>,
+    bindings: HashSet<&'a str>,
+    uses: Vec<Name<'a>>,
+}
+
+struct Resolver<'a> {
+    // the module comes first
+    scopes: Vec<Scope<'a>>,
+    current: usize,
+    // `from module import *` can bind any name
+    star_import: bool,
+}
+
+impl<'a> Resolver<'a> {
+    fn bind(&mut self, name: &'a str) {
+        self.scopes[self.current].bindings.insert(name);
+    }
+
+    // returns the scope to go back to
+    fn enter(&mut self, kind: ScopeKind) -> usize {
+        self.scopes.push(Scope {
+            kind,
+            parent: Some(self.current),
+            bindings: HashSet::new(),
+            uses: Vec::new(),
+        });
+        std::mem::replace(&mut self.current, self.scopes.len() - 1)
+    }
+
+    fn is_defined(&self, scope: usize, name: &str) -> bool {
+        let mut next = Some(scope);
+        while let Some(index) = next {
+            let enclosing = &self.scopes[index];
+            // a class only provides its names to the code directly inside of it
+            let visible = index == scope || enclosing.kind != ScopeKind::Class;
+            if visible && enclosing.bindings.contains(name) {
+                return true;
+            }
+            next = enclosing.parent;
+        }
+        BUILTINS.contains(&name)
+    }
+
+    fn statements(&mut self, statements: &'a [Stmt<'a>]) {
+        for statement in statements {
+            self.statement(statement);
+        }
+    }
+
+    fn statement(&mut self, statement: &'a Stmt<'a>) {
+        match &statement.kind {
+            StmtKind::Expr(value) => self.load(value),
+            StmtKind::Assign { targets, value } => {
+                self.load(value);
+                for target in targets {
+                    self.store(target);
+                }
+            }
+            StmtKind::AugmentedAssign { target, value } => {
+                self.load(value);
+                self.store(target);
+            }
+            StmtKind::AnnotatedAssign { target, annotation, value } => {
+                self.load(annotation);
+                if let Some(value) = value {
+                    self.load(value);
+                }
+                self.store(target);
+            }
+            StmtKind::Delete(values) | StmtKind::Raise(values) | StmtKind::Assert(values) => {
+                for value in values {
+                    self.load(value);
+                }
+            }
+            StmtKind::Return(value) => {
+                if let Some(value) = value {
+                    self.load(value);
+                }
+            }
+            StmtKind::Pass | StmtKind::Break | StmtKind::Continue => {}
+            StmtKind::Global(names) => {
+                for name in names {
+                    self.scopes[0].bindings.insert(name.name);
+                    self.bind(name.name);
+                }
+            }
+            // the name has to be bound in an enclosing function, which is not checked
+            StmtKind::Nonlocal(names) => {
+                for name in names {
+                    self.bind(name.name);
+                }
+            }
+            // `import rclpy.node` binds `rclpy`
+            StmtKind::Import(aliases) => {
+                for alias in aliases {
+                    match &alias.alias {
+                        Some(alias) => self.bind(alias.name),
+                        None => self.bind(alias.name.name.split('.').next().unwrap()),
+                    }
+                }
+            }
+            StmtKind::ImportFrom { names, star, .. } => {
+                self.star_import |= star;
+                for alias in names {
+                    self.bind(alias.alias.as_ref().unwrap_or(&alias.name).name);
+                }
+            }
+            StmtKind::If { test, body, orelse } | StmtKind::While { test, body, orelse } => {
+                self.load(test);
+                self.statements(body);
+                self.statements(orelse);
+            }
+            StmtKind::For { target, iterable, body, orelse } => {
+                self.load(iterable);
+                self.store(target);
+                self.statements(body);
+                self.statements(orelse);
+            }
+            StmtKind::Try { body, handlers, orelse, finally } => {
+                self.statements(body);
+                for handler in handlers {
+                    if let Some(exception) = &handler.exception {
+                        self.load(exception);
+                    }
+                    if let Some(name) = &handler.name {
+                        self.bind(name.name);
+                    }
+                    self.statements(&handler.body);
+                }
+                self.statements(orelse);
+                self.statements(finally);
+            }
+            StmtKind::With { items, body } => {
+                for (context, target) in items {
+                    self.load(context);
+                    if let Some(target) = target {
+                        self.store(target);
+                    }
+                }
+                self.statements(body);
+            }
+            StmtKind::FunctionDef { name, decorators, parameters, returns, body } => {
+                for decorator in decorators {
+                    self.load(decorator);
+                }
+                self.parameter_values(parameters);
+                if let Some(returns) = returns {
+                    self.load(returns);
+                }
+                self.bind(name.name);
+                let previous = self.enter(ScopeKind::Function);
+                for parameter in parameters {
+                    self.bind(parameter.name.name);
+                }
+                self.statements(body);
+                self.current = previous;
+            }
+            StmtKind::ClassDef { name, decorators, bases, body } => {
+                for decorator in decorators {
+                    self.load(decorator);
+                }
+                for base in bases {
+                    self.load(&base.value);
+                }
+                self.bind(name.name);
+                let previous = self.enter(ScopeKind::Class);
+                self.statements(body);
+                self.current = previous;
+            }
+            StmtKind::Match { subject, cases } => {
+                self.load(subject);
+                for case in cases {
+                    self.pattern(&case.pattern);
+                    if let Some(guard) = &case.guard {
+                        self.load(guard);
+                    }
+                    self.statements(&case.body);
+                }
+            }
+        }
+    }
+
+    // the defaults and annotations, which are evaluated where the function is defined
+    fn parameter_values(&mut self, parameters: &'a [Parameter<'a>]) {
+        for parameter in parameters {
+            if let Some(annotation) = &parameter.annotation {
+                self.load(annotation);
+            }
+            if let Some(default) = &parameter.default {
+                self.load(default);
+            }
+        }
+    }
+
+    fn load(&mut self, expression: &'a Expr<'a>) {
+        match &expression.kind {
+            ExprKind::Name(name) => self.scopes[self.current].uses.push(Name { name, range: expression.range.clone() }),
+            ExprKind::Constant(_) => {}
+            ExprKind::Attribute { value, .. } | ExprKind::Starred(value) => self.load(value),
+            ExprKind::Subscript { value, slice } => {
+                self.load(value);
+                self.load(slice);
+            }
+            ExprKind::Call { function, arguments } => {
+                self.load(function);
+                for argument in arguments {
+                    self.load(&argument.value);
+                }
+            }
+            ExprKind::Tuple(elements) | ExprKind::List(elements) | ExprKind::Set(elements) | ExprKind::Operation(_, elements) => {
+                for element in elements {
+                    self.load(element);
+                }
+            }
+            ExprKind::Dict(items) => {
+                for (key, value) in items {
+                    if let Some(key) = key {
+                        self.load(key);
+                    }
+                    self.load(value);
+                }
+            }
+            // binds in the function around a comprehension
+            ExprKind::Named { target, value } => {
+                self.load(value);
+                let mut scope = self.current;
+                while self.scopes[scope].kind == ScopeKind::Comprehension {
+                    scope = self.scopes[scope].parent.unwrap();
+                }
+                self.scopes[scope].bindings.insert(target.name);
+            }
+            ExprKind::Lambda { parameters, body } => {
+                self.parameter_values(parameters);
+                let previous = self.enter(ScopeKind::Function);
+                for parameter in parameters {
+                    self.bind(parameter.name.name);
+                }
+                self.load(body);
+                self.current = previous;
+            }
+            // the first iterable is evaluated outside of the comprehension
+            ExprKind::Comprehension { elements, generators, .. } => {
+                if let Some(first) = generators.first() {
+                    self.load(&first.iterable);
+                }
+                let previous = self.enter(ScopeKind::Comprehension);
+                for (index, generator) in generators.iter().enumerate() {
+                    if index > 0 {
+                        self.load(&generator.iterable);
+                    }
+                    self.store(&generator.target);
+                    for condition in &generator.conditions {
+                        self.load(condition);
+                    }
+                }
+                for element in elements {
+                    self.load(element);
+                }
+                self.current = previous;
+            }
+        }
+    }
+
+    fn store(&mut self, target: &'a Expr<'a>) {
+        match &target.kind {
+            ExprKind::Name(name) => self.bind(name),
+            ExprKind::Tuple(elements) | ExprKind::List(elements) => {
+                for element in elements {
+                    self.store(element);
+                }
+            }
+            ExprKind::Starred(value) => self.store(value),
+            _ => self.load(target),
+        }
+    }
+
+    // see `Case` for how patterns are written
+    fn pattern(&mut self, pattern: &'a Expr<'a>) {
+        match &pattern.kind {
+            ExprKind::Name("_") | ExprKind::Constant(_) => {}
+            ExprKind::Name(name) => self.bind(name),
+            ExprKind::Call { function, arguments } => {
+                self.load(function);
+                for argument in arguments {
+                    self.pattern(&argument.value);
+                }
+            }
+            ExprKind::Tuple(elements) | ExprKind::List(elements) | ExprKind::Operation(_, elements) => {
+                for element in elements {
+                    self.pattern(element);
+                }
+            }
+            ExprKind::Starred(value) => self.pattern(value),
+            ExprKind::Dict(items) => {
+                for (key, value) in items {
+                    if let Some(key) = key {
+                        self.load(key);
+                    }
+                    self.pattern(value);
+                }
+            }
+            ExprKind::Named { target, value } => {
+                self.pattern(value);
+                self.bind(target.name);
+            }
+            _ => self.load(pattern),
+        }
+    }
+}
+
+pub fn undefined<'a>(body: &'a [Stmt<'a>]) -> Vec<Name<'a>> {
+    let mut resolver = Resolver {
+        scopes: vec![Scope {
+            kind: ScopeKind::Module,
+            parent: None,
+            bindings: HashSet::new(),
+            uses: Vec::new(),
+        }],
+        current: 0,
+        star_import: false,
+    };
+    resolver.statements(body);
+    if resolver.star_import {
+        return Vec::new();
+    }
+    let mut undefined = resolver.scopes.iter()
+        .enumerate()
+        .flat_map(|(index, scope)| scope.uses.iter().map(move |name| (index, name)))
+        .filter(|(index, name)| !resolver.is_defined(*index, name.name))
+        .map(|(_, name)| name.clone())
+        .collect::<Vec<_>>();
+    undefined.sort_by_key(|name| name.range.start);
+    undefined
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn undefined_names(text: &str) -> Vec<String> {
+        let module = parse::parse(text);
+        assert!(module.errors.is_empty(), "{:?}", module.errors);
+        undefined(&module.body).into_iter().map(|name| name.name.to_owned()).collect()
+    }
+
+    #[test]
+    fn builtins_and_imports() {
+        assert!(undefined_names("import rclpy\nfrom os import path\nprint(len(path), rclpy, None, True)\n").is_empty());
+        assert_eq!(undefined_names("print(missing)\n"), ["missing"]);
+    }
+
+    #[test]
+    fn functions() {
+        assert!(undefined_names("def f(a, *args, b=1, **kwargs):\n    return a, args, b, kwargs, f\n").is_empty());
+        assert_eq!(undefined_names("def f():\n    return local\n"), ["local"]);
+        // a name that is bound later in the module is found when the function runs
+        assert!(undefined_names("def f():\n    return g()\ndef g():\n    pass\n").is_empty());
+    }
+
+    #[test]
+    fn comprehensions() {
+        assert!(undefined_names("squares = [x * x for x in range(3) if x]\n").is_empty());
+        assert!(undefined_names("pairs = {k: v for k, v in {}.items()}\n").is_empty());
+        // the variable does not leak out of the comprehension
+        assert_eq!(undefined_names("[x for x in range(3)]\nprint(x)\n"), ["x"]);
+    }
+
+    #[test]
+    fn class_scope() {
+        // names of the class body are not visible in its methods
+        assert_eq!(undefined_names("class A:\n    size = 1\n    def f(self):\n        return size\n"), ["size"]);
+        assert!(undefined_names("class A:\n    size = 1\n    double = size * 2\n").is_empty());
+    }
+
+    #[test]
+    fn global_and_nonlocal() {
+        assert!(undefined_names("def f():\n    global counter\n    counter = 1\ndef g():\n    return counter\n").is_empty());
+        assert!(undefined_names("def f():\n    x = 1\n    def g():\n        nonlocal x\n        x = 2\n    return g\n").is_empty());
+    }
+
+    #[test]
+    fn other_bindings() {
+        assert!(undefined_names("for i in range(3):\n    pass\nprint(i)\n").is_empty());
+        assert!(undefined_names("with open('f') as f:\n    pass\nprint(f)\n").is_empty());
+        assert!(undefined_names("try:\n    pass\nexcept Exception as error:\n    print(error)\n").is_empty());
+        assert!(undefined_names("if (n := 10) > 5:\n    print(n)\n").is_empty());
+        assert!(undefined_names("f = lambda x: x + 1\n").is_empty());
+        assert!(undefined_names("from os import *\nprint(anything)\n").is_empty());
+    }
+}
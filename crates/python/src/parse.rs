@@ -0,0 +1,1438 @@
+// a recursive descent parser for Python, which reports an error for each broken statement and
+// carries on with the next one, offsets are in bytes
+use std::ops::Range;
+
+use crate::{
+    ast::{Alias, Argument, Case, Expr, ExprKind, Generator, Handler, Name, Parameter, ParameterKind, Stmt, StmtKind},
+    diagnostics::SyntaxError,
+    tokens::{self, is_keyword, Token, TokenKind},
+};
+
+pub struct Module<'a> {
+    pub body: Vec<Stmt<'a>>,
+    // sorted by their position
+    pub errors: Vec<SyntaxError>,
+}
+
+// the error has been reported, the statement is skipped
+struct Failed;
+
+type Parsed<T> = Result<T, Failed>;
+
+// where a statement is, which decides whether `return`, `yield`, `break` and `continue` are allowed
+#[derive(Clone, Copy, Default)]
+struct Context {
+    function: bool,
+    in_loop: bool,
+}
+
+const COMPARISONS: [&str; 6] = ["==", "!=", "<", ">", "<=", ">="];
+
+// from the loosest to the tightest binding
+const BINARY_OPERATORS: [&[&str]; 6] = [
+    &["|"],
+    &["^"],
+    &["&"],
+    &["<<", ">>"],
+    &["+", "-"],
+    &["*", "/", "//", "%", "@"],
+];
+
+const AUGMENTED_ASSIGNMENTS: [&str; 13] = ["+=", "-=", "*=", "/=", "//=", "%=", "@=", "&=", "|=", "^=", ">>=", "<<=", "**="];
+
+struct Parser<'a> {
+    text: &'a str,
+    // ends with the end of the file
+    tokens: Vec<Token<'a>>,
+    index: usize,
+    errors: Vec<SyntaxError>,
+    token_errors: Vec<SyntaxError>,
+    statement_start: usize,
+    context: Context,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> &Token<'a> {
+        &self.tokens[self.index]
+    }
+
+    fn peek_at(&self, offset: usize) -> &Token<'a> {
+        &self.tokens[(self.index + offset).min(self.tokens.len() - 1)]
+    }
+
+    fn advance(&mut self) -> Token<'a> {
+        let token = self.tokens[self.index].clone();
+        if token.kind != TokenKind::EndOfFile {
+            self.index += 1;
+        }
+        token
+    }
+
+    // keywords and operators
+    fn is(&self, text: &str) -> bool {
+        Self::token_is(self.peek(), text)
+    }
+
+    fn token_is(token: &Token, text: &str) -> bool {
+        matches!(token.kind, TokenKind::Name | TokenKind::Operator) && token.text == text
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        let found = self.is(text);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn at_name(&self) -> bool {
+        self.peek().kind == TokenKind::Name && !is_keyword(self.peek().text)
+    }
+
+    fn at_end_of_line(&self) -> bool {
+        matches!(self.peek().kind, TokenKind::Newline | TokenKind::EndOfFile)
+    }
+
+    fn previous_end(&self) -> usize {
+        match self.index {
+            0 => 0,
+            index => self.tokens[index - 1].range.end,
+        }
+    }
+
+    fn line(&self, offset: usize) -> usize {
+        self.text[..offset].matches('\n').count() + 1
+    }
+
+    // an error of the tokenizer between the start of the statement and the next token explains
+    // the error, e.g., an invalid character, which is not a token
+    fn report(&mut self, range: Range<usize>, message: impl Into<String>) {
+        let end = range.end.max(self.peek().range.end);
+        let explained = self.token_errors.iter()
+            .any(|error| error.range.start >= self.statement_start && error.range.start <= end);
+        if !explained {
+            self.errors.push(SyntaxError { range, message: message.into() });
+        }
+    }
+
+    fn error(&mut self, range: Range<usize>, message: impl Into<String>) -> Failed {
+        self.report(range, message);
+        Failed
+    }
+
+    // an error at the next token, or at the end of the line for tokens without text
+    fn unexpected(&mut self, message: impl Into<String>) -> Failed {
+        let range = match self.peek().kind {
+            TokenKind::Newline | TokenKind::EndOfFile | TokenKind::Indent | TokenKind::Dedent if self.index > 0 => {
+                self.tokens[self.index - 1].range.clone()
+            }
+            _ => self.peek().range.clone(),
+        };
+        self.error(range, message)
+    }
+
+    fn expect(&mut self, text: &str) -> Parsed<Range<usize>> {
+        match self.is(text) {
+            true => Ok(self.advance().range),
+            false => Err(self.unexpected(format!("expected `{text}`"))),
+        }
+    }
+
+    // the closing bracket after a list of elements
+    fn close(&mut self, closing: &str) -> Parsed<Range<usize>> {
+        match self.is(closing) {
+            true => Ok(self.advance().range),
+            false => Err(self.unexpected(format!("expected `,` or `{closing}`"))),
+        }
+    }
+
+    fn with_context<T>(&mut self, context: Context, parse: impl FnOnce(&mut Self) -> Parsed<T>) -> Parsed<T> {
+        let saved = std::mem::replace(&mut self.context, context);
+        let parsed = parse(self);
+        self.context = saved;
+        parsed
+    }
+
+    fn name(&mut self) -> Parsed<Name<'a>> {
+        match self.at_name() {
+            true => {
+                let token = self.advance();
+                Ok(Name { name: token.text, range: token.range })
+            }
+            false => Err(self.unexpected("expected a name")),
+        }
+    }
+
+    // e.g., `rclpy.node`
+    fn dotted_name(&mut self) -> Parsed<Name<'a>> {
+        let first = self.name()?;
+        let mut end = first.range.end;
+        while self.eat(".") {
+            end = self.name()?.range.end;
+        }
+        Ok(Name {
+            name: &self.text[first.range.start..end],
+            range: first.range.start..end,
+        })
+    }
+
+    // skips the rest of a statement that has an error, together with the block that follows it
+    fn recover(&mut self) {
+        while !matches!(self.peek().kind, TokenKind::Newline | TokenKind::EndOfFile | TokenKind::Indent | TokenKind::Dedent) {
+            self.advance();
+        }
+        if self.peek().kind == TokenKind::Newline {
+            self.advance();
+        }
+        if self.peek().kind == TokenKind::Indent {
+            self.advance();
+            self.statements(true);
+        }
+    }
+
+    // the statements of the file or, if nested, of an indented block up to its end
+    fn statements(&mut self, nested: bool) -> Vec<Stmt<'a>> {
+        let mut statements = Vec::new();
+        loop {
+            match self.peek().kind {
+                TokenKind::EndOfFile => break,
+                TokenKind::Dedent => {
+                    self.advance();
+                    if nested {
+                        break;
+                    }
+                }
+                TokenKind::Indent => {
+                    self.statement_start = self.advance().range.start;
+                    let range = self.peek().range.clone();
+                    self.report(range, "unexpected indent");
+                    statements.extend(self.statements(true));
+                }
+                TokenKind::Newline => {
+                    self.advance();
+                }
+                _ => match self.statement() {
+                    Ok(statement) => statements.extend(statement),
+                    Err(Failed) => self.recover(),
+                },
+            }
+        }
+        statements
+    }
+
+    fn statement(&mut self) -> Parsed<Vec<Stmt<'a>>> {
+        let start = self.peek().range.start;
+        self.statement_start = start;
+        let keyword = match self.peek().kind {
+            TokenKind::Name | TokenKind::Operator => self.peek().text,
+            _ => "",
+        };
+        let statement = match keyword {
+            "if" => self.if_statement(start)?,
+            "while" => self.while_statement(start)?,
+            "for" => self.for_statement(start)?,
+            "try" => self.try_statement(start)?,
+            "with" => self.with_statement(start)?,
+            "def" => self.function(Vec::new(), start)?,
+            "class" => self.class(Vec::new(), start)?,
+            "@" => self.decorated(start)?,
+            "async" if matches!(self.peek_at(1).text, "def" | "for" | "with") => {
+                self.advance();
+                match self.peek().text {
+                    "def" => self.function(Vec::new(), start)?,
+                    "for" => self.for_statement(start)?,
+                    _ => self.with_statement(start)?,
+                }
+            }
+            "match" if self.is_match_statement() => self.match_statement(start)?,
+            "elif" => return Err(self.unexpected("`elif` has to follow the block of an `if` statement")),
+            "else" => return Err(self.unexpected("`else` has to follow the block of an `if`, `for`, `while` or `try` statement")),
+            "except" | "finally" => return Err(self.unexpected(format!("`{keyword}` has to follow the block of a `try` statement"))),
+            _ => return self.simple_statements(),
+        };
+        Ok(vec![statement])
+    }
+
+    // the body of a compound statement after its header, either indented on the next lines or on
+    // the same line after the `:`
+    fn block(&mut self, header: &str, header_start: usize) -> Parsed<Vec<Stmt<'a>>> {
+        let colon = self.expect(":")?;
+        if !self.at_end_of_line() {
+            return self.simple_statements();
+        }
+        self.advance();
+        if self.peek().kind != TokenKind::Indent {
+            let line = self.line(header_start);
+            let range = self.tokens[self.index..]
+                .iter()
+                .find(|token| token.kind != TokenKind::Dedent)
+                .filter(|token| token.kind != TokenKind::EndOfFile)
+                .map_or(colon, |token| token.range.clone());
+            self.report(range, format!("expected an indented block after {header} on line {line}"));
+            return Ok(Vec::new());
+        }
+        self.advance();
+        Ok(self.statements(true))
+    }
+
+    fn else_block(&mut self) -> Parsed<Vec<Stmt<'a>>> {
+        match self.is("else") {
+            true => {
+                let token = self.advance();
+                self.block("`else`", token.range.start)
+            }
+            false => Ok(Vec::new()),
+        }
+    }
+
+    // also reads an `elif`, which becomes an `if` in the `else` block
+    fn if_statement(&mut self, start: usize) -> Parsed<Stmt<'a>> {
+        let keyword = self.advance();
+        let test = self.named_expression()?;
+        let body = self.block(&format!("`{}` statement", keyword.text), keyword.range.start)?;
+        let orelse = match self.is("elif") {
+            true => vec![self.if_statement(self.peek().range.start)?],
+            false => self.else_block()?,
+        };
+        Ok(Stmt {
+            kind: StmtKind::If { test, body, orelse },
+            range: start..self.previous_end(),
+        })
+    }
+
+    fn while_statement(&mut self, start: usize) -> Parsed<Stmt<'a>> {
+        let keyword = self.advance();
+        let test = self.named_expression()?;
+        let context = Context { in_loop: true, ..self.context };
+        let body = self.with_context(context, |parser| parser.block("`while` statement", keyword.range.start))?;
+        let orelse = self.else_block()?;
+        Ok(Stmt {
+            kind: StmtKind::While { test, body, orelse },
+            range: start..self.previous_end(),
+        })
+    }
+
+    fn for_statement(&mut self, start: usize) -> Parsed<Stmt<'a>> {
+        let keyword = self.advance();
+        let target = self.targets()?;
+        self.expect("in")?;
+        let iterable = self.expressions()?;
+        let context = Context { in_loop: true, ..self.context };
+        let body = self.with_context(context, |parser| parser.block("`for` statement", keyword.range.start))?;
+        let orelse = self.else_block()?;
+        Ok(Stmt {
+            kind: StmtKind::For { target, iterable, body, orelse },
+            range: start..self.previous_end(),
+        })
+    }
+
+    fn try_statement(&mut self, start: usize) -> Parsed<Stmt<'a>> {
+        let keyword = self.advance();
+        let body = self.block("`try` statement", keyword.range.start)?;
+        let mut handlers = Vec::new();
+        while self.is("except") {
+            let except = self.advance();
+            self.eat("*");
+            let exception = match self.is(":") {
+                true => None,
+                false => Some(self.expression()?),
+            };
+            let name = match self.eat("as") {
+                true => Some(self.name()?),
+                false => None,
+            };
+            let body = self.block("`except` clause", except.range.start)?;
+            handlers.push(Handler { exception, name, body });
+        }
+        let orelse = match handlers.is_empty() {
+            true => Vec::new(),
+            false => self.else_block()?,
+        };
+        let finally = match self.is("finally") {
+            true => {
+                let token = self.advance();
+                Some(self.block("`finally` clause", token.range.start)?)
+            }
+            false => None,
+        };
+        if handlers.is_empty() && finally.is_none() {
+            self.report(keyword.range, "`try` needs an `except` or a `finally` block");
+        }
+        Ok(Stmt {
+            kind: StmtKind::Try { body, handlers, orelse, finally: finally.unwrap_or_default() },
+            range: start..self.previous_end(),
+        })
+    }
+
+    // whether the items of `with` are in parentheses, e.g., `with (open(a) as b, open(c) as d):`
+    fn with_items_in_parentheses(&self) -> bool {
+        let mut depth = 0;
+        for (offset, token) in self.tokens[self.index..].iter().enumerate() {
+            match token.kind {
+                TokenKind::Operator if matches!(token.text, "(" | "[" | "{") => depth += 1,
+                TokenKind::Operator if matches!(token.text, ")" | "]" | "}") => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Self::token_is(self.peek_at(offset + 1), ":");
+                    }
+                }
+                TokenKind::Newline | TokenKind::EndOfFile => return false,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    fn with_statement(&mut self, start: usize) -> Parsed<Stmt<'a>> {
+        let keyword = self.advance();
+        let parenthesized = self.is("(") && self.with_items_in_parentheses();
+        if parenthesized {
+            self.advance();
+        }
+        let mut items = Vec::new();
+        loop {
+            let context = self.expression()?;
+            let target = match self.eat("as") {
+                true => {
+                    let target = self.target()?;
+                    self.check_target(&target, "assign to")?;
+                    Some(target)
+                }
+                false => None,
+            };
+            items.push((context, target));
+            if !self.eat(",") || (parenthesized && self.is(")")) {
+                break;
+            }
+        }
+        if parenthesized {
+            self.close(")")?;
+        }
+        let body = self.block("`with` statement", keyword.range.start)?;
+        Ok(Stmt {
+            kind: StmtKind::With { items, body },
+            range: start..self.previous_end(),
+        })
+    }
+
+    fn decorated(&mut self, start: usize) -> Parsed<Stmt<'a>> {
+        let mut decorators = Vec::new();
+        while self.eat("@") {
+            decorators.push(self.named_expression()?);
+            if !self.at_end_of_line() {
+                return Err(self.unexpected("expected the end of the line after the decorator"));
+            }
+            self.advance();
+        }
+        if self.is("async") && Self::token_is(self.peek_at(1), "def") {
+            self.advance();
+        }
+        match self.peek().text {
+            "def" => self.function(decorators, start),
+            "class" => self.class(decorators, start),
+            _ => Err(self.unexpected("expected a function or a class after the decorators")),
+        }
+    }
+
+    fn function(&mut self, decorators: Vec<Expr<'a>>, start: usize) -> Parsed<Stmt<'a>> {
+        let keyword = self.advance();
+        let name = self.name()?;
+        self.expect("(")?;
+        let parameters = self.parameters(")", true)?;
+        self.close(")")?;
+        let returns = match self.eat("->") {
+            true => Some(self.expression()?),
+            false => None,
+        };
+        let context = Context { function: true, in_loop: false };
+        let body = self.with_context(context, |parser| parser.block("function definition", keyword.range.start))?;
+        Ok(Stmt {
+            kind: StmtKind::FunctionDef { name, decorators, parameters, returns, body },
+            range: start..self.previous_end(),
+        })
+    }
+
+    fn class(&mut self, decorators: Vec<Expr<'a>>, start: usize) -> Parsed<Stmt<'a>> {
+        let keyword = self.advance();
+        let name = self.name()?;
+        let bases = match self.eat("(") {
+            true => {
+                let bases = self.arguments()?;
+                self.close(")")?;
+                bases
+            }
+            false => Vec::new(),
+        };
+        let body = self.with_context(Context::default(), |parser| parser.block("class definition", keyword.range.start))?;
+        Ok(Stmt {
+            kind: StmtKind::ClassDef { name, decorators, bases, body },
+            range: start..self.previous_end(),
+        })
+    }
+
+    // `match` is only a keyword when it starts a block of `case` clauses
+    fn is_match_statement(&self) -> bool {
+        let mut offset = 1;
+        while !matches!(self.peek_at(offset).kind, TokenKind::Newline | TokenKind::EndOfFile) {
+            offset += 1;
+        }
+        offset > 2
+            && Self::token_is(self.peek_at(offset - 1), ":")
+            && self.peek_at(offset + 1).kind == TokenKind::Indent
+            && Self::token_is(self.peek_at(offset + 2), "case")
+    }
+
+    fn match_statement(&mut self, start: usize) -> Parsed<Stmt<'a>> {
+        self.advance();
+        let subject = self.expressions()?;
+        self.expect(":")?;
+        // the newline and the indentation are known to follow
+        self.advance();
+        self.advance();
+        let mut cases = Vec::new();
+        loop {
+            match self.peek().kind {
+                TokenKind::Dedent => {
+                    self.advance();
+                    break;
+                }
+                TokenKind::EndOfFile => break,
+                _ => {}
+            }
+            self.statement_start = self.peek().range.start;
+            match self.case() {
+                Ok(case) => cases.push(case),
+                Err(Failed) => self.recover(),
+            }
+        }
+        Ok(Stmt {
+            kind: StmtKind::Match { subject, cases },
+            range: start..self.previous_end(),
+        })
+    }
+
+    fn case(&mut self) -> Parsed<Case<'a>> {
+        if !self.is("case") {
+            return Err(self.unexpected("expected `case`"));
+        }
+        let keyword = self.advance();
+        let pattern = self.patterns()?;
+        let guard = match self.eat("if") {
+            true => Some(self.named_expression()?),
+            false => None,
+        };
+        let body = self.block("`case` clause", keyword.range.start)?;
+        Ok(Case { pattern, guard, body })
+    }
+
+    // e.g., `case 1, 2`, where a comma makes a sequence
+    fn patterns(&mut self) -> Parsed<Expr<'a>> {
+        let first = self.pattern()?;
+        if !self.is(",") {
+            return Ok(first);
+        }
+        let start = first.range.start;
+        let mut elements = vec![first];
+        while self.eat(",") {
+            if self.is(":") || self.is("if") {
+                break;
+            }
+            elements.push(self.pattern()?);
+        }
+        Ok(Expr { kind: ExprKind::Tuple(elements), range: start..self.previous_end() })
+    }
+
+    fn pattern(&mut self) -> Parsed<Expr<'a>> {
+        let start = self.peek().range.start;
+        let mut alternatives = vec![self.closed_pattern()?];
+        while self.eat("|") {
+            alternatives.push(self.closed_pattern()?);
+        }
+        let value = match alternatives.len() {
+            1 => alternatives.pop().unwrap(),
+            _ => Expr { kind: ExprKind::Operation("pattern", alternatives), range: start..self.previous_end() },
+        };
+        if !self.eat("as") {
+            return Ok(value);
+        }
+        let target = self.name()?;
+        Ok(Expr {
+            kind: ExprKind::Named { target, value: Box::new(value) },
+            range: start..self.previous_end(),
+        })
+    }
+
+    // the elements of a sequence pattern up to the closing bracket
+    fn pattern_elements(&mut self, closing: &str) -> Parsed<Vec<Expr<'a>>> {
+        let mut elements = Vec::new();
+        while !self.is(closing) {
+            elements.push(self.pattern()?);
+            if !self.eat(",") {
+                break;
+            }
+        }
+        self.close(closing)?;
+        Ok(elements)
+    }
+
+    fn closed_pattern(&mut self) -> Parsed<Expr<'a>> {
+        let start = self.peek().range.start;
+        let token = self.peek().clone();
+        let kind = match token.kind {
+            // e.g., `-1`, `"a" "b"` or `1 + 2j`
+            TokenKind::Number | TokenKind::String => self.literal_pattern(start),
+            TokenKind::Operator if token.text == "-" => self.literal_pattern(start),
+            TokenKind::Name if matches!(token.text, "None" | "True" | "False") => {
+                self.advance();
+                ExprKind::Constant(token.text)
+            }
+            TokenKind::Name if !is_keyword(token.text) => {
+                self.advance();
+                let mut value = Expr { kind: ExprKind::Name(token.text), range: token.range };
+                while self.eat(".") {
+                    let attribute = self.name()?;
+                    value = Expr {
+                        kind: ExprKind::Attribute { value: Box::new(value), attribute: attribute.name },
+                        range: start..attribute.range.end,
+                    };
+                }
+                if !self.eat("(") {
+                    return Ok(value);
+                }
+                let mut arguments = Vec::new();
+                while !self.is(")") {
+                    let keyword = match self.at_name() && Self::token_is(self.peek_at(1), "=") {
+                        true => {
+                            let keyword = self.name()?;
+                            self.advance();
+                            Some(keyword)
+                        }
+                        false => None,
+                    };
+                    arguments.push(Argument { keyword, value: self.pattern()? });
+                    if !self.eat(",") {
+                        break;
+                    }
+                }
+                self.close(")")?;
+                ExprKind::Call { function: Box::new(value), arguments }
+            }
+            TokenKind::Operator if token.text == "*" => {
+                self.advance();
+                let name = self.name()?;
+                ExprKind::Starred(Box::new(Expr { kind: ExprKind::Name(name.name), range: name.range }))
+            }
+            TokenKind::Operator if token.text == "(" => {
+                self.advance();
+                if self.eat(")") {
+                    ExprKind::Tuple(Vec::new())
+                } else {
+                    let first = self.pattern()?;
+                    if !self.is(",") {
+                        self.close(")")?;
+                        return Ok(first);
+                    }
+                    self.advance();
+                    let mut elements = vec![first];
+                    elements.extend(self.pattern_elements(")")?);
+                    ExprKind::Tuple(elements)
+                }
+            }
+            TokenKind::Operator if token.text == "[" => {
+                self.advance();
+                ExprKind::List(self.pattern_elements("]")?)
+            }
+            TokenKind::Operator if token.text == "{" => {
+                self.advance();
+                let mut items = Vec::new();
+                while !self.is("}") {
+                    match self.eat("**") {
+                        true => {
+                            let name = self.name()?;
+                            items.push((None, Expr { kind: ExprKind::Name(name.name), range: name.range }));
+                        }
+                        false => {
+                            let key = self.closed_pattern()?;
+                            self.expect(":")?;
+                            items.push((Some(key), self.pattern()?));
+                        }
+                    }
+                    if !self.eat(",") {
+                        break;
+                    }
+                }
+                self.close("}")?;
+                ExprKind::Dict(items)
+            }
+            _ => return Err(self.unexpected("expected a pattern")),
+        };
+        Ok(Expr { kind, range: start..self.previous_end() })
+    }
+
+    fn literal_pattern(&mut self, start: usize) -> ExprKind<'a> {
+        self.eat("-");
+        while matches!(self.peek().kind, TokenKind::Number | TokenKind::String) {
+            self.advance();
+        }
+        // the imaginary part of a complex number
+        if (self.is("+") || self.is("-")) && self.peek_at(1).kind == TokenKind::Number {
+            self.advance();
+            self.advance();
+        }
+        ExprKind::Constant(&self.text[start..self.previous_end()])
+    }
+
+    fn simple_statements(&mut self) -> Parsed<Vec<Stmt<'a>>> {
+        let mut statements = vec![self.simple_statement()?];
+        while self.eat(";") {
+            if self.at_end_of_line() {
+                break;
+            }
+            statements.push(self.simple_statement()?);
+        }
+        if !self.at_end_of_line() {
+            return Err(self.unexpected("expected the end of the statement"));
+        }
+        self.advance();
+        Ok(statements)
+    }
+
+    fn simple_statement(&mut self) -> Parsed<Stmt<'a>> {
+        let start = self.peek().range.start;
+        let keyword = match self.peek().kind {
+            TokenKind::Name => self.peek().text,
+            _ => "",
+        };
+        let kind = match keyword {
+            "pass" => {
+                self.advance();
+                StmtKind::Pass
+            }
+            "break" | "continue" => {
+                let token = self.advance();
+                if !self.context.in_loop {
+                    self.report(token.range, format!("`{keyword}` outside of a loop"));
+                }
+                match keyword {
+                    "break" => StmtKind::Break,
+                    _ => StmtKind::Continue,
+                }
+            }
+            "return" => {
+                let token = self.advance();
+                if !self.context.function {
+                    self.report(token.range, "`return` outside of a function");
+                }
+                match self.starts_expression() {
+                    true => StmtKind::Return(Some(self.expressions()?)),
+                    false => StmtKind::Return(None),
+                }
+            }
+            "raise" => {
+                self.advance();
+                let mut operands = Vec::new();
+                if self.starts_expression() {
+                    operands.push(self.expression()?);
+                    if self.eat("from") {
+                        operands.push(self.expression()?);
+                    }
+                }
+                StmtKind::Raise(operands)
+            }
+            "assert" => {
+                self.advance();
+                let mut operands = vec![self.expression()?];
+                if self.eat(",") {
+                    operands.push(self.expression()?);
+                }
+                StmtKind::Assert(operands)
+            }
+            "global" | "nonlocal" => {
+                let token = self.advance();
+                if keyword == "nonlocal" && !self.context.function {
+                    self.report(token.range, "`nonlocal` outside of a function");
+                }
+                let mut names = vec![self.name()?];
+                while self.eat(",") {
+                    names.push(self.name()?);
+                }
+                match keyword {
+                    "global" => StmtKind::Global(names),
+                    _ => StmtKind::Nonlocal(names),
+                }
+            }
+            "del" => {
+                self.advance();
+                let targets = self.expressions()?;
+                self.check_target(&targets, "delete")?;
+                match targets.kind {
+                    ExprKind::Tuple(targets) => StmtKind::Delete(targets),
+                    _ => StmtKind::Delete(vec![targets]),
+                }
+            }
+            "import" => {
+                self.advance();
+                let mut names = Vec::new();
+                loop {
+                    let name = self.dotted_name()?;
+                    let alias = match self.eat("as") {
+                        true => Some(self.name()?),
+                        false => None,
+                    };
+                    names.push(Alias { name, alias });
+                    if !self.eat(",") {
+                        break;
+                    }
+                }
+                StmtKind::Import(names)
+            }
+            "from" => self.import_from()?,
+            _ => return self.expression_statement(),
+        };
+        Ok(Stmt { kind, range: start..self.previous_end() })
+    }
+
+    fn import_from(&mut self) -> Parsed<StmtKind<'a>> {
+        self.advance();
+        let module_start = self.peek().range.start;
+        // the dots of a relative import
+        while self.is(".") || self.is("...") {
+            self.advance();
+        }
+        if !self.is("import") {
+            self.dotted_name()?;
+        }
+        let module = &self.text[module_start..self.previous_end().max(module_start)];
+        if module.is_empty() {
+            return Err(self.unexpected("expected the name of a module"));
+        }
+        self.expect("import")?;
+        if self.eat("*") {
+            return Ok(StmtKind::ImportFrom { module, names: Vec::new(), star: true });
+        }
+        let parenthesized = self.eat("(");
+        let mut names = Vec::new();
+        loop {
+            let name = self.name()?;
+            let alias = match self.eat("as") {
+                true => Some(self.name()?),
+                false => None,
+            };
+            names.push(Alias { name, alias });
+            if !self.eat(",") || (parenthesized && self.is(")")) {
+                break;
+            }
+        }
+        if parenthesized {
+            self.close(")")?;
+        }
+        Ok(StmtKind::ImportFrom { module, names, star: false })
+    }
+
+    // an expression, which may be followed by an assignment
+    fn expression_statement(&mut self) -> Parsed<Stmt<'a>> {
+        let start = self.peek().range.start;
+        let first = self.assigned_value()?;
+        let kind = if self.eat(":") {
+            match first.kind {
+                ExprKind::Name(_) | ExprKind::Attribute { .. } | ExprKind::Subscript { .. } => {}
+                ExprKind::Tuple(_) => return Err(self.error(first.range, "only a single target can be annotated")),
+                _ => return Err(self.error(first.range.clone(), format!("cannot annotate {}", first.description()))),
+            }
+            let annotation = self.expression()?;
+            let value = match self.eat("=") {
+                true => Some(self.assigned_value()?),
+                false => None,
+            };
+            StmtKind::AnnotatedAssign { target: first, annotation, value }
+        } else if self.peek().kind == TokenKind::Operator && AUGMENTED_ASSIGNMENTS.contains(&self.peek().text) {
+            if !matches!(first.kind, ExprKind::Name(_) | ExprKind::Attribute { .. } | ExprKind::Subscript { .. }) {
+                let message = format!("{} is an illegal target for augmented assignment", first.description());
+                return Err(self.error(first.range, message));
+            }
+            self.advance();
+            let value = self.assigned_value()?;
+            StmtKind::AugmentedAssign { target: first, value }
+        } else if self.is("=") {
+            let mut targets = vec![first];
+            let value = loop {
+                self.advance();
+                let next = self.assigned_value()?;
+                if !self.is("=") {
+                    break next;
+                }
+                targets.push(next);
+            };
+            for target in &targets {
+                self.check_target(target, "assign to")?;
+            }
+            StmtKind::Assign { targets, value }
+        } else {
+            StmtKind::Expr(first)
+        };
+        Ok(Stmt { kind, range: start..self.previous_end() })
+    }
+
+    fn assigned_value(&mut self) -> Parsed<Expr<'a>> {
+        match self.is("yield") {
+            true => self.yield_expression(),
+            false => self.expressions(),
+        }
+    }
+
+    // names, attributes and subscripts, which may be unpacked from tuples and lists
+    fn check_target(&mut self, target: &Expr<'a>, verb: &str) -> Parsed<()> {
+        match &target.kind {
+            ExprKind::Name(_) | ExprKind::Attribute { .. } | ExprKind::Subscript { .. } => Ok(()),
+            ExprKind::Tuple(elements) | ExprKind::List(elements) => {
+                for element in elements {
+                    self.check_target(element, verb)?;
+                }
+                Ok(())
+            }
+            ExprKind::Starred(value) => self.check_target(value, verb),
+            _ => Err(self.error(target.range.clone(), format!("cannot {verb} {}", target.description()))),
+        }
+    }
+
+    fn starts_expression(&self) -> bool {
+        let token = self.peek();
+        match token.kind {
+            TokenKind::Name => !is_keyword(token.text) || matches!(token.text, "None" | "True" | "False" | "not" | "lambda" | "await"),
+            TokenKind::Number | TokenKind::String => true,
+            TokenKind::Operator => matches!(token.text, "(" | "[" | "{" | "-" | "+" | "~" | "*" | "..."),
+            _ => false,
+        }
+    }
+
+    fn operation(&self, description: &'static str, start: usize, operands: Vec<Expr<'a>>) -> Expr<'a> {
+        Expr {
+            kind: ExprKind::Operation(description, operands),
+            range: start..self.previous_end(),
+        }
+    }
+
+    // e.g., the value of `return a, *b`, where a comma makes a tuple
+    fn expressions(&mut self) -> Parsed<Expr<'a>> {
+        let first = self.star_expression()?;
+        if !self.is(",") {
+            return Ok(first);
+        }
+        let start = first.range.start;
+        let mut elements = vec![first];
+        while self.eat(",") {
+            if !self.starts_expression() {
+                break;
+            }
+            elements.push(self.star_expression()?);
+        }
+        Ok(Expr { kind: ExprKind::Tuple(elements), range: start..self.previous_end() })
+    }
+
+    fn star_expression(&mut self) -> Parsed<Expr<'a>> {
+        if !self.is("*") {
+            return self.named_expression();
+        }
+        let start = self.advance().range.start;
+        let value = self.bitwise_or()?;
+        Ok(Expr { kind: ExprKind::Starred(Box::new(value)), range: start..self.previous_end() })
+    }
+
+    fn named_expression(&mut self) -> Parsed<Expr<'a>> {
+        if self.at_name() && Self::token_is(self.peek_at(1), ":=") {
+            let target = self.name()?;
+            self.advance();
+            let value = self.expression()?;
+            let range = target.range.start..self.previous_end();
+            return Ok(Expr { kind: ExprKind::Named { target, value: Box::new(value) }, range });
+        }
+        let expression = self.expression()?;
+        if self.is(":=") {
+            return Err(self.unexpected(format!("cannot use an assignment expression with {}", expression.description())));
+        }
+        Ok(expression)
+    }
+
+    fn expression(&mut self) -> Parsed<Expr<'a>> {
+        if self.is("lambda") {
+            return self.lambda();
+        }
+        let body = self.disjunction()?;
+        if !self.eat("if") {
+            return Ok(body);
+        }
+        let start = body.range.start;
+        let test = self.disjunction()?;
+        if !self.eat("else") {
+            return Err(self.unexpected("expected `else` in the conditional expression"));
+        }
+        let orelse = self.expression()?;
+        Ok(self.operation("conditional expression", start, vec![body, test, orelse]))
+    }
+
+    fn lambda(&mut self) -> Parsed<Expr<'a>> {
+        let start = self.advance().range.start;
+        let context = Context { function: true, in_loop: false };
+        self.with_context(context, |parser| {
+            let parameters = parser.parameters(":", false)?;
+            parser.expect(":")?;
+            let body = parser.expression()?;
+            Ok(Expr {
+                kind: ExprKind::Lambda { parameters, body: Box::new(body) },
+                range: start..parser.previous_end(),
+            })
+        })
+    }
+
+    // the parameters of a function up to the `)` or of a lambda up to the `:`
+    fn parameters(&mut self, closing: &str, annotated: bool) -> Parsed<Vec<Parameter<'a>>> {
+        let mut parameters: Vec<Parameter> = Vec::new();
+        while !self.is(closing) {
+            // a `/` ends the positional-only parameters
+            if !self.eat("/") {
+                let kind = if self.eat("**") {
+                    ParameterKind::VariadicKeyword
+                } else if self.eat("*") {
+                    ParameterKind::VariadicPositional
+                } else {
+                    ParameterKind::Positional
+                };
+                // a bare `*` starts the keyword-only parameters
+                let bare = kind == ParameterKind::VariadicPositional && (self.is(",") || self.is(closing));
+                if !bare {
+                    let name = self.name()?;
+                    let annotation = match annotated && self.eat(":") {
+                        true => Some(self.star_expression()?),
+                        false => None,
+                    };
+                    let default = match kind == ParameterKind::Positional && self.eat("=") {
+                        true => Some(self.expression()?),
+                        false => None,
+                    };
+                    if parameters.iter().any(|parameter| parameter.name.name == name.name) {
+                        self.report(name.range.clone(), format!("duplicate parameter `{}`", name.name));
+                    }
+                    parameters.push(Parameter { name, kind, annotation, default });
+                }
+            }
+            if !self.eat(",") {
+                break;
+            }
+        }
+        Ok(parameters)
+    }
+
+    fn disjunction(&mut self) -> Parsed<Expr<'a>> {
+        self.boolean("or", Self::conjunction)
+    }
+
+    fn conjunction(&mut self) -> Parsed<Expr<'a>> {
+        self.boolean("and", Self::inversion)
+    }
+
+    fn boolean(&mut self, operator: &str, operand: fn(&mut Self) -> Parsed<Expr<'a>>) -> Parsed<Expr<'a>> {
+        let first = operand(self)?;
+        if !self.is(operator) {
+            return Ok(first);
+        }
+        let start = first.range.start;
+        let mut operands = vec![first];
+        while self.eat(operator) {
+            operands.push(operand(self)?);
+        }
+        Ok(self.operation("expression", start, operands))
+    }
+
+    fn inversion(&mut self) -> Parsed<Expr<'a>> {
+        if !self.is("not") {
+            return self.comparison();
+        }
+        let start = self.advance().range.start;
+        let operand = self.inversion()?;
+        Ok(self.operation("expression", start, vec![operand]))
+    }
+
+    fn comparison(&mut self) -> Parsed<Expr<'a>> {
+        let first = self.bitwise_or()?;
+        let start = first.range.start;
+        let mut operands = vec![first];
+        loop {
+            if self.is("not") && Self::token_is(self.peek_at(1), "in") {
+                self.advance();
+                self.advance();
+            } else if self.is("is") {
+                self.advance();
+                self.eat("not");
+            } else if self.is("in") || (self.peek().kind == TokenKind::Operator && COMPARISONS.contains(&self.peek().text)) {
+                self.advance();
+            } else {
+                break;
+            }
+            operands.push(self.bitwise_or()?);
+        }
+        match operands.len() {
+            1 => Ok(operands.pop().unwrap()),
+            _ => Ok(self.operation("comparison", start, operands)),
+        }
+    }
+
+    fn bitwise_or(&mut self) -> Parsed<Expr<'a>> {
+        self.binary(0)
+    }
+
+    fn binary(&mut self, level: usize) -> Parsed<Expr<'a>> {
+        let Some(operators) = BINARY_OPERATORS.get(level) else {
+            return self.factor();
+        };
+        let first = self.binary(level + 1)?;
+        let start = first.range.start;
+        let mut operands = vec![first];
+        while self.peek().kind == TokenKind::Operator && operators.contains(&self.peek().text) {
+            self.advance();
+            operands.push(self.binary(level + 1)?);
+        }
+        match operands.len() {
+            1 => Ok(operands.pop().unwrap()),
+            _ => Ok(self.operation("expression", start, operands)),
+        }
+    }
+
+    fn factor(&mut self) -> Parsed<Expr<'a>> {
+        if !(self.is("+") || self.is("-") || self.is("~")) {
+            return self.power();
+        }
+        let start = self.advance().range.start;
+        let operand = self.factor()?;
+        Ok(self.operation("expression", start, vec![operand]))
+    }
+
+    fn power(&mut self) -> Parsed<Expr<'a>> {
+        let start = self.peek().range.start;
+        let base = match self.eat("await") {
+            true => {
+                let operand = self.primary()?;
+                self.operation("await expression", start, vec![operand])
+            }
+            false => self.primary()?,
+        };
+        if !self.eat("**") {
+            return Ok(base);
+        }
+        let exponent = self.factor()?;
+        Ok(self.operation("expression", start, vec![base, exponent]))
+    }
+
+    fn primary(&mut self) -> Parsed<Expr<'a>> {
+        let mut expression = self.atom()?;
+        let start = expression.range.start;
+        loop {
+            let kind = if self.eat(".") {
+                let attribute = self.name()?;
+                ExprKind::Attribute { value: Box::new(expression), attribute: attribute.name }
+            } else if self.eat("(") {
+                let arguments = self.arguments()?;
+                self.close(")")?;
+                ExprKind::Call { function: Box::new(expression), arguments }
+            } else if self.eat("[") {
+                let slice = self.slices()?;
+                self.close("]")?;
+                ExprKind::Subscript { value: Box::new(expression), slice: Box::new(slice) }
+            } else {
+                return Ok(expression);
+            };
+            expression = Expr { kind, range: start..self.previous_end() };
+        }
+    }
+
+    // the arguments of a call up to the `)`
+    fn arguments(&mut self) -> Parsed<Vec<Argument<'a>>> {
+        let mut arguments = Vec::new();
+        while !self.is(")") {
+            let start = self.peek().range.start;
+            let argument = if self.eat("*") {
+                let value = self.expression()?;
+                Argument { keyword: None, value: Expr { kind: ExprKind::Starred(Box::new(value)), range: start..self.previous_end() } }
+            } else if self.eat("**") {
+                let value = self.expression()?;
+                Argument { keyword: None, value: self.operation("keyword argument unpacking", start, vec![value]) }
+            } else if self.at_name() && Self::token_is(self.peek_at(1), "=") {
+                let keyword = self.name()?;
+                self.advance();
+                Argument { keyword: Some(keyword), value: self.expression()? }
+            } else {
+                let value = self.named_expression()?;
+                let value = match self.is_comprehension() {
+                    true => self.comprehension("generator expression", vec![value], start)?,
+                    false => value,
+                };
+                Argument { keyword: None, value }
+            };
+            arguments.push(argument);
+            if !self.eat(",") {
+                break;
+            }
+        }
+        Ok(arguments)
+    }
+
+    // e.g., `a[1]`, `a[1:2, ::3]` or `a[*b]`
+    fn slices(&mut self) -> Parsed<Expr<'a>> {
+        let first = self.slice()?;
+        if !self.is(",") {
+            return Ok(first);
+        }
+        let start = first.range.start;
+        let mut elements = vec![first];
+        while self.eat(",") {
+            if self.is("]") {
+                break;
+            }
+            elements.push(self.slice()?);
+        }
+        Ok(Expr { kind: ExprKind::Tuple(elements), range: start..self.previous_end() })
+    }
+
+    fn slice(&mut self) -> Parsed<Expr<'a>> {
+        if self.is("*") {
+            return self.star_expression();
+        }
+        let start = self.peek().range.start;
+        let mut operands = Vec::new();
+        if !self.is(":") {
+            let lower = self.named_expression()?;
+            if !self.is(":") {
+                return Ok(lower);
+            }
+            operands.push(lower);
+        }
+        // the upper bound and the step
+        for _ in 0..2 {
+            if !self.eat(":") {
+                break;
+            }
+            if !(self.is(":") || self.is(",") || self.is("]")) {
+                operands.push(self.expression()?);
+            }
+        }
+        Ok(self.operation("slice", start, operands))
+    }
+
+    fn atom(&mut self) -> Parsed<Expr<'a>> {
+        let token = self.peek().clone();
+        let kind = match token.kind {
+            TokenKind::Name if matches!(token.text, "None" | "True" | "False") => {
+                self.advance();
+                ExprKind::Constant(token.text)
+            }
+            TokenKind::Name if !is_keyword(token.text) => {
+                self.advance();
+                ExprKind::Name(token.text)
+            }
+            TokenKind::Number => {
+                self.advance();
+                ExprKind::Constant(token.text)
+            }
+            // adjacent strings are joined
+            TokenKind::String => {
+                while self.peek().kind == TokenKind::String {
+                    self.advance();
+                }
+                ExprKind::Constant(&self.text[token.range.start..self.previous_end()])
+            }
+            TokenKind::Operator if token.text == "..." => {
+                self.advance();
+                ExprKind::Constant(token.text)
+            }
+            TokenKind::Operator if token.text == "(" => return self.parenthesized(),
+            TokenKind::Operator if token.text == "[" => return self.list(),
+            TokenKind::Operator if token.text == "{" => return self.braces(),
+            _ => return Err(self.unexpected("expected an expression")),
+        };
+        Ok(Expr { kind, range: token.range })
+    }
+
+    fn is_comprehension(&self) -> bool {
+        self.is("for") || (self.is("async") && Self::token_is(self.peek_at(1), "for"))
+    }
+
+    // the `for` clauses after the element
+    fn comprehension(&mut self, description: &'static str, elements: Vec<Expr<'a>>, start: usize) -> Parsed<Expr<'a>> {
+        let mut generators = Vec::new();
+        while self.is_comprehension() {
+            self.eat("async");
+            self.advance();
+            let target = self.targets()?;
+            self.expect("in")?;
+            let iterable = self.disjunction()?;
+            let mut conditions = Vec::new();
+            while self.eat("if") {
+                conditions.push(self.disjunction()?);
+            }
+            generators.push(Generator { target, iterable, conditions });
+        }
+        Ok(Expr {
+            kind: ExprKind::Comprehension { description, elements, generators },
+            range: start..self.previous_end(),
+        })
+    }
+
+    // the elements of a display after the first one up to the closing bracket
+    fn elements(&mut self, first: Expr<'a>, closing: &str) -> Parsed<Vec<Expr<'a>>> {
+        let mut elements = vec![first];
+        while self.eat(",") {
+            if self.is(closing) {
+                break;
+            }
+            elements.push(self.star_expression()?);
+        }
+        self.close(closing)?;
+        Ok(elements)
+    }
+
+    fn parenthesized(&mut self) -> Parsed<Expr<'a>> {
+        let start = self.advance().range.start;
+        if self.eat(")") {
+            return Ok(Expr { kind: ExprKind::Tuple(Vec::new()), range: start..self.previous_end() });
+        }
+        if self.is("yield") {
+            let value = self.yield_expression()?;
+            self.close(")")?;
+            return Ok(value);
+        }
+        let first = self.star_expression()?;
+        if self.is_comprehension() {
+            let comprehension = self.comprehension("generator expression", vec![first], start)?;
+            self.close(")")?;
+            return Ok(Expr { range: start..self.previous_end(), ..comprehension });
+        }
+        if !self.is(",") {
+            self.close(")")?;
+            return Ok(first);
+        }
+        let elements = self.elements(first, ")")?;
+        Ok(Expr { kind: ExprKind::Tuple(elements), range: start..self.previous_end() })
+    }
+
+    fn list(&mut self) -> Parsed<Expr<'a>> {
+        let start = self.advance().range.start;
+        if self.eat("]") {
+            return Ok(Expr { kind: ExprKind::List(Vec::new()), range: start..self.previous_end() });
+        }
+        let first = self.star_expression()?;
+        if self.is_comprehension() {
+            let comprehension = self.comprehension("list comprehension", vec![first], start)?;
+            self.close("]")?;
+            return Ok(Expr { range: start..self.previous_end(), ..comprehension });
+        }
+        let elements = self.elements(first, "]")?;
+        Ok(Expr { kind: ExprKind::List(elements), range: start..self.previous_end() })
+    }
+
+    // a dict or a set
+    fn braces(&mut self) -> Parsed<Expr<'a>> {
+        let start = self.advance().range.start;
+        if self.eat("}") {
+            return Ok(Expr { kind: ExprKind::Dict(Vec::new()), range: start..self.previous_end() });
+        }
+        let first = match self.eat("**") {
+            true => (None, self.bitwise_or()?),
+            false => {
+                let key = self.star_expression()?;
+                if !self.eat(":") {
+                    if self.is_comprehension() {
+                        let comprehension = self.comprehension("set comprehension", vec![key], start)?;
+                        self.close("}")?;
+                        return Ok(Expr { range: start..self.previous_end(), ..comprehension });
+                    }
+                    let elements = self.elements(key, "}")?;
+                    return Ok(Expr { kind: ExprKind::Set(elements), range: start..self.previous_end() });
+                }
+                let value = self.expression()?;
+                if self.is_comprehension() {
+                    let comprehension = self.comprehension("dict comprehension", vec![key, value], start)?;
+                    self.close("}")?;
+                    return Ok(Expr { range: start..self.previous_end(), ..comprehension });
+                }
+                (Some(key), value)
+            }
+        };
+        let mut items = vec![first];
+        while self.eat(",") {
+            if self.is("}") {
+                break;
+            }
+            match self.eat("**") {
+                true => items.push((None, self.bitwise_or()?)),
+                false => {
+                    let key = self.expression()?;
+                    self.expect(":")?;
+                    items.push((Some(key), self.expression()?));
+                }
+            }
+        }
+        self.close("}")?;
+        Ok(Expr { kind: ExprKind::Dict(items), range: start..self.previous_end() })
+    }
+
+    fn yield_expression(&mut self) -> Parsed<Expr<'a>> {
+        let token = self.advance();
+        if !self.context.function {
+            self.report(token.range.clone(), "`yield` outside of a function");
+        }
+        let mut operands = Vec::new();
+        if self.eat("from") {
+            operands.push(self.expression()?);
+        } else if self.starts_expression() {
+            operands.push(self.expressions()?);
+        }
+        Ok(self.operation("yield expression", token.range.start, operands))
+    }
+
+    // the targets of `for`, which end before the `in`
+    fn targets(&mut self) -> Parsed<Expr<'a>> {
+        let first = self.target()?;
+        let target = match self.is(",") {
+            true => {
+                let start = first.range.start;
+                let mut elements = vec![first];
+                while self.eat(",") {
+                    if self.is("in") {
+                        break;
+                    }
+                    elements.push(self.target()?);
+                }
+                Expr { kind: ExprKind::Tuple(elements), range: start..self.previous_end() }
+            }
+            false => first,
+        };
+        self.check_target(&target, "assign to")?;
+        Ok(target)
+    }
+
+    fn target(&mut self) -> Parsed<Expr<'a>> {
+        if !self.is("*") {
+            return self.bitwise_or();
+        }
+        let start = self.advance().range.start;
+        let value = self.bitwise_or()?;
+        Ok(Expr { kind: ExprKind::Starred(Box::new(value)), range: start..self.previous_end() })
+    }
+}
+
+pub fn parse(text: &str) -> Module<'_> {
+    let (tokens, token_errors) = tokens::tokenize(text);
+    let mut parser = Parser {
+        text,
+        tokens,
+        index: 0,
+        errors: Vec::new(),
+        token_errors,
+        statement_start: 0,
+        context: Context::default(),
+    };
+    let body = parser.statements(false);
+    let mut errors = parser.token_errors;
+    errors.extend(parser.errors);
+    errors.sort_by_key(|error| error.range.start);
+    Module { body, errors }
+}
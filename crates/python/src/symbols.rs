@@ -0,0 +1,129 @@
+// the classes and functions of a file for the outline and the breadcrumbs, offsets are in bytes
+use std::ops::Range;
+
+use crate::{ast::{Stmt, StmtKind}, parse};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymbolKind {
+    Class,
+    Method,
+    Function,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Symbol<'a> {
+    pub name: &'a str,
+    pub kind: SymbolKind,
+    // the whole definition including its decorators
+    pub range: Range<usize>,
+    pub name_range: Range<usize>,
+    pub children: Vec<Symbol<'a>>,
+}
+
+// statements that are skipped because of a syntax error leave out the definitions inside of them
+pub fn symbols(text: &str) -> Vec<Symbol<'_>> {
+    let module = parse::parse(text);
+    let mut symbols = Vec::new();
+    collect(text, &module.body, false, &mut symbols);
+    symbols
+}
+
+// definitions inside of other compound statements, e.g., `if` or `try`, belong to the enclosing
+// definition
+fn collect<'a>(text: &str, body: &[Stmt<'a>], in_class: bool, symbols: &mut Vec<Symbol<'a>>) {
+    for statement in body {
+        // without the line breaks that the statement ends with
+        let range = statement.range.start..statement.range.start + text[statement.range.clone()].trim_end().len();
+        match &statement.kind {
+            StmtKind::FunctionDef { name, body, .. } => {
+                let mut children = Vec::new();
+                collect(text, body, false, &mut children);
+                symbols.push(Symbol {
+                    name: name.name,
+                    kind: if in_class { SymbolKind::Method } else { SymbolKind::Function },
+                    range,
+                    name_range: name.range.clone(),
+                    children,
+                });
+            }
+            StmtKind::ClassDef { name, body, .. } => {
+                let mut children = Vec::new();
+                collect(text, body, true, &mut children);
+                symbols.push(Symbol {
+                    name: name.name,
+                    kind: SymbolKind::Class,
+                    range,
+                    name_range: name.range.clone(),
+                    children,
+                });
+            }
+            StmtKind::If { body, orelse, .. }
+            | StmtKind::While { body, orelse, .. }
+            | StmtKind::For { body, orelse, .. } => {
+                collect(text, body, in_class, symbols);
+                collect(text, orelse, in_class, symbols);
+            }
+            StmtKind::Try { body, handlers, orelse, finally } => {
+                collect(text, body, in_class, symbols);
+                for handler in handlers {
+                    collect(text, &handler.body, in_class, symbols);
+                }
+                collect(text, orelse, in_class, symbols);
+                collect(text, finally, in_class, symbols);
+            }
+            StmtKind::With { body, .. } => collect(text, body, in_class, symbols),
+            StmtKind::Match { cases, .. } => {
+                for case in cases {
+                    collect(text, &case.body, in_class, symbols);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outline(symbols: &[Symbol]) -> Vec<(String, SymbolKind)> {
+        let mut rows = Vec::new();
+        for symbol in symbols {
+            rows.push((symbol.name.to_owned(), symbol.kind));
+            rows.extend(outline(&symbol.children).into_iter()
+                .map(|(name, kind)| (format!("{}.{name}", symbol.name), kind)));
+        }
+        rows
+    }
+
+    #[test]
+    fn classes_methods_and_functions() {
+        let text = "class Talker(Node):\n    def __init__(self):\n        def callback():\n            pass\n\nasync def main():\n    pass\n";
+        assert_eq!(outline(&symbols(text)), [
+            ("Talker".to_owned(), SymbolKind::Class),
+            ("Talker.__init__".to_owned(), SymbolKind::Method),
+            ("Talker.__init__.callback".to_owned(), SymbolKind::Function),
+            ("main".to_owned(), SymbolKind::Function),
+        ]);
+    }
+
+    #[test]
+    fn ranges() {
+        let text = "@decorator\ndef f():\n    return 1\n\nx = 1\n";
+        let symbol = &symbols(text)[0];
+        assert_eq!(&text[symbol.range.clone()], "@decorator\ndef f():\n    return 1");
+        assert_eq!(&text[symbol.name_range.clone()], "f");
+    }
+
+    #[test]
+    fn definitions_in_compound_statements() {
+        let text = "try:\n    def f():\n        pass\nexcept ImportError:\n    def g():\n        pass\nif True:\n    class A:\n        pass\n";
+        let names = symbols(text).iter().map(|symbol| symbol.name).collect::<Vec<_>>();
+        assert_eq!(names, ["f", "g", "A"]);
+    }
+
+    #[test]
+    fn strings_are_not_definitions() {
+        assert!(symbols("x = \"\"\"\ndef f():\n    pass\n\"\"\"\n").is_empty());
+    }
+}
@@ -0,0 +1,365 @@
+// splits Python source into tokens, including the indentation of the lines, and reports the
+// problems that are found on the way, offsets are in bytes
+use std::ops::Range;
+
+use crate::diagnostics::SyntaxError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    // keywords are names as well
+    Name,
+    Number,
+    String,
+    Operator,
+    // the end of a logical line
+    Newline,
+    Indent,
+    Dedent,
+    EndOfFile,
+}
+
+#[derive(Clone, Debug)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    pub range: Range<usize>,
+}
+
+pub const KEYWORDS: [&str; 35] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+pub fn is_keyword(name: &str) -> bool {
+    KEYWORDS.contains(&name)
+}
+
+// the longest ones first, so that they are matched before their prefixes
+const OPERATORS: [&str; 47] = [
+    "**=", "//=", ">>=", "<<=", "...", "!=", "%=", "&=", "**", "*=", "+=", "-=", "->", "//", "/=",
+    ":=", "<<", "<=", "==", ">=", ">>", "@=", "^=", "|=", "%", "&", "(", ")", "*", "+", ",", "-",
+    ".", "/", ":", ";", "<", "=", ">", "@", "[", "]", "^", "{", "|", "}", "~",
+];
+
+const STRING_PREFIXES: [&str; 8] = ["r", "u", "b", "f", "br", "rb", "fr", "rf"];
+
+fn is_name_start(character: char) -> bool {
+    character.is_alphabetic() || character == '_'
+}
+
+fn is_name_character(character: char) -> bool {
+    character.is_alphanumeric() || character == '_'
+}
+
+// e.g., 42, 0x2A, 1_000, 3.14, 1e-3 or 2j
+fn is_valid_number(number: &str) -> bool {
+    let digits = number.replace('_', "");
+    if number.starts_with('_') || number.ends_with('_') || number.contains("__") {
+        return false;
+    }
+    let lowercase = digits.to_ascii_lowercase();
+    let radix = match lowercase.get(..2) {
+        Some("0x") => Some(16),
+        Some("0o") => Some(8),
+        Some("0b") => Some(2),
+        _ => None,
+    };
+    if let Some(radix) = radix {
+        return lowercase.len() > 2 && lowercase[2..].chars().all(|digit| digit.is_digit(radix));
+    }
+    let real = lowercase.strip_suffix('j').unwrap_or(&lowercase);
+    if real.chars().all(|digit| digit.is_ascii_digit()) {
+        // leading zeros are only allowed in zero itself
+        return !real.is_empty() && (!real.starts_with('0') || real.chars().all(|digit| digit == '0'));
+    }
+    real.chars().all(|character| character.is_ascii_digit() || matches!(character, '.' | 'e' | '+' | '-'))
+        && real.parse::<f64>().is_ok()
+}
+
+// the indentation of an indented block, measured with tabs to the next multiple of eight and with
+// tabs as a single column, which have to agree on the order of the levels
+struct Level {
+    column: usize,
+    alternative: usize,
+    // a level that was made up after an unindent that did not match, leaving it emits no dedent
+    phantom: bool,
+}
+
+struct Tokenizer<'a> {
+    text: &'a str,
+    index: usize,
+    tokens: Vec<Token<'a>>,
+    errors: Vec<SyntaxError>,
+    levels: Vec<Level>,
+    // the open brackets, inside of which lines are joined
+    brackets: Vec<(char, Range<usize>)>,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn error(&mut self, range: Range<usize>, message: impl Into<String>) {
+        self.errors.push(SyntaxError { range, message: message.into() });
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.index..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn push(&mut self, kind: TokenKind, start: usize) {
+        self.tokens.push(Token {
+            kind,
+            text: &self.text[start..self.index],
+            range: start..self.index,
+        });
+    }
+
+    // whether a token other than the indentation was read since the last newline
+    fn in_logical_line(&self) -> bool {
+        self.tokens.last().is_some_and(|token| !matches!(token.kind, TokenKind::Newline | TokenKind::Indent | TokenKind::Dedent))
+    }
+
+    // reads the indentation at the start of a line, lines without code are skipped
+    fn indentation(&mut self) {
+        let start = self.index;
+        let (mut column, mut alternative) = (0, 0);
+        while let Some(character) = self.peek() {
+            match character {
+                ' ' => column += 1,
+                '\t' => column = (column / 8 + 1) * 8,
+                '\x0c' => column = 0,
+                _ => break,
+            }
+            if character != '\x0c' {
+                alternative += 1;
+            }
+            self.index += 1;
+        }
+        if matches!(self.peek(), None | Some('#' | '\n' | '\r')) {
+            return;
+        }
+        let range = start..self.index;
+        let top = self.levels.last().unwrap();
+        if column > top.column {
+            if alternative <= top.alternative {
+                self.error(range, "inconsistent use of tabs and spaces in indentation");
+            }
+            self.levels.push(Level { column, alternative, phantom: false });
+            self.push(TokenKind::Indent, start);
+            return;
+        }
+        while self.levels.len() > 1 && column < self.levels.last().unwrap().column {
+            if !self.levels.pop().unwrap().phantom {
+                self.push(TokenKind::Dedent, self.index);
+            }
+        }
+        let top = self.levels.last().unwrap();
+        if column != top.column {
+            self.error(range, "unindent does not match any outer indentation level");
+            self.levels.push(Level { column, alternative, phantom: true });
+        } else if alternative != top.alternative {
+            self.error(range, "inconsistent use of tabs and spaces in indentation");
+        }
+    }
+
+    fn name(&mut self) {
+        let start = self.index;
+        let length = self.rest().find(|character| !is_name_character(character)).unwrap_or(self.rest().len());
+        self.index += length;
+        let word = &self.text[start..self.index];
+        match self.peek() {
+            Some('"' | '\'') if STRING_PREFIXES.contains(&word.to_ascii_lowercase().as_str()) => self.string(start),
+            _ => self.push(TokenKind::Name, start),
+        }
+    }
+
+    fn number(&mut self) {
+        let start = self.index;
+        let hexadecimal = self.rest().starts_with("0x") || self.rest().starts_with("0X");
+        while let Some(character) = self.peek() {
+            let exponent_sign = matches!(character, '+' | '-')
+                && !hexadecimal
+                && self.text[start..self.index].ends_with(['e', 'E']);
+            if !(character.is_alphanumeric() || matches!(character, '_' | '.') || exponent_sign) {
+                break;
+            }
+            self.index += character.len_utf8();
+        }
+        if !is_valid_number(&self.text[start..self.index]) {
+            self.error(start..self.index, "invalid number literal");
+        }
+        self.push(TokenKind::Number, start);
+    }
+
+    // the prefix has already been read
+    fn string(&mut self, start: usize) {
+        let quote = self.peek().unwrap();
+        let triple = match quote {
+            '"' => "\"\"\"",
+            _ => "'''",
+        };
+        if self.rest().starts_with(triple) {
+            self.index += 3;
+            let mut characters = self.rest().char_indices();
+            let end = loop {
+                match characters.next() {
+                    Some((_, '\\')) => {
+                        characters.next();
+                    }
+                    Some((offset, _)) if self.rest()[offset..].starts_with(triple) => break Some(offset),
+                    Some(_) => {}
+                    None => break None,
+                }
+            };
+            match end {
+                Some(offset) => self.index += offset + 3,
+                None => {
+                    self.error(start..self.index, "unterminated triple-quoted string literal");
+                    self.index = self.text.len();
+                }
+            }
+        } else {
+            self.index += 1;
+            let mut characters = self.rest().char_indices();
+            let end = loop {
+                match characters.next() {
+                    // an escaped line break continues the string on the next line
+                    Some((_, '\\')) => {
+                        characters.next();
+                    }
+                    Some((offset, character)) if character == quote => break Ok(offset),
+                    Some((offset, '\n' | '\r')) => break Err(offset),
+                    Some(_) => {}
+                    None => break Err(self.rest().len()),
+                }
+            };
+            match end {
+                Ok(offset) => self.index += offset + 1,
+                Err(offset) => {
+                    self.index += offset;
+                    self.error(start..self.index, "unterminated string literal");
+                }
+            }
+        }
+        self.push(TokenKind::String, start);
+    }
+
+    fn operator(&mut self) {
+        let start = self.index;
+        let Some(operator) = OPERATORS.iter().find(|operator| self.rest().starts_with(**operator)) else {
+            let character = self.peek().unwrap();
+            self.index += character.len_utf8();
+            self.error(start..self.index, format!("invalid character `{character}`"));
+            return;
+        };
+        self.index += operator.len();
+        let range = start..self.index;
+        match *operator {
+            "(" => self.brackets.push(('(', range)),
+            "[" => self.brackets.push(('[', range)),
+            "{" => self.brackets.push(('{', range)),
+            ")" | "]" | "}" => {
+                let opening = match *operator {
+                    ")" => '(',
+                    "]" => '[',
+                    _ => '{',
+                };
+                match self.brackets.last() {
+                    Some((bracket, _)) if *bracket == opening => {
+                        self.brackets.pop();
+                    }
+                    Some((bracket, _)) => {
+                        let message = format!("closing bracket `{operator}` does not match opening bracket `{bracket}`");
+                        self.error(range, message);
+                        // the brackets that were left open inside of the matching one
+                        if let Some(depth) = self.brackets.iter().rposition(|(bracket, _)| *bracket == opening) {
+                            self.brackets.truncate(depth);
+                        }
+                    }
+                    None => self.error(range, format!("unmatched `{operator}`")),
+                }
+            }
+            _ => {}
+        }
+        self.push(TokenKind::Operator, start);
+    }
+
+    fn run(&mut self) {
+        let mut line_start = true;
+        loop {
+            if line_start && self.brackets.is_empty() {
+                self.indentation();
+            }
+            line_start = false;
+            let Some(character) = self.peek() else {
+                break;
+            };
+            let start = self.index;
+            match character {
+                ' ' | '\t' | '\x0c' => self.index += 1,
+                '#' => self.index += self.rest().find(['\n', '\r']).unwrap_or(self.rest().len()),
+                '\n' | '\r' => {
+                    self.index += match self.rest().starts_with("\r\n") {
+                        true => 2,
+                        false => 1,
+                    };
+                    if self.brackets.is_empty() {
+                        if self.in_logical_line() {
+                            self.push(TokenKind::Newline, start);
+                        }
+                        line_start = true;
+                    }
+                }
+                '\\' => {
+                    self.index += 1;
+                    if self.rest().starts_with("\r\n") {
+                        self.index += 2;
+                    } else if self.rest().starts_with(['\n', '\r']) {
+                        self.index += 1;
+                    } else if self.rest().is_empty() {
+                        self.error(start..self.index, "unexpected end of file after the line continuation character");
+                    } else {
+                        self.error(start..self.index, "unexpected character after the line continuation character");
+                    }
+                }
+                '"' | '\'' => self.string(start),
+                '0'..='9' => self.number(),
+                '.' if self.rest()[1..].starts_with(|character: char| character.is_ascii_digit()) => self.number(),
+                character if is_name_start(character) => self.name(),
+                _ => self.operator(),
+            }
+        }
+        if self.in_logical_line() {
+            self.push(TokenKind::Newline, self.index);
+        }
+        for (bracket, range) in std::mem::take(&mut self.brackets) {
+            self.error(range, format!("`{bracket}` was never closed"));
+        }
+        while let Some(level) = self.levels.pop() {
+            if self.levels.is_empty() {
+                break;
+            }
+            if !level.phantom {
+                self.push(TokenKind::Dedent, self.index);
+            }
+        }
+        self.push(TokenKind::EndOfFile, self.index);
+    }
+}
+
+pub fn tokenize(text: &str) -> (Vec<Token<'_>>, Vec<SyntaxError>) {
+    let mut tokenizer = Tokenizer {
+        text,
+        index: 0,
+        tokens: Vec::new(),
+        errors: Vec::new(),
+        levels: vec![Level { column: 0, alternative: 0, phantom: false }],
+        brackets: Vec::new(),
+    };
+    tokenizer.run();
+    (tokenizer.tokens, tokenizer.errors)
+}
@@ -0,0 +1,129 @@
+// the functions that the web worker calls, which hand their results to CodeMirror as JS objects
+use std::{cell::RefCell, ops::Range};
+
+use common::{completions::{Completion, Completions}, js::set, offsets::{byte_offset, utf16_offset, utf16_ranges}};
+use wasm_bindgen::prelude::*;
+
+use crate::{api::MessageDefinition, assist, infer::Library, symbols::{self, Symbol, SymbolKind}};
+
+// the diagnostics in the form that the lint source of CodeMirror returns
+#[wasm_bindgen]
+pub fn check(text: &str) -> js_sys::Array {
+    common::diagnostics::to_js(text, &crate::diagnostics(text))
+}
+
+// the classes and functions of the file with their ranges and the position of their names, the
+// symbols are nested in the order they appear in the file
+#[wasm_bindgen]
+pub fn symbols(text: &str) -> js_sys::Array {
+    fn byte_ranges(symbols: &[Symbol], ranges: &mut Vec<Range<usize>>) {
+        for symbol in symbols {
+            ranges.push(symbol.range.clone());
+            ranges.push(symbol.name_range.clone());
+            byte_ranges(&symbol.children, ranges);
+        }
+    }
+    fn to_js(symbols: &[Symbol], ranges: &mut impl Iterator<Item = Range<usize>>) -> js_sys::Array {
+        symbols.iter().map(|symbol| {
+            let (range, name_range) = (ranges.next().unwrap(), ranges.next().unwrap());
+            let object = js_sys::Object::new();
+            set(&object, "name", symbol.name);
+            set(&object, "kind", match symbol.kind {
+                SymbolKind::Class => "class",
+                SymbolKind::Method => "method",
+                SymbolKind::Function => "function",
+            });
+            set(&object, "from", range.start as u32);
+            set(&object, "to", range.end as u32);
+            set(&object, "selection", name_range.start as u32);
+            set(&object, "children", to_js(&symbol.children, ranges));
+            object
+        }).collect()
+    }
+    let symbols = symbols::symbols(text);
+    let mut ranges = Vec::new();
+    byte_ranges(&symbols, &mut ranges);
+    to_js(&symbols, &mut utf16_ranges(text, &ranges).into_iter())
+}
+
+thread_local! {
+    // the package, name and text of the `.msg` files of the project
+    static MESSAGES: RefCell<Vec<(String, String, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+// replaces the message types of the project, which the editor passes as objects with a package, a
+// name and the text of the `.msg` file whenever one of the files changes
+#[wasm_bindgen(js_name = setMessages)]
+pub fn set_messages(messages: js_sys::Array) {
+    let messages = messages.iter().map(|message| {
+        let get = |key: &str| js_sys::Reflect::get(&message, &key.into()).ok().and_then(|value| value.as_string()).unwrap_or_default();
+        (get("package"), get("name"), get("text"))
+    }).collect();
+    MESSAGES.with(|current| current.replace(messages));
+}
+
+// the library with the message types of the project
+fn with_library<T>(f: impl FnOnce(&Library) -> T) -> T {
+    MESSAGES.with(|messages| {
+        let messages = messages.borrow();
+        let definitions = messages.iter()
+            .map(|(package, name, text)| MessageDefinition { package, name, text })
+            .collect::<Vec<_>>();
+        f(&Library::new(&definitions))
+    })
+}
+
+// the completions in the form that the completion sources of CodeMirror return, or null
+#[wasm_bindgen]
+pub fn complete(text: &str, position: u32, explicit: bool) -> JsValue {
+    let position = byte_offset(text, position);
+    let Some(completions) = with_library(|library| assist::complete(text, position, explicit, library)) else {
+        return JsValue::NULL;
+    };
+    let options = completions.options.into_iter().map(|completion| Completion {
+        detail: completion.detail,
+        info: Some(completion.info).filter(|info| !info.is_empty()),
+        ..Completion::new(completion.label, completion.kind.completion_kind())
+    }).collect();
+    // there are no snippets among the completions of Python files
+    Completions { from: completions.from, options, valid_for: r"^\w*$" }.to_js(text, |_, option| option.into())
+}
+
+// the documentation of the name at the position with its range, or null
+#[wasm_bindgen]
+pub fn hover(text: &str, position: u32) -> JsValue {
+    let position = byte_offset(text, position);
+    let Some(hover) = with_library(|library| assist::hover(text, position, library)) else {
+        return JsValue::NULL;
+    };
+    let object = js_sys::Object::new();
+    set(&object, "from", utf16_offset(text, hover.range.start) as u32);
+    set(&object, "to", utf16_offset(text, hover.range.end) as u32);
+    set(&object, "signature", hover.signature.as_str());
+    set(&object, "description", hover.description.as_str());
+    object.into()
+}
+
+// the signature of the call around the position, the ranges of the parameters are in bytes of
+// the label, or null
+#[wasm_bindgen(js_name = signatureHelp)]
+pub fn signature_help(text: &str, position: u32) -> JsValue {
+    let position = byte_offset(text, position);
+    let Some(help) = with_library(|library| assist::signature_help(text, position, library)) else {
+        return JsValue::NULL;
+    };
+    let parameters = help.signature.parameters.iter().map(|parameter| {
+        let object = js_sys::Object::new();
+        set(&object, "from", parameter.range.start as u32);
+        set(&object, "to", parameter.range.end as u32);
+        set(&object, "description", parameter.description.as_str());
+        object
+    }).collect::<js_sys::Array>();
+    let object = js_sys::Object::new();
+    set(&object, "position", utf16_offset(text, help.position) as u32);
+    set(&object, "label", help.signature.label.as_str());
+    set(&object, "parameters", parameters);
+    set(&object, "active", help.active.map_or(JsValue::NULL, |active| (active as u32).into()));
+    set(&object, "description", help.signature.description.as_str());
+    object.into()
+}
@@ -0,0 +1,22 @@
+// checks, completes and outlines Python files off the main thread, each response carries the id of
+// its request and a request that fails is answered with null
+import { check, complete, hover, setMessages, signatureHelp, symbols } from './python.js';
+
+self.onmessage = (event) => {
+    const { id, request, text, position, explicit, messages } = event.data;
+    let result = null;
+    try {
+        switch (request) {
+            case 'check': result = check(text); break;
+            case 'complete': result = complete(text, position, explicit); break;
+            case 'hover': result = hover(text, position); break;
+            case 'signatureHelp': result = signatureHelp(text, position); break;
+            case 'symbols': result = symbols(text); break;
+            // kept for the following requests until the message types change again
+            case 'setMessages': setMessages(messages); break;
+        }
+    } catch (error) {
+        console.error(error);
+    }
+    self.postMessage({ id, result: result ?? null });
+};
@@ -43,12 +43,15 @@ def build():
         stdout=subprocess.DEVNULL,
         stderr=subprocess.DEVNULL)
     ### Run cargo build
+    # the target is only set here so that the tests run on the host with `cargo test --workspace`
+    # in the crates directory
     print('Building with Cargo')
     cargo_args = [
         'cargo',
-        'build'
+        'build',
+        '--target', 'wasm32-unknown-unknown'
     ]
-    for package in ['frontend']:
+    for package in ['frontend', 'python']:
         cargo_args.extend(['--package', package])
     cargo_proc = subprocess.Popen(cargo_args, cwd='crates')
     npm_proc.wait()
@@ -61,7 +64,7 @@ def build():
     print('Generating bindings')
     artifact_path = '{}/artifacts/debug'.format(build_path)
     wasm_bindgen_procs = []
-    for package in ['frontend']:
+    for package in ['frontend', 'python']:
         wasm_bindgen_args = [
             'wasm-bindgen',
             '--reference-types',
@@ -74,6 +77,8 @@ def build():
         wasm_bindgen_proc.wait()
         if wasm_bindgen_proc.returncode != 0:
             raise RuntimeError('wasm-bindgen terminated with {}'.format(wasm_bindgen_proc.returncode))
+    # the worker that checks Python files imports the bindings of the python crate next to it
+    shutil.copy(crates_path + '/python/worker.js', artifact_path + '/python_worker.js')
     ### Prepare output 
     dist_path = '{}/dist/debug'.format(build_path)
     shutil.rmtree(dist_path, ignore_errors=True)